@@ -0,0 +1,228 @@
+//! Utilities for timezone aware chrono [DateTime](https://docs.rs/chrono/0.4.35/chrono/struct.DateTime.html)s
+use crate::naive::datetime::{DateTimeTransitions, EndConvention};
+use chrono::{DateTime, LocalResult, NaiveDateTime, Offset, TimeDelta, TimeZone};
+
+pub mod recurrence;
+
+/// What to do with a local time falling in a gap of the timezone, such as 02:30 on the day
+/// clocks move forward from 02:00 to 03:00
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Nonexistent {
+    /// Leave out the local time
+    Skip,
+    /// Move the local time forward by the length of the gap, as RFC 5545 does. 02:30 becomes
+    /// 03:30 when clocks move forward by an hour at 02:00.
+    Shift,
+}
+
+/// What to do with a local time occurring twice in the timezone, such as 02:30 on the day
+/// clocks move back from 03:00 to 02:00
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ambiguous {
+    /// Pick the first of the two instants, before clocks move back
+    Earliest,
+    /// Pick the second of the two instants, after clocks move back
+    Latest,
+    /// Leave out the local time
+    Skip,
+}
+
+/// Returns the instant at which the clocks of the timezone show the local time, resolving gaps
+/// and overlaps as per the policies
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{FixedOffset, NaiveDate, TimeZone};
+/// use chrono_utilities::aware::{from_local, Ambiguous, Nonexistent};
+///
+/// let tz = FixedOffset::east_opt(3600).unwrap();
+/// let local = NaiveDate::from_ymd_opt(2021, 3, 28).unwrap().and_hms_opt(2, 30, 0).unwrap();
+/// let dt = from_local(&tz, local, Nonexistent::Shift, Ambiguous::Earliest).unwrap();
+/// assert_eq!(dt, tz.from_local_datetime(&local).unwrap());
+pub fn from_local<Tz: TimeZone>(
+    timezone: &Tz,
+    local: NaiveDateTime,
+    nonexistent: Nonexistent,
+    ambiguous: Ambiguous,
+) -> Option<DateTime<Tz>> {
+    match timezone.from_local_datetime(&local) {
+        LocalResult::Single(dt) => Some(dt),
+        LocalResult::Ambiguous(earliest, latest) => match ambiguous {
+            Ambiguous::Earliest => Some(earliest),
+            Ambiguous::Latest => Some(latest),
+            Ambiguous::Skip => None,
+        },
+        LocalResult::None => match nonexistent {
+            Nonexistent::Skip => None,
+            Nonexistent::Shift => {
+                // Read the local time with the offset in effect before the gap
+                let before = local.checked_sub_signed(TimeDelta::days(1))?;
+                let offset = timezone.offset_from_utc_datetime(&before).fix();
+                let utc = local.checked_sub_offset(offset)?;
+                Some(timezone.from_utc_datetime(&utc))
+            }
+        },
+    }
+}
+
+/// Returns the instant at which the clocks of the timezone of the datetime show the local time.
+/// Of the two instants of an ambiguous local time, the one sharing the offset of the datetime is
+/// preferred, and local times in a gap are shifted as per
+/// [Nonexistent::Shift](enum.Nonexistent.html#variant.Shift).
+fn with_local<Tz: TimeZone>(dt: &DateTime<Tz>, local: NaiveDateTime) -> Option<DateTime<Tz>> {
+    let timezone = dt.timezone();
+    match timezone.from_local_datetime(&local) {
+        LocalResult::Ambiguous(earliest, latest) => {
+            if latest.offset().fix() == dt.offset().fix() {
+                Some(latest)
+            } else {
+                Some(earliest)
+            }
+        }
+        _ => from_local(&timezone, local, Nonexistent::Shift, Ambiguous::Earliest),
+    }
+}
+
+/// Returns the datetime rounded by the function, which is given its local time at its offset.
+/// The rounded local time is read back at the same offset, so that the rounding keeps the order
+/// of instants.
+fn round_instant<Tz, F>(dt: &DateTime<Tz>, round: F) -> Option<DateTime<Tz>>
+where
+    Tz: TimeZone,
+    F: FnOnce(NaiveDateTime) -> Option<NaiveDateTime>,
+{
+    let offset = dt.offset().fix();
+    let utc = round(dt.naive_local())?.checked_sub_offset(offset)?;
+    Some(dt.timezone().from_utc_datetime(&utc))
+}
+
+/// Transitions of timezone aware datetimes, carried out on their local time. Local times which
+/// are ambiguous resolve to the instant sharing the offset of the datetime when possible, and
+/// local times falling in a gap are shifted forward by the length of the gap.
+///
+/// Rounding is carried out on the instant rather than on the local time, with multiples of the
+/// interval aligned on the local time at the offset of the datetime. Rounding down thus never
+/// moves a datetime forward, nor rounding up backward, even next to a gap.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{FixedOffset, TimeZone};
+/// use chrono_utilities::naive::datetime::{DateTimeTransitions, EndConvention};
+///
+/// let tz = FixedOffset::east_opt(5 * 3600 + 45 * 60).unwrap();
+/// let dt = tz.with_ymd_and_hms(2020, 2, 14, 17, 30, 5).unwrap();
+/// assert_eq!(dt.start_of_day(), tz.with_ymd_and_hms(2020, 2, 14, 0, 0, 0).single());
+/// assert_eq!(dt.start_of_hour(), tz.with_ymd_and_hms(2020, 2, 14, 17, 0, 0).single());
+/// assert_eq!(dt.end_of_minute(EndConvention::Exclusive), tz.with_ymd_and_hms(2020, 2, 14, 17, 31, 0).single());
+impl<Tz: TimeZone> DateTimeTransitions for DateTime<Tz> {
+    #[inline]
+    fn start_of_day(&self) -> Option<Self> {
+        with_local(self, self.naive_local().start_of_day()?)
+    }
+
+    #[inline]
+    fn end_of_day(&self, convention: EndConvention) -> Option<Self> {
+        with_local(self, self.naive_local().end_of_day(convention)?)
+    }
+
+    #[inline]
+    fn start_of_hour(&self) -> Option<Self> {
+        with_local(self, self.naive_local().start_of_hour()?)
+    }
+
+    #[inline]
+    fn end_of_hour(&self, convention: EndConvention) -> Option<Self> {
+        with_local(self, self.naive_local().end_of_hour(convention)?)
+    }
+
+    #[inline]
+    fn start_of_minute(&self) -> Option<Self> {
+        with_local(self, self.naive_local().start_of_minute()?)
+    }
+
+    #[inline]
+    fn end_of_minute(&self, convention: EndConvention) -> Option<Self> {
+        with_local(self, self.naive_local().end_of_minute(convention)?)
+    }
+
+    #[inline]
+    fn start_of_second(&self) -> Option<Self> {
+        with_local(self, self.naive_local().start_of_second()?)
+    }
+
+    #[inline]
+    fn end_of_second(&self, convention: EndConvention) -> Option<Self> {
+        with_local(self, self.naive_local().end_of_second(convention)?)
+    }
+
+    #[inline]
+    fn floor_to(&self, interval: TimeDelta) -> Option<Self> {
+        round_instant(self, |local| local.floor_to(interval))
+    }
+
+    #[inline]
+    fn ceil_to(&self, interval: TimeDelta) -> Option<Self> {
+        round_instant(self, |local| local.ceil_to(interval))
+    }
+
+    #[inline]
+    fn round_to(&self, interval: TimeDelta) -> Option<Self> {
+        round_instant(self, |local| local.round_to(interval))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::recurrence::tests::Berlin2021;
+    use crate::naive::datetime::{DateTimeTransitions, EndConvention};
+    use chrono::{LocalResult, NaiveDate, TimeDelta, TimeZone};
+
+    #[test]
+    fn test_transitions_keep_offset_when_ambiguous() {
+        // 02:00 to 03:00 happens twice on October 31
+        let local = NaiveDate::from_ymd_opt(2021, 10, 31)
+            .unwrap()
+            .and_hms_opt(2, 45, 0)
+            .unwrap();
+        let (summer, winter) = match Berlin2021.from_local_datetime(&local) {
+            LocalResult::Ambiguous(summer, winter) => (summer, winter),
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            winter.start_of_hour().unwrap(),
+            winter - TimeDelta::minutes(45)
+        );
+        assert_eq!(
+            summer.start_of_hour().unwrap(),
+            summer - TimeDelta::minutes(45)
+        );
+        let end = winter.end_of_hour(EndConvention::Exclusive).unwrap();
+        assert_eq!(end - winter, TimeDelta::minutes(15));
+        // The day lasts 25 hours
+        let start = winter.start_of_day().unwrap();
+        let end = winter.end_of_day(EndConvention::Exclusive).unwrap();
+        assert_eq!(end - start, TimeDelta::hours(25));
+    }
+
+    #[test]
+    fn test_rounding_keeps_order_around_gap() {
+        // Clocks move forward from 02:00 to 03:00 on March 28
+        let start = Berlin2021.with_ymd_and_hms(2021, 3, 28, 0, 30, 0).unwrap();
+        for minutes in (0..240).step_by(5) {
+            let dt = start + TimeDelta::minutes(minutes);
+            for interval in [15, 45, 60, 75, 120].iter() {
+                let interval = TimeDelta::minutes(*interval);
+                let floor = dt.floor_to(interval).unwrap();
+                let ceil = dt.ceil_to(interval).unwrap();
+                let round = dt.round_to(interval).unwrap();
+                assert!(floor <= dt && dt <= ceil);
+                assert!(floor <= round && round <= ceil);
+                assert!(ceil - floor <= interval);
+            }
+        }
+        let dt = Berlin2021.with_ymd_and_hms(2021, 3, 28, 3, 10, 0).unwrap();
+        assert!(dt.floor_to(TimeDelta::minutes(75)).unwrap() <= dt);
+    }
+}
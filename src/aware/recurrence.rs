@@ -0,0 +1,243 @@
+//! Recurrences of a local time of day in a timezone
+use super::{from_local, Ambiguous, Nonexistent};
+use crate::naive::recurrence::{Occurrences, Recurrence};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone};
+
+/// A [Recurrence](../../naive/recurrence/struct.Recurrence.html) of dates paired with a local
+/// time of day in a timezone. Every occurrence shows the same wall clock time, whatever the
+/// daylight saving time in effect, so that 09:00 stays 09:00 when clocks change. Local times
+/// falling in a gap or an overlap of the timezone are resolved as per the `nonexistent` and
+/// `ambiguous` policies, which shift them forward and pick the earliest instant by default.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone};
+/// use chrono_utilities::aware::recurrence::ZonedRecurrence;
+/// use chrono_utilities::naive::recurrence::Recurrence;
+///
+/// let tz = FixedOffset::east_opt(3600).unwrap();
+/// let every_day = ZonedRecurrence::new(Recurrence::daily(), NaiveTime::from_hms_opt(9, 0, 0).unwrap(), tz);
+/// let next: Vec<_> = every_day.occurrences(NaiveDate::from_ymd_opt(2021, 3, 27).unwrap()).take(2).collect();
+/// assert_eq!(next, vec![
+///     tz.with_ymd_and_hms(2021, 3, 27, 9, 0, 0).unwrap(),
+///     tz.with_ymd_and_hms(2021, 3, 28, 9, 0, 0).unwrap(),
+/// ]);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZonedRecurrence<Tz: TimeZone> {
+    /// The rule picking the dates of the occurrences
+    pub rule: Recurrence,
+    /// The local time of day of the occurrences
+    pub time: NaiveTime,
+    /// The timezone of the local time
+    pub timezone: Tz,
+    /// What to do with local times falling in a gap of the timezone
+    pub nonexistent: Nonexistent,
+    /// What to do with local times occurring twice in the timezone
+    pub ambiguous: Ambiguous,
+}
+
+impl<Tz: TimeZone> ZonedRecurrence<Tz> {
+    /// Creates a recurrence of the local time on the dates of the rule, shifting nonexistent
+    /// local times forward and picking the earliest instant of ambiguous ones
+    pub fn new(rule: Recurrence, time: NaiveTime, timezone: Tz) -> Self {
+        ZonedRecurrence {
+            rule,
+            time,
+            timezone,
+            nonexistent: Nonexistent::Shift,
+            ambiguous: Ambiguous::Earliest,
+        }
+    }
+
+    /// Returns the recurrence resolving local times falling in a gap of the timezone as per the
+    /// policy
+    pub fn on_nonexistent(mut self, nonexistent: Nonexistent) -> Self {
+        self.nonexistent = nonexistent;
+        self
+    }
+
+    /// Returns the recurrence resolving local times occurring twice in the timezone as per the
+    /// policy
+    pub fn on_ambiguous(mut self, ambiguous: Ambiguous) -> Self {
+        self.ambiguous = ambiguous;
+        self
+    }
+
+    /// Returns an iterator over the occurrences of the recurrence, starting from the given date,
+    /// in chronological order. The count of the rule applies to the dates of the occurrences,
+    /// including those left out by the policies.
+    pub fn occurrences(&self, start: NaiveDate) -> ZonedOccurrences<Tz> {
+        ZonedOccurrences {
+            dates: self.rule.occurrences(start),
+            time: self.time,
+            timezone: self.timezone.clone(),
+            nonexistent: self.nonexistent,
+            ambiguous: self.ambiguous,
+        }
+    }
+}
+
+/// Iterator over the occurrences of a [ZonedRecurrence](struct.ZonedRecurrence.html)
+#[derive(Debug, Clone)]
+pub struct ZonedOccurrences<Tz: TimeZone> {
+    dates: Occurrences,
+    time: NaiveTime,
+    timezone: Tz,
+    nonexistent: Nonexistent,
+    ambiguous: Ambiguous,
+}
+
+impl<Tz: TimeZone> Iterator for ZonedOccurrences<Tz> {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<DateTime<Tz>> {
+        loop {
+            let local = self.dates.next()?.and_time(self.time);
+            if let Some(dt) = from_local(&self.timezone, local, self.nonexistent, self.ambiguous) {
+                return Some(dt);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::ZonedRecurrence;
+    use crate::aware::{Ambiguous, Nonexistent};
+    use crate::naive::recurrence::Recurrence;
+    use chrono::{
+        FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike,
+        Weekday,
+    };
+
+    /// Central European time in 2021, with summer time from March 28 to October 31
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) struct Berlin2021;
+
+    impl Berlin2021 {
+        fn offset(hours: i32) -> FixedOffset {
+            FixedOffset::east_opt(hours * 3600).unwrap()
+        }
+    }
+
+    impl TimeZone for Berlin2021 {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            Berlin2021
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            let valid: Vec<FixedOffset> = [Self::offset(2), Self::offset(1)]
+                .iter()
+                .copied()
+                .filter(|offset| {
+                    let utc = *local - offset.fix();
+                    self.offset_from_utc_datetime(&utc) == *offset
+                })
+                .collect();
+            match valid[..] {
+                [offset] => LocalResult::Single(offset),
+                [earliest, latest] => LocalResult::Ambiguous(earliest, latest),
+                _ => LocalResult::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            let at = |month, day| {
+                NaiveDate::from_ymd_opt(2021, month, day)
+                    .unwrap()
+                    .and_hms_opt(1, 0, 0)
+                    .unwrap()
+            };
+            if at(3, 28) <= *utc && *utc < at(10, 31) {
+                Self::offset(2)
+            } else {
+                Self::offset(1)
+            }
+        }
+    }
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_wall_time_across_transitions() {
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let rule = ZonedRecurrence::new(Recurrence::daily(), nine, Berlin2021);
+        let spring: Vec<_> = rule.occurrences(ymd(2021, 3, 27)).take(2).collect();
+        let autumn: Vec<_> = rule.occurrences(ymd(2021, 10, 30)).take(2).collect();
+        for dt in spring.iter().chain(autumn.iter()) {
+            assert_eq!(dt.naive_local().time(), nine);
+        }
+        assert_eq!(spring[1] - spring[0], chrono::TimeDelta::hours(23));
+        assert_eq!(autumn[1] - autumn[0], chrono::TimeDelta::hours(25));
+    }
+
+    #[test]
+    fn test_policies() {
+        let half_past_two = NaiveTime::from_hms_opt(2, 30, 0).unwrap();
+        let rule = ZonedRecurrence::new(Recurrence::daily(), half_past_two, Berlin2021);
+        // 02:30 does not exist on March 28 and happens twice on October 31
+        let shifted: Vec<_> = rule.occurrences(ymd(2021, 3, 28)).take(1).collect();
+        assert_eq!(shifted[0].naive_local().hour(), 3);
+        let skipped: Vec<_> = rule
+            .clone()
+            .on_nonexistent(Nonexistent::Skip)
+            .occurrences(ymd(2021, 3, 28))
+            .take(1)
+            .collect();
+        assert_eq!(skipped[0].date_naive(), ymd(2021, 3, 29));
+        let earliest = rule.occurrences(ymd(2021, 10, 31)).next().unwrap();
+        let latest = rule
+            .clone()
+            .on_ambiguous(Ambiguous::Latest)
+            .occurrences(ymd(2021, 10, 31))
+            .next()
+            .unwrap();
+        assert_eq!(latest - earliest, chrono::TimeDelta::hours(1));
+        let skipped = rule
+            .on_ambiguous(Ambiguous::Skip)
+            .occurrences(ymd(2021, 10, 31))
+            .next()
+            .unwrap();
+        assert_eq!(skipped.date_naive(), ymd(2021, 11, 1));
+    }
+
+    #[test]
+    fn test_count_with_several_weekdays() {
+        let half_past_two = NaiveTime::from_hms_opt(2, 30, 0).unwrap();
+        let long_weekends = Recurrence::weekly()
+            .on(Weekday::Sat)
+            .on(Weekday::Sun)
+            .on(Weekday::Mon)
+            .times(4);
+        let rule = ZonedRecurrence::new(long_weekends, half_past_two, Berlin2021);
+        let dates = |rule: ZonedRecurrence<Berlin2021>| -> Vec<NaiveDate> {
+            rule.occurrences(ymd(2021, 3, 27))
+                .take(10)
+                .map(|dt| dt.date_naive())
+                .collect()
+        };
+        let expected = vec![
+            ymd(2021, 3, 27),
+            ymd(2021, 3, 28),
+            ymd(2021, 3, 29),
+            ymd(2021, 4, 3),
+        ];
+        assert_eq!(dates(rule.clone()), expected);
+        // The skipped 02:30 of March 28 still counts toward the four occurrences
+        let skipped = dates(rule.on_nonexistent(Nonexistent::Skip));
+        assert_eq!(skipped, vec![expected[0], expected[2], expected[3]]);
+    }
+}
@@ -1,6 +1,8 @@
 //! Provides utility functions to manipulate [chrono](https://github.com/chronotope/chrono/) dates.
-//! Only [NaiveDate](https://docs.rs/chrono/0.4.11/chrono/naive/struct.NaiveDate.html) is
-//! supported as of now. Support for naive and timezone aware DateTime coming soon.
+//! Transitions are provided for [NaiveDate](https://docs.rs/chrono/0.4.35/chrono/naive/struct.NaiveDate.html)
+//! in the [naive](naive/index.html) module, for `NaiveDateTime` in
+//! [naive::datetime](naive/datetime/index.html), and for timezone aware `DateTime` in the
+//! [aware](aware/index.html) module.
 //!
 //! The crate provides the following:
 //!
@@ -8,10 +10,18 @@
 //! Transition a chrono struct into a future or previous date using standardised methods
 //! like `start_of_pred_iso8601_week()` which provides the date on which the previous week
 //! starts. Such functions are provided for week, month and year.
+//!
+//! **Range APIs**
+//! Walk over a span of dates a fixed number of days, weeks or months at a time using
+//! [DateRange](naive/range/struct.DateRange.html).
+//!
+//! **Timezone aware APIs**
+//! Repeat a local time of day in a timezone, keeping the wall clock time stable across daylight
+//! saving time transitions, using [ZonedRecurrence](aware/recurrence/struct.ZonedRecurrence.html).
 
 extern crate chrono;
-extern crate time as oldtime;
 
+pub mod aware;
 pub mod naive;
 
 
@@ -21,35 +31,36 @@ mod tests {
     use crate::naive::DateTransitions;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_api_interface() {
-        let d1 = NaiveDate::from_ymd(1996, 2, 23);
+        let d1 = NaiveDate::from_ymd_opt(1996, 2, 23).unwrap();
         // Month
-        assert_eq!(d1.end_of_month().unwrap(), NaiveDate::from_ymd(1996, 2, 29));
-        assert_eq!(d1.start_of_month().unwrap(), NaiveDate::from_ymd(1996, 2, 1));
-        assert_eq!(d1.end_of_pred_month().unwrap(), NaiveDate::from_ymd(1996, 1, 31));
-        assert_eq!(d1.start_of_pred_month().unwrap(), NaiveDate::from_ymd(1996, 1, 1));
-        assert_eq!(d1.end_of_succ_month().unwrap(), NaiveDate::from_ymd(1996, 3, 31));
-        assert_eq!(d1.start_of_succ_month().unwrap(), NaiveDate::from_ymd(1996, 3, 1));
+        assert_eq!(d1.end_of_month().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 29).unwrap());
+        assert_eq!(d1.start_of_month().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 1).unwrap());
+        assert_eq!(d1.end_of_pred_month().unwrap(), NaiveDate::from_ymd_opt(1996, 1, 31).unwrap());
+        assert_eq!(d1.start_of_pred_month().unwrap(), NaiveDate::from_ymd_opt(1996, 1, 1).unwrap());
+        assert_eq!(d1.end_of_succ_month().unwrap(), NaiveDate::from_ymd_opt(1996, 3, 31).unwrap());
+        assert_eq!(d1.start_of_succ_month().unwrap(), NaiveDate::from_ymd_opt(1996, 3, 1).unwrap());
 
         // Year
-        assert_eq!(d1.end_of_year().unwrap(), NaiveDate::from_ymd(1996, 12, 31));
-        assert_eq!(d1.start_of_year().unwrap(), NaiveDate::from_ymd(1996, 1, 1));
-        assert_eq!(d1.end_of_pred_year().unwrap(), NaiveDate::from_ymd(1995, 12, 31));
-        assert_eq!(d1.start_of_pred_year().unwrap(), NaiveDate::from_ymd(1995, 1, 1));
-        assert_eq!(d1.end_of_succ_year().unwrap(), NaiveDate::from_ymd(1997, 12, 31));
-        assert_eq!(d1.start_of_succ_year().unwrap(), NaiveDate::from_ymd(1997, 1, 1));
+        assert_eq!(d1.end_of_year().unwrap(), NaiveDate::from_ymd_opt(1996, 12, 31).unwrap());
+        assert_eq!(d1.start_of_year().unwrap(), NaiveDate::from_ymd_opt(1996, 1, 1).unwrap());
+        assert_eq!(d1.end_of_pred_year().unwrap(), NaiveDate::from_ymd_opt(1995, 12, 31).unwrap());
+        assert_eq!(d1.start_of_pred_year().unwrap(), NaiveDate::from_ymd_opt(1995, 1, 1).unwrap());
+        assert_eq!(d1.end_of_succ_year().unwrap(), NaiveDate::from_ymd_opt(1997, 12, 31).unwrap());
+        assert_eq!(d1.start_of_succ_year().unwrap(), NaiveDate::from_ymd_opt(1997, 1, 1).unwrap());
 
         // ISO 8601 Week
-        assert_eq!(d1.end_of_iso8601_week().unwrap(), NaiveDate::from_ymd(1996, 2, 25));
-        assert_eq!(d1.start_of_iso8601_week().unwrap(), NaiveDate::from_ymd(1996, 2, 19));
-        assert_eq!(d1.end_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd(1996, 2, 18));
-        assert_eq!(d1.start_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd(1996, 2, 12));
-        assert_eq!(d1.end_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd(1996, 3, 3));
-        assert_eq!(d1.start_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd(1996, 2, 26));
+        assert_eq!(d1.end_of_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 25).unwrap());
+        assert_eq!(d1.start_of_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 19).unwrap());
+        assert_eq!(d1.end_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 18).unwrap());
+        assert_eq!(d1.start_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 12).unwrap());
+        assert_eq!(d1.end_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1996, 3, 3).unwrap());
+        assert_eq!(d1.start_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 26).unwrap());
 
         // Leap year
         assert_eq!(d1.is_leap_year(), true);
-        let d2 = NaiveDate::from_ymd(1900, 7, 4);
+        let d2 = NaiveDate::from_ymd_opt(1900, 7, 4).unwrap();
         assert_eq!(d2.is_leap_year(), false);
     }
 }
@@ -0,0 +1,258 @@
+//! Academic calendars made of terms recurring every year, such as semesters, whose start and end
+//! dates follow rules like "the first Monday of September"
+use super::range::DateRange;
+use super::weekday::{last_weekday_of_month, nth_weekday_of_month};
+use super::DateTransitions;
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// A rule giving a date in every year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnnualDate {
+    /// The given day of the given month
+    Fixed { month: u32, day: u32 },
+    /// The `n`th occurrence, counting from 1, of the weekday in the given month
+    NthWeekday {
+        month: u32,
+        weekday: Weekday,
+        n: u32,
+    },
+    /// The last occurrence of the weekday in the given month
+    LastWeekday { month: u32, weekday: Weekday },
+}
+
+impl AnnualDate {
+    /// Returns the date given by the rule in the year, or `None` if there is no such date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::academic::AnnualDate;
+    ///
+    /// let rule = AnnualDate::NthWeekday { month: 9, weekday: Weekday::Mon, n: 1 };
+    /// assert_eq!(rule.in_year(2020), NaiveDate::from_ymd_opt(2020, 9, 7));
+    /// let rule = AnnualDate::Fixed { month: 2, day: 29 };
+    /// assert_eq!(rule.in_year(2021), None);
+    pub fn in_year(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            AnnualDate::Fixed { month, day } => NaiveDate::from_ymd_opt(year, month, day),
+            AnnualDate::NthWeekday { month, weekday, n } => {
+                nth_weekday_of_month(year, month, weekday, n)
+            }
+            AnnualDate::LastWeekday { month, weekday } => {
+                last_weekday_of_month(year, month, weekday)
+            }
+        }
+    }
+}
+
+/// The definition of a term recurring every year
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TermRule {
+    name: String,
+    start: AnnualDate,
+    end: AnnualDate,
+}
+
+/// A calendar of named terms recurring every year. Every term starts on the date given by its
+/// start rule and ends on the first date given by its end rule falling on or after its start,
+/// which may be in the next year. A term is identified by its name and the year in which it
+/// starts.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, Weekday};
+/// use chrono_utilities::naive::academic::{AnnualDate, TermCalendar};
+///
+/// let cal = TermCalendar::new()
+///     .term(
+///         "Fall",
+///         AnnualDate::NthWeekday { month: 9, weekday: Weekday::Tue, n: 1 },
+///         AnnualDate::Fixed { month: 12, day: 18 },
+///     )
+///     .term(
+///         "Spring",
+///         AnnualDate::NthWeekday { month: 1, weekday: Weekday::Mon, n: 3 },
+///         AnnualDate::LastWeekday { month: 5, weekday: Weekday::Fri },
+///     );
+/// let d = NaiveDate::from_ymd_opt(2020, 10, 14).unwrap();
+/// let term = cal.term_of(d).unwrap();
+/// assert_eq!(term.name(), "Fall");
+/// assert_eq!(term.dates().start(), NaiveDate::from_ymd_opt(2020, 9, 1).unwrap());
+/// assert_eq!(term.dates().end(), NaiveDate::from_ymd_opt(2020, 12, 18).unwrap());
+/// assert_eq!(term.teaching_week(d), Some(7));
+/// assert_eq!(cal.teaching_week(d), Some(7));
+/// // Winter break
+/// assert!(cal.term_of(NaiveDate::from_ymd_opt(2021, 1, 4).unwrap()).is_none());
+/// let spring = cal.term_dates("Spring", 2021).unwrap();
+/// assert_eq!(spring.start(), NaiveDate::from_ymd_opt(2021, 1, 18).unwrap());
+/// assert_eq!(spring.end(), NaiveDate::from_ymd_opt(2021, 5, 28).unwrap());
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TermCalendar {
+    terms: Vec<TermRule>,
+}
+
+/// A term of a [TermCalendar](struct.TermCalendar.html) in a given year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Term<'a> {
+    name: &'a str,
+    year: i32,
+    dates: DateRange,
+}
+
+impl Term<'_> {
+    /// Returns the name of the term
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Returns the year in which the term starts
+    #[inline]
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the dates spanned by the term
+    #[inline]
+    pub fn dates(&self) -> DateRange {
+        self.dates
+    }
+
+    /// Returns the teaching week of the date within the term, or `None` if the date lies
+    /// outside the term. Week 1 is the ISO 8601 week, from Monday to Sunday, in which the term
+    /// starts.
+    pub fn teaching_week(&self, date: NaiveDate) -> Option<u32> {
+        if !self.dates.contains(date) {
+            return None;
+        }
+        let first_monday = self.dates.start().start_of_iso8601_week()?;
+        let days = date.signed_duration_since(first_monday).num_days();
+        Some(days as u32 / 7 + 1)
+    }
+}
+
+impl TermCalendar {
+    /// Creates a calendar without terms
+    #[inline]
+    pub fn new() -> Self {
+        TermCalendar::default()
+    }
+
+    /// Returns the calendar with an additional term starting and ending as per the given rules
+    pub fn term(mut self, name: &str, start: AnnualDate, end: AnnualDate) -> Self {
+        self.terms.push(TermRule {
+            name: name.to_string(),
+            start,
+            end,
+        });
+        self
+    }
+
+    /// Returns the names of the terms, in the order they were added
+    pub fn term_names(&self) -> impl Iterator<Item = &str> {
+        self.terms.iter().map(|term| term.name.as_str())
+    }
+
+    /// Returns the dates of the named term starting in the given year, or `None` if there is no
+    /// such term or its rules give no date in that year
+    pub fn term_dates(&self, name: &str, year: i32) -> Option<DateRange> {
+        let rule = self.terms.iter().find(|term| term.name == name)?;
+        TermCalendar::dates_of(rule, year)
+    }
+
+    /// Returns the terms starting in the given year, in chronological order
+    pub fn terms_in(&self, year: i32) -> Vec<Term<'_>> {
+        let mut terms: Vec<Term<'_>> = self
+            .terms
+            .iter()
+            .filter_map(|rule| {
+                Some(Term {
+                    name: &rule.name,
+                    year,
+                    dates: TermCalendar::dates_of(rule, year)?,
+                })
+            })
+            .collect();
+        terms.sort_by_key(|term| term.dates.start());
+        terms
+    }
+
+    /// Returns the term the date falls in, or `None` if it falls outside every term. If terms
+    /// overlap, the one added first is returned.
+    pub fn term_of(&self, date: NaiveDate) -> Option<Term<'_>> {
+        self.terms.iter().find_map(|rule| {
+            [date.year(), date.year() - 1].iter().find_map(|&year| {
+                let dates = TermCalendar::dates_of(rule, year)?;
+                if dates.contains(date) {
+                    Some(Term {
+                        name: &rule.name,
+                        year,
+                        dates,
+                    })
+                } else {
+                    None
+                }
+            })
+        })
+    }
+
+    /// Returns the teaching week of the date within the term it falls in, or `None` if it falls
+    /// outside every term. See [Term::teaching_week](struct.Term.html#method.teaching_week).
+    #[inline]
+    pub fn teaching_week(&self, date: NaiveDate) -> Option<u32> {
+        self.term_of(date)?.teaching_week(date)
+    }
+
+    /// Returns the dates of the term starting in the given year
+    fn dates_of(rule: &TermRule, year: i32) -> Option<DateRange> {
+        let start = rule.start.in_year(year)?;
+        let end = rule
+            .end
+            .in_year(year)
+            .filter(|end| *end >= start)
+            .or_else(|| rule.end.in_year(year.checked_add(1)?))?;
+        DateRange::new(start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnnualDate, TermCalendar};
+    use chrono::{NaiveDate, Weekday};
+
+    #[test]
+    fn test_term_across_year_end() {
+        let cal = TermCalendar::new().term(
+            "Winter",
+            AnnualDate::NthWeekday {
+                month: 11,
+                weekday: Weekday::Wed,
+                n: 1,
+            },
+            AnnualDate::LastWeekday {
+                month: 2,
+                weekday: Weekday::Fri,
+            },
+        );
+        let d = NaiveDate::from_ymd_opt(2021, 1, 11).unwrap();
+        let term = cal.term_of(d).unwrap();
+        assert_eq!(term.year(), 2020);
+        assert_eq!(
+            term.dates().start(),
+            NaiveDate::from_ymd_opt(2020, 11, 4).unwrap()
+        );
+        assert_eq!(
+            term.dates().end(),
+            NaiveDate::from_ymd_opt(2021, 2, 26).unwrap()
+        );
+        // Week 1 runs from Monday, November 2
+        assert_eq!(term.teaching_week(d), Some(11));
+        assert_eq!(
+            cal.teaching_week(NaiveDate::from_ymd_opt(2020, 11, 3).unwrap()),
+            None
+        );
+        assert_eq!(cal.terms_in(2020), vec![term]);
+    }
+}
@@ -0,0 +1,154 @@
+//! Aggregation of dated records into periods
+use super::period::{Cohort, Period, Unit, WeekConvention};
+use super::range::DateRange;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+/// Groups the records by the typed [period](../period/trait.Period.html) containing their date,
+/// keeping the records of every period in the order they were seen. Weeks are ISO 8601 weeks,
+/// see [group_by_cohort](fn.group_by_cohort.html) for other week conventions. Records whose
+/// period lies beyond the supported date range are dropped.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::aggregate::group_by_period;
+/// use chrono_utilities::naive::period::{Month, Quarter};
+///
+/// let records = vec![
+///     (NaiveDate::from_ymd_opt(2020, 3, 2).unwrap(), "a"),
+///     (NaiveDate::from_ymd_opt(2020, 2, 11).unwrap(), "b"),
+///     (NaiveDate::from_ymd_opt(2020, 3, 26).unwrap(), "c"),
+/// ];
+/// let months = group_by_period::<Month, _, _>(records.clone());
+/// assert_eq!(months[&Month::new(2020, 2).unwrap()], vec!["b"]);
+/// assert_eq!(months[&Month::new(2020, 3).unwrap()], vec!["a", "c"]);
+/// let quarters = group_by_period::<Quarter, _, _>(records);
+/// assert_eq!(quarters.len(), 1);
+///
+/// // Plain dates are grouped by pairing them with themselves
+/// let dates = vec![NaiveDate::from_ymd_opt(2020, 3, 2).unwrap()];
+/// let months = group_by_period::<Month, _, _>(dates.into_iter().map(|d| (d, d)));
+/// assert_eq!(months.len(), 1);
+pub fn group_by_period<P, I, T>(items: I) -> BTreeMap<P, Vec<T>>
+where
+    P: Period,
+    I: IntoIterator<Item = (NaiveDate, T)>,
+{
+    group_by(items, P::containing)
+}
+
+/// Groups the records by the [cohort](../period/struct.Cohort.html) of the given granularity
+/// containing their date, keeping the records of every cohort in the order they were seen.
+/// Weekly cohorts start on the first day of the week as per the week convention. Records whose
+/// cohort lies beyond the supported date range are dropped.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::aggregate::group_by_cohort;
+/// use chrono_utilities::naive::period::{Unit, WeekConvention};
+///
+/// // A Sunday followed by a Monday
+/// let records = vec![
+///     (NaiveDate::from_ymd_opt(2020, 8, 9).unwrap(), 1),
+///     (NaiveDate::from_ymd_opt(2020, 8, 10).unwrap(), 2),
+/// ];
+/// let us = group_by_cohort(records.clone(), Unit::Week, WeekConvention::US);
+/// assert_eq!(us.values().collect::<Vec<_>>(), vec![&vec![1, 2]]);
+/// let iso = group_by_cohort(records, Unit::Week, WeekConvention::ISO);
+/// assert_eq!(iso.values().collect::<Vec<_>>(), vec![&vec![1], &vec![2]]);
+/// let first = iso.keys().next().unwrap();
+/// assert_eq!(first.start(), NaiveDate::from_ymd_opt(2020, 8, 3).unwrap());
+pub fn group_by_cohort<I, T>(items: I, unit: Unit, week: WeekConvention) -> BTreeMap<Cohort, Vec<T>>
+where
+    I: IntoIterator<Item = (NaiveDate, T)>,
+{
+    group_by(items, |date| Cohort::of(date, unit, week))
+}
+
+/// Buckets the values into the typed [periods](../period/trait.Period.html) containing their
+/// date and aggregates the values of every period with the given closure, such as a sum, a mean
+/// or the last value. Values are passed to the closure in the order they were seen, and values
+/// whose period lies beyond the supported date range are dropped.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::aggregate::resample;
+/// use chrono_utilities::naive::period::Month;
+///
+/// let series = vec![
+///     (NaiveDate::from_ymd_opt(2020, 2, 11).unwrap(), 4.0),
+///     (NaiveDate::from_ymd_opt(2020, 3, 2).unwrap(), 1.0),
+///     (NaiveDate::from_ymd_opt(2020, 3, 26).unwrap(), 2.0),
+/// ];
+/// let sums = resample::<Month, _, _, _, _>(series.clone(), |values| values.iter().sum::<f64>());
+/// assert_eq!(sums.values().collect::<Vec<_>>(), vec![&4.0, &3.0]);
+/// let means = resample::<Month, _, _, _, _>(series.clone(), |values| values.iter().sum::<f64>() / values.len() as f64);
+/// assert_eq!(means[&Month::new(2020, 3).unwrap()], 1.5);
+/// let last = resample::<Month, _, _, _, _>(series, |values| *values.last().unwrap());
+/// assert_eq!(last[&Month::new(2020, 3).unwrap()], 2.0);
+pub fn resample<P, I, T, A, F>(items: I, mut aggregate: F) -> BTreeMap<P, A>
+where
+    P: Period,
+    I: IntoIterator<Item = (NaiveDate, T)>,
+    F: FnMut(Vec<T>) -> A,
+{
+    group_by_period(items)
+        .into_iter()
+        .map(|(period, values)| (period, aggregate(values)))
+        .collect()
+}
+
+/// Inserts the periods overlapping the range which are missing from the series, with the value
+/// returned by the closure for each of them, so that the series has no holes over the range.
+/// Periods already in the series are left untouched.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::aggregate::{fill_missing_periods, resample};
+/// use chrono_utilities::naive::period::{Month, Period};
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let series = vec![
+///     (NaiveDate::from_ymd_opt(2020, 1, 11).unwrap(), 4),
+///     (NaiveDate::from_ymd_opt(2020, 3, 2).unwrap(), 1),
+/// ];
+/// let mut counts = resample::<Month, _, _, _, _>(series, |values| values.len());
+/// let range = DateRange::new(NaiveDate::from_ymd_opt(2019, 12, 15).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 1).unwrap()).unwrap();
+/// fill_missing_periods(&mut counts, range, |_| 0);
+/// assert_eq!(counts.keys().next(), Some(&Month::new(2019, 12).unwrap()));
+/// assert_eq!(counts.values().collect::<Vec<_>>(), vec![&0, &1, &0, &1, &0]);
+pub fn fill_missing_periods<P, V, F>(series: &mut BTreeMap<P, V>, range: DateRange, mut fill: F)
+where
+    P: Period,
+    F: FnMut(P) -> V,
+{
+    let mut next = P::containing(range.start());
+    while let Some(period) = next.filter(|period| period.start() <= range.end()) {
+        series.entry(period).or_insert_with(|| fill(period));
+        next = period.succ();
+    }
+}
+
+/// Groups the records by the key of their date, dropping those without a key
+fn group_by<K, I, T, F>(items: I, mut key_of: F) -> BTreeMap<K, Vec<T>>
+where
+    K: Ord,
+    I: IntoIterator<Item = (NaiveDate, T)>,
+    F: FnMut(NaiveDate) -> Option<K>,
+{
+    let mut groups: BTreeMap<K, Vec<T>> = BTreeMap::new();
+    for (date, item) in items {
+        if let Some(key) = key_of(date) {
+            groups.entry(key).or_default().push(item);
+        }
+    }
+    groups
+}
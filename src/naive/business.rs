@@ -0,0 +1,738 @@
+//! Business day calendars made of weekends and holidays, and business hours
+use super::days_in_month;
+use super::interval::{free_slots, DateTimeInterval};
+use super::period::Unit;
+use super::range::DateRange;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Weekday};
+use std::borrow::Borrow;
+use std::collections::btree_set;
+use std::collections::BTreeSet;
+use std::iter::FromIterator;
+
+/// A set of weekdays making up the weekend. By default Saturday and Sunday make up the weekend.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::Weekday;
+/// use chrono_utilities::naive::business::WeekendSet;
+///
+/// let weekend = WeekendSet::new(&[Weekday::Fri, Weekday::Sat]);
+/// assert_eq!(weekend, WeekendSet::FRIDAY_SATURDAY);
+/// assert!(weekend.contains(Weekday::Fri));
+/// assert!(!weekend.contains(Weekday::Sun));
+/// assert_eq!(weekend.len(), 2);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeekendSet {
+    /// Bit `i` is set if the weekday `i` days from Monday is a weekend day
+    days: u8,
+}
+
+impl Default for WeekendSet {
+    fn default() -> Self {
+        WeekendSet::SATURDAY_SUNDAY
+    }
+}
+
+impl WeekendSet {
+    /// A weekend made of Saturday and Sunday
+    pub const SATURDAY_SUNDAY: WeekendSet = WeekendSet { days: 0b110_0000 };
+
+    /// A weekend made of Friday and Saturday
+    pub const FRIDAY_SATURDAY: WeekendSet = WeekendSet { days: 0b011_0000 };
+
+    /// A weekend made of Sunday only
+    pub const SUNDAY: WeekendSet = WeekendSet { days: 0b100_0000 };
+
+    /// A set without any weekend day
+    pub const EMPTY: WeekendSet = WeekendSet { days: 0 };
+
+    /// Creates a weekend made of the given weekdays
+    pub fn new(weekend: &[Weekday]) -> Self {
+        weekend.iter().copied().collect()
+    }
+
+    /// Returns true if the weekday is a weekend day
+    #[inline]
+    pub fn contains(&self, weekday: Weekday) -> bool {
+        self.days & (1 << weekday.num_days_from_monday()) != 0
+    }
+
+    /// Adds the weekday to the weekend
+    #[inline]
+    pub fn insert(&mut self, weekday: Weekday) {
+        self.days |= 1 << weekday.num_days_from_monday();
+    }
+
+    /// Removes the weekday from the weekend
+    #[inline]
+    pub fn remove(&mut self, weekday: Weekday) {
+        self.days &= !(1 << weekday.num_days_from_monday());
+    }
+
+    /// Returns the number of weekend days in a week
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.days.count_ones() as usize
+    }
+
+    /// Returns true if there is no weekend day
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.days == 0
+    }
+
+    /// Returns true if the date falls on a weekend day
+    #[inline]
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.contains(date.weekday())
+    }
+}
+
+impl FromIterator<Weekday> for WeekendSet {
+    fn from_iter<I: IntoIterator<Item = Weekday>>(iter: I) -> Self {
+        let mut weekend = WeekendSet::EMPTY;
+        weekend.extend(iter);
+        weekend
+    }
+}
+
+impl Extend<Weekday> for WeekendSet {
+    fn extend<I: IntoIterator<Item = Weekday>>(&mut self, iter: I) {
+        for weekday in iter {
+            self.insert(weekday);
+        }
+    }
+}
+
+/// A calendar of non-working days made of a set of weekend days and a set of holidays. Every
+/// other day is a business day. By default Saturday and Sunday make up the weekend and there
+/// are no holidays.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HolidayCalendar {
+    weekend: WeekendSet,
+    holidays: BTreeSet<NaiveDate>,
+}
+
+impl Default for HolidayCalendar {
+    fn default() -> Self {
+        HolidayCalendar::new()
+    }
+}
+
+impl HolidayCalendar {
+    /// Creates a calendar with a Saturday and Sunday weekend and no holidays
+    pub fn new() -> Self {
+        HolidayCalendar {
+            weekend: WeekendSet::SATURDAY_SUNDAY,
+            holidays: BTreeSet::new(),
+        }
+    }
+
+    /// Replaces the weekend days of the calendar
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::business::HolidayCalendar;
+    ///
+    /// let mut cal = HolidayCalendar::new();
+    /// cal.set_weekend(&[Weekday::Fri, Weekday::Sat]);
+    /// assert!(cal.is_weekend(NaiveDate::from_ymd_opt(2020, 5, 1).unwrap()));
+    /// assert!(cal.is_business_day(NaiveDate::from_ymd_opt(2020, 5, 3).unwrap()));
+    pub fn set_weekend(&mut self, weekend: &[Weekday]) {
+        self.weekend = WeekendSet::new(weekend);
+    }
+
+    /// Returns the weekend days of the calendar
+    #[inline]
+    pub fn weekend(&self) -> WeekendSet {
+        self.weekend
+    }
+
+    /// Adds a holiday to the calendar
+    #[inline]
+    pub fn add_holiday(&mut self, date: NaiveDate) {
+        self.holidays.insert(date);
+    }
+
+    /// Returns an iterator over the holidays of the calendar in chronological order
+    #[inline]
+    pub fn holidays(&self) -> btree_set::Iter<'_, NaiveDate> {
+        self.holidays.iter()
+    }
+
+    /// Returns true if the date is a holiday
+    #[inline]
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date)
+    }
+
+    /// Returns true if the date falls on a weekend day
+    #[inline]
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend.is_weekend(date)
+    }
+
+    /// Returns true if the date is neither a holiday nor on a weekend
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::HolidayCalendar;
+    ///
+    /// let mut cal = HolidayCalendar::new();
+    /// cal.add_holiday(NaiveDate::from_ymd_opt(2020, 12, 25).unwrap());
+    /// assert!(!cal.is_business_day(NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()));
+    /// assert!(!cal.is_business_day(NaiveDate::from_ymd_opt(2020, 12, 26).unwrap()));
+    /// assert!(cal.is_business_day(NaiveDate::from_ymd_opt(2020, 12, 24).unwrap()));
+    #[inline]
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// Returns the first business day falling on or after the date. Returns `None` if every day
+    /// of the week is a weekend day or no business day is left before the end of the range
+    /// supported by chrono.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::HolidayCalendar;
+    ///
+    /// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 12, 28).unwrap()].into_iter().collect();
+    /// let d = NaiveDate::from_ymd_opt(2020, 12, 26).unwrap();
+    /// assert_eq!(cal.next_business_day(d), Some(NaiveDate::from_ymd_opt(2020, 12, 29).unwrap()));
+    pub fn next_business_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        if self.weekend.len() == 7 {
+            return None;
+        }
+        let mut date = date;
+        while !self.is_business_day(date) {
+            date = date.succ_opt()?;
+        }
+        Some(date)
+    }
+
+    /// Returns the last business day falling on or before the date. Returns `None` if every day
+    /// of the week is a weekend day or no business day is left before the start of the range
+    /// supported by chrono.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::HolidayCalendar;
+    ///
+    /// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()].into_iter().collect();
+    /// let d = NaiveDate::from_ymd_opt(2020, 12, 27).unwrap();
+    /// assert_eq!(cal.prev_business_day(d), Some(NaiveDate::from_ymd_opt(2020, 12, 24).unwrap()));
+    pub fn prev_business_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        if self.weekend.len() == 7 {
+            return None;
+        }
+        let mut date = date;
+        while !self.is_business_day(date) {
+            date = date.pred_opt()?;
+        }
+        Some(date)
+    }
+
+    /// Moves the date by the given number of business days, backward if negative. Non-business
+    /// days are not counted, so that adding a business day to a Friday gives the following
+    /// Monday, whether or not the date itself is a business day. Returns the date unchanged when
+    /// moving by zero days, and `None` if every day of the week is a weekend day or the result
+    /// is out of range.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::HolidayCalendar;
+    ///
+    /// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 12, 28).unwrap()].into_iter().collect();
+    /// let d = NaiveDate::from_ymd_opt(2020, 12, 24).unwrap();
+    /// assert_eq!(cal.add_business_days(d, 2), Some(NaiveDate::from_ymd_opt(2020, 12, 29).unwrap()));
+    /// assert_eq!(cal.add_business_days(d, -1), Some(NaiveDate::from_ymd_opt(2020, 12, 23).unwrap()));
+    pub fn add_business_days(&self, date: NaiveDate, days: i64) -> Option<NaiveDate> {
+        if self.weekend.len() == 7 {
+            return None;
+        }
+        let mut date = date;
+        for _ in 0..days.unsigned_abs() {
+            date = if days > 0 {
+                self.next_business_day(date.succ_opt()?)?
+            } else {
+                self.prev_business_day(date.pred_opt()?)?
+            };
+        }
+        Some(date)
+    }
+
+    /// Returns the business day the date rolls to under the convention, which is the date
+    /// itself if it is a business day
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::{HolidayCalendar, RollConvention};
+    ///
+    /// let cal = HolidayCalendar::new();
+    /// let d = NaiveDate::from_ymd_opt(2020, 2, 15).unwrap();
+    /// assert_eq!(cal.roll(d, RollConvention::Following), Some(NaiveDate::from_ymd_opt(2020, 2, 17).unwrap()));
+    /// assert_eq!(cal.roll(d, RollConvention::Preceding), Some(NaiveDate::from_ymd_opt(2020, 2, 14).unwrap()));
+    /// // The following business day is in the next month
+    /// let d = NaiveDate::from_ymd_opt(2020, 2, 29).unwrap();
+    /// assert_eq!(cal.roll(d, RollConvention::ModifiedFollowing), Some(NaiveDate::from_ymd_opt(2020, 2, 28).unwrap()));
+    pub fn roll(&self, date: NaiveDate, convention: RollConvention) -> Option<NaiveDate> {
+        let same_month = |rolled: &NaiveDate| rolled.month() == date.month();
+        match convention {
+            RollConvention::Following => self.next_business_day(date),
+            RollConvention::Preceding => self.prev_business_day(date),
+            RollConvention::ModifiedFollowing => self
+                .next_business_day(date)
+                .filter(same_month)
+                .or_else(|| self.prev_business_day(date)),
+            RollConvention::ModifiedPreceding => self
+                .prev_business_day(date)
+                .filter(same_month)
+                .or_else(|| self.next_business_day(date)),
+        }
+    }
+
+    /// Returns the `n`th business day, starting from 1, of the period of the given granularity
+    /// containing the date. Returns `None` if `n` is zero or the period has fewer business days.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::HolidayCalendar;
+    /// use chrono_utilities::naive::period::Unit;
+    ///
+    /// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()].into_iter().collect();
+    /// let d = NaiveDate::from_ymd_opt(2021, 1, 20).unwrap();
+    /// assert_eq!(cal.nth_business_day_of(d, Unit::Week, 5), NaiveDate::from_ymd_opt(2021, 1, 22));
+    /// assert_eq!(cal.nth_business_day_of(d, Unit::Week, 6), None);
+    /// assert_eq!(cal.nth_business_day_of(d, Unit::Year, 1), NaiveDate::from_ymd_opt(2021, 1, 4));
+    pub fn nth_business_day_of(&self, date: NaiveDate, unit: Unit, n: u32) -> Option<NaiveDate> {
+        let period = unit.period_of(date)?;
+        if n as i64 > period.num_days() {
+            return None;
+        }
+        let first = self.next_business_day(period.start())?;
+        self.add_business_days(first, n.checked_sub(1)? as i64)
+            .filter(|nth| period.contains(*nth))
+    }
+
+    /// Returns the first business day of the month containing the date
+    #[inline]
+    pub fn first_business_day_of_month(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.nth_business_day_of(date, Unit::Month, 1)
+    }
+
+    /// Returns the `n`th business day, starting from 1, of the month containing the date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::HolidayCalendar;
+    ///
+    /// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()].into_iter().collect();
+    /// let d = NaiveDate::from_ymd_opt(2021, 1, 20).unwrap();
+    /// assert_eq!(cal.first_business_day_of_month(d), NaiveDate::from_ymd_opt(2021, 1, 4));
+    /// // Run on the fifth working day
+    /// assert_eq!(cal.nth_business_day_of_month(d, 5), NaiveDate::from_ymd_opt(2021, 1, 8));
+    /// assert_eq!(cal.nth_business_day_of_month(d, 21), None);
+    #[inline]
+    pub fn nth_business_day_of_month(&self, date: NaiveDate, n: u32) -> Option<NaiveDate> {
+        self.nth_business_day_of(date, Unit::Month, n)
+    }
+
+    /// Returns the first business day of the quarter containing the date
+    #[inline]
+    pub fn first_business_day_of_quarter(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.nth_business_day_of(date, Unit::Quarter, 1)
+    }
+
+    /// Returns the `n`th business day, starting from 1, of the quarter containing the date
+    #[inline]
+    pub fn nth_business_day_of_quarter(&self, date: NaiveDate, n: u32) -> Option<NaiveDate> {
+        self.nth_business_day_of(date, Unit::Quarter, n)
+    }
+
+    /// Returns the first business day of the year containing the date
+    #[inline]
+    pub fn first_business_day_of_year(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.nth_business_day_of(date, Unit::Year, 1)
+    }
+
+    /// Returns the `n`th business day, starting from 1, of the year containing the date
+    #[inline]
+    pub fn nth_business_day_of_year(&self, date: NaiveDate, n: u32) -> Option<NaiveDate> {
+        self.nth_business_day_of(date, Unit::Year, n)
+    }
+
+    /// Returns the number of business days within the range, counting both ends
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::HolidayCalendar;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()].into_iter().collect();
+    /// let dec = DateRange::new(NaiveDate::from_ymd_opt(2020, 12, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 12, 31).unwrap()).unwrap();
+    /// assert_eq!(cal.count_business_days(&dec), 22);
+    pub fn count_business_days(&self, range: &DateRange) -> i64 {
+        range
+            .iter()
+            .filter(|date| self.is_business_day(*date))
+            .count() as i64
+    }
+
+    /// Returns which business day of its month the date is, starting from 1, or `None` if the
+    /// date is not a business day
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::HolidayCalendar;
+    ///
+    /// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()].into_iter().collect();
+    /// assert_eq!(cal.business_day_of_month(NaiveDate::from_ymd_opt(2021, 1, 8).unwrap()), Some(5));
+    /// assert_eq!(cal.business_day_of_month(NaiveDate::from_ymd_opt(2021, 1, 9).unwrap()), None);
+    pub fn business_day_of_month(&self, date: NaiveDate) -> Option<u32> {
+        if !self.is_business_day(date) {
+            return None;
+        }
+        let elapsed = DateRange::new(Unit::Month.start_of(date)?, date)?;
+        Some(self.count_business_days(&elapsed) as u32)
+    }
+
+    /// Returns the number of business days left in the month of the date after the date itself
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::HolidayCalendar;
+    ///
+    /// let holidays = vec![NaiveDate::from_ymd_opt(2020, 12, 25).unwrap(), NaiveDate::from_ymd_opt(2020, 12, 31).unwrap()];
+    /// let cal: HolidayCalendar = holidays.into_iter().collect();
+    /// assert_eq!(cal.business_days_remaining_in_month(NaiveDate::from_ymd_opt(2020, 12, 24).unwrap()), 3);
+    /// assert_eq!(cal.business_days_remaining_in_month(NaiveDate::from_ymd_opt(2020, 12, 30).unwrap()), 0);
+    pub fn business_days_remaining_in_month(&self, date: NaiveDate) -> u32 {
+        let end = date.with_day(days_in_month(date.year(), date.month()));
+        end.and_then(|end| DateRange::new(date.succ_opt()?, end))
+            .map_or(0, |remaining| self.count_business_days(&remaining) as u32)
+    }
+
+    /// Returns the business day closest to the date, which is the date itself if it is a
+    /// business day. Ties between the previous and the next business days are broken as per the
+    /// policy. Returns `None` if every day of the week is a weekend day.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::{HolidayCalendar, TieBreak};
+    ///
+    /// let cal = HolidayCalendar::new();
+    /// let sat = NaiveDate::from_ymd_opt(2020, 2, 15).unwrap();
+    /// assert_eq!(cal.nearest_business_day(sat, TieBreak::Later), Some(NaiveDate::from_ymd_opt(2020, 2, 14).unwrap()));
+    /// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 2, 14).unwrap()].into_iter().collect();
+    /// assert_eq!(cal.nearest_business_day(sat, TieBreak::Earlier), Some(NaiveDate::from_ymd_opt(2020, 2, 13).unwrap()));
+    /// assert_eq!(cal.nearest_business_day(sat, TieBreak::Later), Some(NaiveDate::from_ymd_opt(2020, 2, 17).unwrap()));
+    pub fn nearest_business_day(&self, date: NaiveDate, tie: TieBreak) -> Option<NaiveDate> {
+        match (self.prev_business_day(date), self.next_business_day(date)) {
+            (Some(prev), Some(next)) => Some(tie.nearest(date, prev, next)),
+            (prev, next) => prev.or(next),
+        }
+    }
+
+    /// Returns the first holiday falling on or after the date
+    #[inline]
+    pub fn next_holiday(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.holidays.range(date..).next().copied()
+    }
+
+    /// Returns the last holiday falling on or before the date
+    #[inline]
+    pub fn prev_holiday(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.holidays.range(..=date).next_back().copied()
+    }
+}
+
+/// Which of two candidates equally distant from a date is chosen when snapping the date to the
+/// nearest one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TieBreak {
+    /// The candidate before the date
+    Earlier,
+    /// The candidate after the date
+    Later,
+}
+
+impl TieBreak {
+    /// Returns whichever of the earlier and later candidates is closest to the date, breaking
+    /// ties as per the policy
+    pub(crate) fn nearest(
+        &self,
+        date: NaiveDate,
+        earlier: NaiveDate,
+        later: NaiveDate,
+    ) -> NaiveDate {
+        let before = date.signed_duration_since(earlier);
+        let after = later.signed_duration_since(date);
+        if before < after || (before == after && *self == TieBreak::Earlier) {
+            earlier
+        } else {
+            later
+        }
+    }
+}
+
+/// How a date falling on a non-business day is moved to a business day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RollConvention {
+    /// The first business day after the date
+    Following,
+    /// The last business day before the date
+    Preceding,
+    /// The first business day after the date, unless it falls in the next month, in which case
+    /// the last business day before the date
+    ModifiedFollowing,
+    /// The last business day before the date, unless it falls in the previous month, in which
+    /// case the first business day after the date
+    ModifiedPreceding,
+}
+
+/// The hours during which business is conducted, from an opening time to a closing time on
+/// every business day of a [HolidayCalendar](struct.HolidayCalendar.html)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BusinessHours {
+    open: NaiveTime,
+    close: NaiveTime,
+    calendar: HolidayCalendar,
+}
+
+impl BusinessHours {
+    /// Creates business hours from `open` to `close` on the business days of the calendar.
+    /// Returns `None` unless `open` falls before `close`.
+    pub fn new(open: NaiveTime, close: NaiveTime, calendar: HolidayCalendar) -> Option<Self> {
+        if open < close {
+            Some(BusinessHours {
+                open,
+                close,
+                calendar,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the time at which business opens
+    #[inline]
+    pub fn open(&self) -> NaiveTime {
+        self.open
+    }
+
+    /// Returns the time at which business closes
+    #[inline]
+    pub fn close(&self) -> NaiveTime {
+        self.close
+    }
+
+    /// Returns the calendar of business days
+    #[inline]
+    pub fn calendar(&self) -> &HolidayCalendar {
+        &self.calendar
+    }
+
+    /// Returns the business hours of the date, or `None` if it is not a business day
+    pub fn hours_on(&self, date: NaiveDate) -> Option<DateTimeInterval> {
+        if !self.calendar.is_business_day(date) {
+            return None;
+        }
+        DateTimeInterval::new(date.and_time(self.open), date.and_time(self.close))
+    }
+
+    /// Returns true if the datetime falls within business hours
+    #[inline]
+    pub fn is_open(&self, datetime: NaiveDateTime) -> bool {
+        self.hours_on(datetime.date())
+            .is_some_and(|hours| hours.contains(datetime))
+    }
+
+    /// Moves the datetime by the given amount of working time, backward if it is negative. Only
+    /// time within business hours is counted, so that the move spills over to the following
+    /// business days once business closes, skipping weekends and holidays. A datetime outside
+    /// of business hours first moves to the next opening, or to the previous closing when
+    /// moving backward. Moves ending exactly at closing, or at opening when moving backward, stay
+    /// on that day. Returns the datetime unchanged when moving by zero, and `None` if every day
+    /// of the week is a weekend day or the result is out of range.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, NaiveTime, TimeDelta};
+    /// use chrono_utilities::naive::business::{BusinessHours, HolidayCalendar};
+    ///
+    /// let mut cal = HolidayCalendar::new();
+    /// cal.add_holiday(NaiveDate::from_ymd_opt(2020, 12, 28).unwrap());
+    /// let hours = BusinessHours::new(NaiveTime::from_hms_opt(9, 0, 0).unwrap(), NaiveTime::from_hms_opt(17, 0, 0).unwrap(), cal).unwrap();
+    /// let at = |d, h, m| NaiveDate::from_ymd_opt(2020, 12, d).unwrap().and_hms_opt(h, m, 0).unwrap();
+    /// // Two hours left on Friday, then a weekend and a holiday
+    /// assert_eq!(hours.add_business_duration(at(25, 15, 0), TimeDelta::hours(5)), Some(at(29, 12, 0)));
+    /// assert_eq!(hours.add_business_duration(at(25, 15, 0), TimeDelta::hours(2)), Some(at(25, 17, 0)));
+    /// assert_eq!(hours.add_business_duration(at(29, 12, 0), TimeDelta::hours(-5)), Some(at(25, 15, 0)));
+    /// // Saturday night starts counting from the next opening
+    /// assert_eq!(hours.add_business_duration(at(26, 22, 30), TimeDelta::minutes(30)), Some(at(29, 9, 30)));
+    pub fn add_business_duration(
+        &self,
+        datetime: NaiveDateTime,
+        duration: TimeDelta,
+    ) -> Option<NaiveDateTime> {
+        if duration.is_zero() {
+            return Some(datetime);
+        }
+        let forward = duration > TimeDelta::zero();
+        let mut remaining = duration.abs();
+        let mut date = datetime.date();
+        let mut current = datetime;
+        loop {
+            date = if forward {
+                self.calendar.next_business_day(date)?
+            } else {
+                self.calendar.prev_business_day(date)?
+            };
+            if let Some(hours) = self.hours_on(date) {
+                let (start, end) = if forward {
+                    (current.max(hours.start()), hours.end())
+                } else {
+                    (hours.start(), current.min(hours.end()))
+                };
+                let available = end.signed_duration_since(start);
+                if available >= remaining {
+                    return if forward {
+                        start.checked_add_signed(remaining)
+                    } else {
+                        end.checked_sub_signed(remaining)
+                    };
+                }
+                if available > TimeDelta::zero() {
+                    remaining -= available;
+                }
+            }
+            if forward {
+                date = date.succ_opt()?;
+                current = date.and_time(NaiveTime::MIN);
+            } else {
+                date = date.pred_opt()?;
+                current = date.succ_opt()?.and_time(NaiveTime::MIN);
+            }
+        }
+    }
+
+    /// Returns the earliest interval lasting `duration`, starting no earlier than `after`, which
+    /// falls within the business hours of a single day and overlaps none of the busy intervals.
+    /// Returns `None` if the duration is not positive or does not fit within a business day, or
+    /// if no such interval exists before the end of the range supported by chrono.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, NaiveTime, TimeDelta};
+    /// use chrono_utilities::naive::business::{BusinessHours, HolidayCalendar};
+    /// use chrono_utilities::naive::interval::DateTimeInterval;
+    ///
+    /// let mut cal = HolidayCalendar::new();
+    /// cal.add_holiday(NaiveDate::from_ymd_opt(2020, 12, 28).unwrap());
+    /// let hours = BusinessHours::new(NaiveTime::from_hms_opt(9, 0, 0).unwrap(), NaiveTime::from_hms_opt(17, 0, 0).unwrap(), cal).unwrap();
+    /// let at = |d, h| NaiveDate::from_ymd_opt(2020, 12, d).unwrap().and_hms_opt(h, 0, 0).unwrap();
+    /// let busy = vec![DateTimeInterval::new(at(29, 8), at(29, 11)).unwrap()];
+    /// // Friday afternoon is too short, then come a weekend, a holiday and a busy morning
+    /// let slot = hours.earliest_slot(at(25, 15), TimeDelta::hours(3), &busy).unwrap();
+    /// assert_eq!(slot, DateTimeInterval::new(at(29, 11), at(29, 14)).unwrap());
+    /// assert_eq!(hours.earliest_slot(at(25, 15), TimeDelta::hours(9), &busy), None);
+    pub fn earliest_slot<I>(
+        &self,
+        after: NaiveDateTime,
+        duration: TimeDelta,
+        busy: I,
+    ) -> Option<DateTimeInterval>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<DateTimeInterval>,
+    {
+        let day_length = self.close.signed_duration_since(self.open);
+        if duration <= TimeDelta::zero() || duration > day_length {
+            return None;
+        }
+        let mut sorted: Vec<DateTimeInterval> = busy
+            .into_iter()
+            .map(|interval| *interval.borrow())
+            .filter(|interval| interval.end() > after)
+            .collect();
+        sorted.sort();
+        // Merging overlapping intervals keeps their ends sorted as well
+        let mut busy: Vec<DateTimeInterval> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match busy.last_mut() {
+                Some(last) if interval.start() <= last.end() => {
+                    *last = last.union(&interval).unwrap_or(*last)
+                }
+                _ => busy.push(interval),
+            }
+        }
+        let mut date = after.date();
+        loop {
+            date = self.calendar.next_business_day(date)?;
+            let window = self
+                .hours_on(date)
+                .and_then(|hours| DateTimeInterval::new(hours.start().max(after), hours.end()));
+            if let Some(window) = window {
+                let first = busy.partition_point(|interval| interval.end() <= window.start());
+                let last = busy.partition_point(|interval| interval.start() < window.end());
+                let overlapping = &busy[first..last.max(first)];
+                if let Some(slot) = free_slots(overlapping, window, duration).first() {
+                    return DateTimeInterval::new(slot.start(), slot.start() + duration);
+                }
+            }
+            date = date.succ_opt()?;
+        }
+    }
+}
+
+/// Moves the datetime by the given amount of working time within the business hours. See
+/// [BusinessHours::add_business_duration](struct.BusinessHours.html#method.add_business_duration).
+#[inline]
+pub fn add_business_duration(
+    datetime: NaiveDateTime,
+    duration: TimeDelta,
+    hours: &BusinessHours,
+) -> Option<NaiveDateTime> {
+    hours.add_business_duration(datetime, duration)
+}
+
+impl FromIterator<NaiveDate> for HolidayCalendar {
+    fn from_iter<I: IntoIterator<Item = NaiveDate>>(iter: I) -> Self {
+        let mut cal = HolidayCalendar::new();
+        cal.extend(iter);
+        cal
+    }
+}
+
+impl Extend<NaiveDate> for HolidayCalendar {
+    fn extend<I: IntoIterator<Item = NaiveDate>>(&mut self, iter: I) {
+        self.holidays.extend(iter);
+    }
+}
@@ -0,0 +1,247 @@
+//! Clocks supplying the current date and time, including fixed and stepping clocks for tests,
+//! and helpers relative to the current date which take a clock so that they can be tested
+//! deterministically
+use super::countdown;
+use super::period::Unit;
+use super::range::DateRange;
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use std::cell::Cell;
+
+/// A source of the current date and time
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, NaiveDateTime};
+/// use chrono_utilities::naive::clock::{start_of_this_month, Clock};
+///
+/// struct Noon;
+///
+/// impl Clock for Noon {
+///     fn now(&self) -> NaiveDateTime {
+///         NaiveDate::from_ymd_opt(2020, 6, 15).unwrap().and_hms_opt(12, 0, 0).unwrap()
+///     }
+/// }
+///
+/// assert_eq!(Noon.today(), NaiveDate::from_ymd_opt(2020, 6, 15).unwrap());
+/// assert_eq!(start_of_this_month(&Noon), NaiveDate::from_ymd_opt(2020, 6, 1));
+pub trait Clock {
+    /// Returns the current date and time
+    fn now(&self) -> NaiveDateTime;
+
+    /// Returns the current date
+    #[inline]
+    fn today(&self) -> NaiveDate {
+        self.now().date()
+    }
+}
+
+/// A clock reading the local time of the system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    #[inline]
+    fn now(&self) -> NaiveDateTime {
+        (**self).now()
+    }
+}
+
+/// Returns the period of the given granularity containing the current date
+#[inline]
+pub fn this_period<C: Clock + ?Sized>(clock: &C, unit: Unit) -> Option<DateRange> {
+    unit.period_of(clock.today())
+}
+
+/// Returns the first day of the current ISO 8601 week
+#[inline]
+pub fn start_of_this_week<C: Clock + ?Sized>(clock: &C) -> Option<NaiveDate> {
+    Unit::Week.start_of(clock.today())
+}
+
+/// Returns the last day of the current ISO 8601 week
+#[inline]
+pub fn end_of_this_week<C: Clock + ?Sized>(clock: &C) -> Option<NaiveDate> {
+    this_period(clock, Unit::Week).map(|week| week.end())
+}
+
+/// Returns the first day of the current month
+#[inline]
+pub fn start_of_this_month<C: Clock + ?Sized>(clock: &C) -> Option<NaiveDate> {
+    Unit::Month.start_of(clock.today())
+}
+
+/// Returns the last day of the current month
+#[inline]
+pub fn end_of_this_month<C: Clock + ?Sized>(clock: &C) -> Option<NaiveDate> {
+    this_period(clock, Unit::Month).map(|month| month.end())
+}
+
+/// Returns the first day of the current quarter
+#[inline]
+pub fn start_of_this_quarter<C: Clock + ?Sized>(clock: &C) -> Option<NaiveDate> {
+    Unit::Quarter.start_of(clock.today())
+}
+
+/// Returns the last day of the current quarter
+#[inline]
+pub fn end_of_this_quarter<C: Clock + ?Sized>(clock: &C) -> Option<NaiveDate> {
+    this_period(clock, Unit::Quarter).map(|quarter| quarter.end())
+}
+
+/// Returns the first day of the current year
+#[inline]
+pub fn start_of_this_year<C: Clock + ?Sized>(clock: &C) -> Option<NaiveDate> {
+    Unit::Year.start_of(clock.today())
+}
+
+/// Returns the last day of the current year
+#[inline]
+pub fn end_of_this_year<C: Clock + ?Sized>(clock: &C) -> Option<NaiveDate> {
+    this_period(clock, Unit::Year).map(|year| year.end())
+}
+
+/// Returns the number of days from the current date until the target date, negative if the
+/// target date has passed
+#[inline]
+pub fn days_until<C: Clock + ?Sized>(clock: &C, target: NaiveDate) -> i64 {
+    countdown::days_until(target, clock.today())
+}
+
+/// A clock standing still at a given time until it is set or advanced manually, meant for tests
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, TimeDelta};
+/// use chrono_utilities::naive::clock::{end_of_this_month, Clock, FixedClock};
+///
+/// let clock = FixedClock::new(NaiveDate::from_ymd_opt(2020, 1, 31).unwrap().and_hms_opt(23, 0, 0).unwrap());
+/// assert_eq!(clock.now(), clock.now());
+/// assert_eq!(end_of_this_month(&clock), NaiveDate::from_ymd_opt(2020, 1, 31));
+/// clock.advance(TimeDelta::hours(1));
+/// assert_eq!(end_of_this_month(&clock), NaiveDate::from_ymd_opt(2020, 2, 29));
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedClock {
+    now: Cell<NaiveDateTime>,
+}
+
+impl FixedClock {
+    /// Creates a clock standing at the given time
+    #[inline]
+    pub fn new(now: NaiveDateTime) -> Self {
+        FixedClock {
+            now: Cell::new(now),
+        }
+    }
+
+    /// Creates a clock standing at midnight of the given date
+    #[inline]
+    pub fn at_date(date: NaiveDate) -> Self {
+        FixedClock::new(date.and_time(NaiveTime::MIN))
+    }
+
+    /// Moves the clock to the given time, which may be in the past
+    #[inline]
+    pub fn set(&self, now: NaiveDateTime) {
+        self.now.set(now);
+    }
+
+    /// Moves the clock forward by the given duration, backward if it is negative
+    ///
+    /// # Panics
+    ///
+    /// Panics if the time is out of the range supported by chrono
+    #[inline]
+    pub fn advance(&self, delta: TimeDelta) {
+        self.now.set(advanced(self.now.get(), delta));
+    }
+}
+
+impl Clock for FixedClock {
+    #[inline]
+    fn now(&self) -> NaiveDateTime {
+        self.now.get()
+    }
+}
+
+/// A clock moving forward by a fixed step every time it is read, meant for tests measuring
+/// elapsed time. It may also be advanced manually.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, TimeDelta};
+/// use chrono_utilities::naive::clock::{Clock, SteppingClock};
+///
+/// let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// let clock = SteppingClock::new(start, TimeDelta::seconds(1));
+/// assert_eq!(clock.now(), start);
+/// assert_eq!(clock.now(), start + TimeDelta::seconds(1));
+/// clock.advance(TimeDelta::minutes(1));
+/// assert_eq!(clock.now(), start + TimeDelta::seconds(62));
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteppingClock {
+    next: Cell<NaiveDateTime>,
+    step: TimeDelta,
+}
+
+impl SteppingClock {
+    /// Creates a clock first reading the given time, then moving forward by `step` on every
+    /// read
+    #[inline]
+    pub fn new(start: NaiveDateTime, step: TimeDelta) -> Self {
+        SteppingClock {
+            next: Cell::new(start),
+            step,
+        }
+    }
+
+    /// Returns the duration the clock moves forward by on every read
+    #[inline]
+    pub fn step(&self) -> TimeDelta {
+        self.step
+    }
+
+    /// Returns the time the next read will return, without moving the clock
+    #[inline]
+    pub fn peek(&self) -> NaiveDateTime {
+        self.next.get()
+    }
+
+    /// Moves the clock forward by the given duration, backward if it is negative
+    ///
+    /// # Panics
+    ///
+    /// Panics if the time is out of the range supported by chrono
+    #[inline]
+    pub fn advance(&self, delta: TimeDelta) {
+        self.next.set(advanced(self.next.get(), delta));
+    }
+}
+
+impl Clock for SteppingClock {
+    /// Returns the current time, then moves the clock forward by its step
+    ///
+    /// # Panics
+    ///
+    /// Panics if the next time is out of the range supported by chrono
+    fn now(&self) -> NaiveDateTime {
+        let now = self.next.get();
+        self.next.set(advanced(now, self.step));
+        now
+    }
+}
+
+/// Returns the time moved by the delta
+fn advanced(now: NaiveDateTime, delta: TimeDelta) -> NaiveDateTime {
+    now.checked_add_signed(delta)
+        .expect("clock out of the range supported by chrono")
+}
@@ -0,0 +1,159 @@
+//! Helpers for comparing dates and periods against their prior counterparts
+use super::period::Unit;
+use super::range::DateRange;
+use super::{days_in_month, shift_months};
+use chrono::{Datelike, NaiveDate, TimeDelta};
+
+/// How a date is moved back a year while keeping its weekday
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YearAgoShift {
+    /// Move back exactly 52 weeks, or 364 days
+    Weeks52,
+    /// Move to the same weekday of the same ISO 8601 week in the previous ISO year. Week 53 maps
+    /// to week 52 when the previous year has no week 53.
+    IsoWeek,
+}
+
+/// Returns the date falling on the same weekday as the given one, roughly a year earlier.
+/// Forecasting baselines use this to compare like weekdays rather than like calendar dates.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{Datelike, NaiveDate};
+/// use chrono_utilities::naive::compare::{same_weekday_aligned_year_ago, YearAgoShift};
+///
+/// let d = NaiveDate::from_ymd_opt(2021, 1, 8).unwrap();
+/// let weeks = same_weekday_aligned_year_ago(d, YearAgoShift::Weeks52).unwrap();
+/// assert_eq!(weeks, NaiveDate::from_ymd_opt(2020, 1, 10).unwrap());
+/// assert_eq!(weeks.weekday(), d.weekday());
+/// // 2020 has 53 ISO weeks, so the first week of 2021 maps to the first week of 2020
+/// let iso = same_weekday_aligned_year_ago(d, YearAgoShift::IsoWeek).unwrap();
+/// assert_eq!(iso, NaiveDate::from_ymd_opt(2020, 1, 3).unwrap());
+pub fn same_weekday_aligned_year_ago(date: NaiveDate, shift: YearAgoShift) -> Option<NaiveDate> {
+    match shift {
+        YearAgoShift::Weeks52 => date.checked_sub_signed(TimeDelta::weeks(52)),
+        YearAgoShift::IsoWeek => {
+            let week = date.iso_week();
+            let year = week.year().checked_sub(1)?;
+            NaiveDate::from_isoywd_opt(year, week.week(), date.weekday())
+                .or_else(|| NaiveDate::from_isoywd_opt(year, 52, date.weekday()))
+        }
+    }
+}
+
+/// Returns the range equivalent to the given one, one period of the given granularity earlier.
+/// Days and weeks shift the range by a fixed number of days. Months, quarters and years shift
+/// both ends by whole months, clamping them to the length of the target month, and a range
+/// ending on the last day of a month keeps ending on the last day of a month. Hence February
+/// 2020 maps to February 2019 and April maps to the whole of March when shifting by a month.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::compare::prior_equivalent;
+/// use chrono_utilities::naive::period::Unit;
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let feb = DateRange::new(NaiveDate::from_ymd_opt(2020, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()).unwrap();
+/// let prior = prior_equivalent(feb, Unit::Year).unwrap();
+/// assert_eq!(prior, DateRange::new(NaiveDate::from_ymd_opt(2019, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2019, 2, 28).unwrap()).unwrap());
+///
+/// let apr = DateRange::new(NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 30).unwrap()).unwrap();
+/// let prior = prior_equivalent(apr, Unit::Month).unwrap();
+/// assert_eq!(prior, DateRange::new(NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 31).unwrap()).unwrap());
+///
+/// let mtd = DateRange::new(NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 30).unwrap()).unwrap();
+/// let prior = prior_equivalent(mtd, Unit::Month).unwrap();
+/// assert_eq!(prior, DateRange::new(NaiveDate::from_ymd_opt(2020, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()).unwrap());
+pub fn prior_equivalent(range: DateRange, unit: Unit) -> Option<DateRange> {
+    let months = match unit {
+        Unit::Day | Unit::Week => {
+            let days = if unit == Unit::Day { 1 } else { 7 };
+            return DateRange::new(
+                range.start().checked_sub_signed(TimeDelta::days(days))?,
+                range.end().checked_sub_signed(TimeDelta::days(days))?,
+            );
+        }
+        Unit::Month => 1,
+        Unit::Quarter => 3,
+        Unit::Year => 12,
+    };
+    let start = shift_months(range.start(), -months)?;
+    let end = shift_months(range.end(), -months)?;
+    let end = if range.end().day() == days_in_month(range.end().year(), range.end().month()) {
+        end.with_day(days_in_month(end.year(), end.month()))?
+    } else {
+        end
+    };
+    DateRange::new(start, end)
+}
+
+/// Returns the range equivalent to the given one a year earlier, as per
+/// [prior_equivalent](fn.prior_equivalent.html)
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::compare::same_range_last_year;
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let range = DateRange::new(NaiveDate::from_ymd_opt(2020, 2, 29).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 6).unwrap()).unwrap();
+/// let prior = same_range_last_year(range).unwrap();
+/// assert_eq!(prior.start(), NaiveDate::from_ymd_opt(2019, 2, 28).unwrap());
+/// assert_eq!(prior.end(), NaiveDate::from_ymd_opt(2019, 3, 6).unwrap());
+#[inline]
+pub fn same_range_last_year(range: DateRange) -> Option<DateRange> {
+    prior_equivalent(range, Unit::Year)
+}
+
+/// A period along with the periods it is usually compared against: the one right before it
+/// (week over week, month over month...) and its counterpart a year earlier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ComparisonPeriods {
+    /// The period containing the anchor date
+    pub current: DateRange,
+    /// The period preceding the current one
+    pub prior_period: DateRange,
+    /// The counterpart of the current period a year earlier. Weeks map to the same ISO 8601
+    /// week of the previous year.
+    pub prior_year: DateRange,
+}
+
+impl ComparisonPeriods {
+    /// Returns the period of the given granularity containing the anchor date along with its
+    /// prior period and prior year counterparts
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::compare::ComparisonPeriods;
+    /// use chrono_utilities::naive::period::Unit;
+    ///
+    /// let periods = ComparisonPeriods::new(NaiveDate::from_ymd_opt(2020, 3, 18).unwrap(), Unit::Month).unwrap();
+    /// assert_eq!(periods.current.start(), NaiveDate::from_ymd_opt(2020, 3, 1).unwrap());
+    /// assert_eq!(periods.prior_period.start(), NaiveDate::from_ymd_opt(2020, 2, 1).unwrap());
+    /// assert_eq!(periods.prior_period.end(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap());
+    /// assert_eq!(periods.prior_year.start(), NaiveDate::from_ymd_opt(2019, 3, 1).unwrap());
+    ///
+    /// let weeks = ComparisonPeriods::new(NaiveDate::from_ymd_opt(2020, 3, 18).unwrap(), Unit::Week).unwrap();
+    /// assert_eq!(weeks.current.start(), NaiveDate::from_ymd_opt(2020, 3, 16).unwrap());
+    /// assert_eq!(weeks.prior_period.start(), NaiveDate::from_ymd_opt(2020, 3, 9).unwrap());
+    /// assert_eq!(weeks.prior_year.start(), NaiveDate::from_ymd_opt(2019, 3, 18).unwrap());
+    pub fn new(anchor: NaiveDate, unit: Unit) -> Option<Self> {
+        let current = unit.period_of(anchor)?;
+        let prior_period = unit.period_of(current.start().pred_opt()?)?;
+        let year_ago = match unit {
+            Unit::Week => same_weekday_aligned_year_ago(current.start(), YearAgoShift::IsoWeek)?,
+            _ => shift_months(current.start(), -12)?,
+        };
+        Some(ComparisonPeriods {
+            current,
+            prior_period,
+            prior_year: unit.period_of(year_ago)?,
+        })
+    }
+}
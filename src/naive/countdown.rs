@@ -0,0 +1,141 @@
+//! Anniversaries of dates, such as birthdays, and counts of the days until or since a date
+use chrono::{Datelike, NaiveDate};
+
+/// Where the anniversaries of February 29 fall in years which are not leap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeapDayPolicy {
+    /// On February 28
+    Feb28,
+    /// On March 1
+    Mar1,
+}
+
+/// Returns the anniversary of the original date in the given year, which is the original date
+/// itself in its own year. Anniversaries of February 29 fall as per the policy in years which
+/// are not leap. Returns `None` if the anniversary is out of the range supported by chrono.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::countdown::{anniversary, LeapDayPolicy};
+///
+/// let leap_day = NaiveDate::from_ymd_opt(2000, 2, 29).unwrap();
+/// assert_eq!(anniversary(leap_day, 2021, LeapDayPolicy::Feb28), NaiveDate::from_ymd_opt(2021, 2, 28));
+/// assert_eq!(anniversary(leap_day, 2021, LeapDayPolicy::Mar1), NaiveDate::from_ymd_opt(2021, 3, 1));
+/// assert_eq!(anniversary(leap_day, 2024, LeapDayPolicy::Mar1), NaiveDate::from_ymd_opt(2024, 2, 29));
+pub fn anniversary(original: NaiveDate, year: i32, policy: LeapDayPolicy) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, original.month(), original.day()).or_else(|| match policy {
+        LeapDayPolicy::Feb28 => NaiveDate::from_ymd_opt(year, 2, 28),
+        LeapDayPolicy::Mar1 => NaiveDate::from_ymd_opt(year, 3, 1),
+    })
+}
+
+/// Returns the first anniversary of the original date falling strictly after the given date,
+/// the original date itself included. Anniversaries of February 29 fall as per the policy in
+/// years which are not leap. Returns `None` if the anniversary is out of the range supported by
+/// chrono.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::countdown::{next_anniversary, LeapDayPolicy};
+///
+/// let birthday = NaiveDate::from_ymd_opt(1996, 2, 29).unwrap();
+/// let today = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+/// assert_eq!(next_anniversary(birthday, today, LeapDayPolicy::Feb28), NaiveDate::from_ymd_opt(2022, 2, 28));
+/// assert_eq!(next_anniversary(birthday, today, LeapDayPolicy::Mar1), NaiveDate::from_ymd_opt(2021, 3, 1));
+/// let before_birth = NaiveDate::from_ymd_opt(1990, 1, 1).unwrap();
+/// assert_eq!(next_anniversary(birthday, before_birth, LeapDayPolicy::Mar1), Some(birthday));
+pub fn next_anniversary(
+    original: NaiveDate,
+    after: NaiveDate,
+    policy: LeapDayPolicy,
+) -> Option<NaiveDate> {
+    if original > after {
+        return Some(original);
+    }
+    let this_year = anniversary(original, after.year(), policy)?;
+    if this_year > after {
+        Some(this_year)
+    } else {
+        anniversary(original, after.year().checked_add(1)?, policy)
+    }
+}
+
+/// Common set of methods counting the days between dates, seen from the date they are called on
+pub trait Countdown {
+    /// Returns the number of days from the date until the other date, negative if the other
+    /// date has passed
+    fn days_until(&self, other: NaiveDate) -> i64;
+
+    /// Returns the number of days elapsed since the other date, negative if the other date is
+    /// yet to come
+    fn days_since(&self, other: NaiveDate) -> i64;
+}
+
+impl Countdown for NaiveDate {
+    /// Returns the number of days from the date until the other date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::countdown::Countdown;
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2020, 12, 20).unwrap();
+    /// assert_eq!(today.days_until(NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()), 5);
+    /// assert_eq!(today.days_until(NaiveDate::from_ymd_opt(2020, 12, 1).unwrap()), -19);
+    #[inline]
+    fn days_until(&self, other: NaiveDate) -> i64 {
+        days_until(other, *self)
+    }
+
+    /// Returns the number of days elapsed since the other date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::countdown::Countdown;
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2021, 1, 2).unwrap();
+    /// assert_eq!(today.days_since(NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()), 8);
+    #[inline]
+    fn days_since(&self, other: NaiveDate) -> i64 {
+        days_since(other, *self)
+    }
+}
+
+/// Returns the number of days from the reference date until the target date, negative if the
+/// target date falls before the reference date
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::countdown::days_until;
+///
+/// let deadline = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+/// assert_eq!(days_until(deadline, NaiveDate::from_ymd_opt(2021, 2, 1).unwrap()), 28);
+#[inline]
+pub fn days_until(target: NaiveDate, reference: NaiveDate) -> i64 {
+    target.signed_duration_since(reference).num_days()
+}
+
+/// Returns the number of days elapsed from the past date until the reference date, negative
+/// if the past date falls after the reference date
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::countdown::days_since;
+///
+/// let release = NaiveDate::from_ymd_opt(2020, 2, 1).unwrap();
+/// assert_eq!(days_since(release, NaiveDate::from_ymd_opt(2021, 2, 1).unwrap()), 366);
+#[inline]
+pub fn days_since(past: NaiveDate, reference: NaiveDate) -> i64 {
+    reference.signed_duration_since(past).num_days()
+}
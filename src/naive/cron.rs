@@ -0,0 +1,349 @@
+//! Cron expressions in the standard 5-field syntax
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A cron expression made of the minute, hour, day of month, month and day of week fields.
+/// Fields accept `*`, numbers, ranges (`1-5`), steps (`*/15`, `10-50/20`) and comma separated
+/// lists of those. Months may also be given as `JAN` to `DEC` and days of week as `SUN` to
+/// `SAT`, Sunday being either 0 or 7.
+///
+/// As in standard cron, when both the day of month and the day of week are restricted, that is
+/// neither of them is `*`, a date matches if either of them matches.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::cron::CronSchedule;
+///
+/// let at = |m, d, h, min| NaiveDate::from_ymd_opt(2020, m, d).unwrap().and_hms_opt(h, min, 0).unwrap();
+/// // At 09:30 on weekdays
+/// let cron: CronSchedule = "30 9 * * MON-FRI".parse().unwrap();
+/// assert_eq!(cron.next_occurrence_after(at(1, 3, 9, 30)), Some(at(1, 6, 9, 30)));
+/// // On the 13th of every month and on every Friday, at midnight
+/// let cron: CronSchedule = "0 0 13 * 5".parse().unwrap();
+/// let next: Vec<_> = cron.occurrences_after(at(3, 1, 0, 0)).take(3).collect();
+/// assert_eq!(next, vec![at(3, 6, 0, 0), at(3, 13, 0, 0), at(3, 20, 0, 0)]);
+/// // Every 15 minutes
+/// let cron: CronSchedule = "*/15 * * * *".parse().unwrap();
+/// assert_eq!(cron.next_occurrence_after(at(1, 1, 23, 50)), Some(at(1, 2, 0, 0)));
+/// assert!("60 * * * *".parse::<CronSchedule>().is_err());
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u32,
+    days_of_month: u32,
+    months: u16,
+    days_of_week: u8,
+    restricted_day_of_month: bool,
+    restricted_day_of_week: bool,
+}
+
+impl CronSchedule {
+    /// Returns true if the datetime, truncated to the minute, matches the schedule
+    pub fn matches(&self, datetime: NaiveDateTime) -> bool {
+        self.matches_date(datetime.date())
+            && self.hours & (1 << datetime.hour()) != 0
+            && self.minutes & (1 << datetime.minute()) != 0
+    }
+
+    /// Returns the first minute strictly after the datetime matching the schedule. Returns
+    /// `None` if no such minute exists before the end of the range supported by chrono, or
+    /// within 400 years, after which the Gregorian calendar repeats itself.
+    pub fn next_occurrence_after(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        let next_minute = datetime
+            .with_second(0)?
+            .with_nanosecond(0)?
+            .checked_add_signed(TimeDelta::minutes(1))?;
+        let mut date = next_minute.date();
+        let mut from = next_minute.time();
+        let last = date.checked_add_signed(TimeDelta::days(146_097));
+        loop {
+            if self.matches_date(date) {
+                if let Some(time) = self.first_time_from(from) {
+                    return Some(date.and_time(time));
+                }
+            }
+            date = date.succ_opt()?;
+            if last.is_some_and(|last| date > last) {
+                return None;
+            }
+            from = NaiveTime::MIN;
+        }
+    }
+
+    /// Returns an iterator over the minutes strictly after the datetime matching the schedule,
+    /// in chronological order
+    pub fn occurrences_after(&self, datetime: NaiveDateTime) -> CronOccurrences {
+        CronOccurrences {
+            schedule: *self,
+            last: Some(datetime),
+        }
+    }
+
+    /// Returns true if the date matches the day of month, month and day of week fields
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let day_of_month = self.days_of_month & (1 << date.day()) != 0;
+        let day_of_week = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        if self.restricted_day_of_month && self.restricted_day_of_week {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+
+    /// Returns the first time of day no earlier than the given one matching the hour and minute
+    /// fields
+    fn first_time_from(&self, from: NaiveTime) -> Option<NaiveTime> {
+        for hour in from.hour()..24 {
+            if self.hours & (1 << hour) == 0 {
+                continue;
+            }
+            let first_minute = if hour == from.hour() {
+                from.minute()
+            } else {
+                0
+            };
+            if let Some(minute) = (first_minute..60).find(|m| self.minutes & (1 << m) != 0) {
+                return NaiveTime::from_hms_opt(hour, minute, 0);
+            }
+        }
+        None
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = ParseCronError;
+
+    /// Parses a cron expression made of 5 fields separated by whitespace
+    fn from_str(s: &str) -> Result<Self, ParseCronError> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(ParseCronError {
+                field: "expression",
+            });
+        }
+        let err = |field| ParseCronError { field };
+        let minutes = parse_field(fields[0], 0, 59, &[]).ok_or(err("minute"))?;
+        let hours = parse_field(fields[1], 0, 23, &[]).ok_or(err("hour"))?;
+        let days_of_month = parse_field(fields[2], 1, 31, &[]).ok_or(err("day of month"))?;
+        let months = parse_field(fields[3], 1, 12, &MONTHS).ok_or(err("month"))?;
+        let mut days_of_week = parse_field(fields[4], 0, 7, &WEEKDAYS).ok_or(err("day of week"))?;
+        // Sunday may be written as 7
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+        Ok(CronSchedule {
+            minutes,
+            hours: hours as u32,
+            days_of_month: days_of_month as u32,
+            months: months as u16,
+            days_of_week: (days_of_week & 0x7f) as u8,
+            restricted_day_of_month: !fields[2].starts_with('*'),
+            restricted_day_of_week: !fields[4].starts_with('*'),
+        })
+    }
+}
+
+/// Names of the months, starting from January
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// Names of the days of the week, starting from Sunday
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Parses a field into a bit set of the values it matches. Names stand for the values starting
+/// from `min`.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Option<u64> {
+    let value = |s: &str| -> Option<u32> {
+        let n = match names.iter().position(|name| name.eq_ignore_ascii_case(s)) {
+            Some(index) => index as u32 + min,
+            None => s.parse().ok()?,
+        };
+        Some(n).filter(|n| (min..=max).contains(n))
+    };
+    let mut bits = 0;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, step.parse().ok().filter(|step| *step > 0)?),
+            None => (item, 1),
+        };
+        let (first, last) = if range == "*" {
+            (min, max)
+        } else if let Some((first, last)) = range.split_once('-') {
+            (value(first)?, value(last)?)
+        } else if item.contains('/') {
+            (value(range)?, max)
+        } else {
+            let n = value(range)?;
+            (n, n)
+        };
+        if first > last {
+            return None;
+        }
+        for n in (first..=last).step_by(step) {
+            bits |= 1 << n;
+        }
+    }
+    Some(bits)
+}
+
+/// Iterator over the occurrences of a [CronSchedule](struct.CronSchedule.html)
+#[derive(Debug, Clone)]
+pub struct CronOccurrences {
+    schedule: CronSchedule,
+    last: Option<NaiveDateTime>,
+}
+
+impl Iterator for CronOccurrences {
+    type Item = NaiveDateTime;
+
+    fn next(&mut self) -> Option<NaiveDateTime> {
+        self.last = self
+            .last
+            .and_then(|last| self.schedule.next_occurrence_after(last));
+        self.last
+    }
+}
+
+/// Error returned when parsing an invalid cron expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseCronError {
+    field: &'static str,
+}
+
+impl fmt::Display for ParseCronError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid cron {}", self.field)
+    }
+}
+
+impl Error for ParseCronError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{CronSchedule, ParseCronError};
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn cron(s: &str) -> CronSchedule {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        // January 1, 2020 is a Wednesday
+        let both = cron("0 0 1 * MON");
+        assert!(both.matches(at(2020, 1, 1, 0, 0)));
+        assert!(both.matches(at(2020, 1, 6, 0, 0)));
+        assert!(!both.matches(at(2020, 1, 7, 0, 0)));
+        let day_of_month = cron("0 0 1 * *");
+        assert!(day_of_month.matches(at(2020, 1, 1, 0, 0)));
+        assert!(!day_of_month.matches(at(2020, 1, 6, 0, 0)));
+        let day_of_week = cron("0 0 * * MON");
+        assert!(!day_of_week.matches(at(2020, 1, 1, 0, 0)));
+        assert!(day_of_week.matches(at(2020, 1, 6, 0, 0)));
+        // A field starting with `*` is not restricted, so that both fields must match. The 1st
+        // of February 2020 is the first one falling on a Sunday, Tuesday, Thursday or Saturday.
+        let stepped = cron("0 0 1 * */2");
+        assert!(!stepped.matches(at(2020, 1, 1, 0, 0)));
+        assert!(!stepped.matches(at(2020, 1, 2, 0, 0)));
+        assert_eq!(
+            stepped.next_occurrence_after(at(2020, 1, 1, 0, 0)),
+            Some(at(2020, 2, 1, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_ranges_steps_and_names() {
+        let hours = cron("0 9-11 * * *");
+        let next: Vec<_> = hours
+            .occurrences_after(at(2020, 1, 1, 0, 0))
+            .take(4)
+            .collect();
+        assert_eq!(
+            next,
+            vec![
+                at(2020, 1, 1, 9, 0),
+                at(2020, 1, 1, 10, 0),
+                at(2020, 1, 1, 11, 0),
+                at(2020, 1, 2, 9, 0)
+            ]
+        );
+        let minutes = cron("10-50/20,5/25 0 * * *");
+        let next: Vec<_> = minutes
+            .occurrences_after(at(2020, 1, 1, 0, 0))
+            .take(6)
+            .collect();
+        assert_eq!(
+            next,
+            vec![
+                at(2020, 1, 1, 0, 5),
+                at(2020, 1, 1, 0, 10),
+                at(2020, 1, 1, 0, 30),
+                at(2020, 1, 1, 0, 50),
+                at(2020, 1, 1, 0, 55),
+                at(2020, 1, 2, 0, 5)
+            ]
+        );
+        assert_eq!(cron("0 0 * * 7"), cron("0 0 * * SUN"));
+        assert_eq!(cron("0 0 * * 0"), cron("0 0 * * sun"));
+        assert_eq!(cron("0 0 1 jan-Mar *"), cron("0 0 1 1-3 *"));
+        // Seconds are ignored when matching, and excluded when looking for the next minute
+        let dt = NaiveDate::from_ymd_opt(2020, 1, 1)
+            .unwrap()
+            .and_hms_opt(9, 0, 30)
+            .unwrap();
+        assert!(hours.matches(dt));
+        assert_eq!(hours.next_occurrence_after(dt), Some(at(2020, 1, 1, 10, 0)));
+    }
+
+    #[test]
+    fn test_rare_and_impossible_dates() {
+        let leap_day = cron("0 0 29 2 *");
+        assert_eq!(
+            leap_day.next_occurrence_after(at(2021, 1, 1, 0, 0)),
+            Some(at(2024, 2, 29, 0, 0))
+        );
+        assert_eq!(
+            cron("0 0 30 2 *").next_occurrence_after(at(2020, 1, 1, 0, 0)),
+            None
+        );
+        assert_eq!(
+            cron("0 0 * * *").next_occurrence_after(NaiveDateTime::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_fields() {
+        let err = |s: &str| s.parse::<CronSchedule>().err();
+        let field = |field| Some(ParseCronError { field });
+        assert_eq!(err("* * * *"), field("expression"));
+        assert_eq!(err("* * * * * *"), field("expression"));
+        assert_eq!(err("60 * * * *"), field("minute"));
+        assert_eq!(err("5-1 * * * *"), field("minute"));
+        assert_eq!(err("*/0 * * * *"), field("minute"));
+        assert_eq!(err("1,,2 * * * *"), field("minute"));
+        assert_eq!(err("a * * * *"), field("minute"));
+        assert_eq!(err("* 24 * * *"), field("hour"));
+        assert_eq!(err("* * 0 * *"), field("day of month"));
+        assert_eq!(err("* * 32 * *"), field("day of month"));
+        assert_eq!(err("* * * 13 *"), field("month"));
+        assert_eq!(err("* * * JANUARY *"), field("month"));
+        assert_eq!(err("* * * * 8"), field("day of week"));
+        assert_eq!(err("* * * * MON-"), field("day of week"));
+    }
+}
@@ -0,0 +1,264 @@
+//! Transitions of datetimes to the boundaries of the periods containing them, and rounding of
+//! datetimes to multiples of an interval
+use chrono::{DateTime, NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+use std::convert::TryFrom;
+
+/// How the end of a period is represented
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndConvention {
+    /// The last nanosecond of the period, as in 23:59:59.999999999 for the end of a day. The
+    /// period ends on or before its end.
+    Inclusive,
+    /// The start of the next period, as in midnight of the next day for the end of a day. The
+    /// period ends strictly before its end.
+    Exclusive,
+}
+
+/// Common set of methods for transitioning datetimes to the boundaries of the periods containing
+/// them. Mixing the end conventions across a code base is a common source of off-by-one bugs,
+/// hence methods returning an end take the convention explicitly.
+pub trait DateTimeTransitions: Sized {
+    /// Returns the datetime at midnight at the start of its day
+    fn start_of_day(&self) -> Option<Self>;
+
+    /// Returns the end of the day of the datetime as per the convention
+    fn end_of_day(&self, convention: EndConvention) -> Option<Self>;
+
+    /// Returns the datetime truncated to the start of its hour
+    fn start_of_hour(&self) -> Option<Self>;
+
+    /// Returns the end of the hour of the datetime as per the convention
+    fn end_of_hour(&self, convention: EndConvention) -> Option<Self>;
+
+    /// Returns the datetime truncated to the start of its minute
+    fn start_of_minute(&self) -> Option<Self>;
+
+    /// Returns the end of the minute of the datetime as per the convention
+    fn end_of_minute(&self, convention: EndConvention) -> Option<Self>;
+
+    /// Returns the datetime truncated to the start of its second
+    fn start_of_second(&self) -> Option<Self>;
+
+    /// Returns the end of the second of the datetime as per the convention
+    fn end_of_second(&self, convention: EndConvention) -> Option<Self>;
+
+    /// Returns the latest multiple of the interval on or before the datetime, or `None` if the
+    /// interval is not positive. Multiples are counted from midnight on January 1, 1970, so that
+    /// intervals dividing a day, such as 15 minutes, are aligned on midnight of every day.
+    fn floor_to(&self, interval: TimeDelta) -> Option<Self>;
+
+    /// Returns the earliest multiple of the interval on or after the datetime, or `None` if the
+    /// interval is not positive. See [floor_to](#tymethod.floor_to) for the alignment.
+    fn ceil_to(&self, interval: TimeDelta) -> Option<Self>;
+
+    /// Returns the multiple of the interval nearest to the datetime, or `None` if the interval
+    /// is not positive. A datetime half-way between two multiples is rounded up to the later
+    /// one. See [floor_to](#tymethod.floor_to) for the alignment.
+    fn round_to(&self, interval: TimeDelta) -> Option<Self>;
+}
+
+/// Number of nanoseconds in a second
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// Returns the datetime rounded to a multiple of the interval by the function, which is given
+/// the datetime and the interval in nanoseconds along with the remainder of their division
+fn round_with<F>(datetime: &NaiveDateTime, interval: TimeDelta, round: F) -> Option<NaiveDateTime>
+where
+    F: Fn(i128, i128, i128) -> i128,
+{
+    let interval = interval.num_seconds() as i128 * NANOS_PER_SEC + interval.subsec_nanos() as i128;
+    if interval <= 0 {
+        return None;
+    }
+    let utc = datetime.and_utc();
+    let nanos = utc.timestamp() as i128 * NANOS_PER_SEC + utc.timestamp_subsec_nanos() as i128;
+    let rounded = round(nanos, interval, nanos.rem_euclid(interval));
+    let secs = i64::try_from(rounded.div_euclid(NANOS_PER_SEC)).ok()?;
+    let rounded = DateTime::from_timestamp(secs, rounded.rem_euclid(NANOS_PER_SEC) as u32)?;
+    Some(rounded.naive_utc())
+}
+
+impl DateTimeTransitions for NaiveDateTime {
+    /// Returns the datetime at midnight at the start of its day
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::datetime::DateTimeTransitions;
+    ///
+    /// let dt = NaiveDate::from_ymd_opt(2020, 2, 14).unwrap().and_hms_opt(17, 30, 5).unwrap();
+    /// assert_eq!(dt.start_of_day(), NaiveDate::from_ymd_opt(2020, 2, 14).unwrap().and_hms_opt(0, 0, 0));
+    #[inline]
+    fn start_of_day(&self) -> Option<Self> {
+        Some(self.date().and_time(NaiveTime::MIN))
+    }
+
+    /// Returns the end of the day of the datetime as per the convention
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::datetime::{DateTimeTransitions, EndConvention};
+    ///
+    /// let dt = NaiveDate::from_ymd_opt(2020, 2, 29).unwrap().and_hms_opt(17, 30, 5).unwrap();
+    /// assert_eq!(
+    ///     dt.end_of_day(EndConvention::Inclusive),
+    ///     NaiveDate::from_ymd_opt(2020, 2, 29).unwrap().and_hms_nano_opt(23, 59, 59, 999_999_999)
+    /// );
+    /// assert_eq!(
+    ///     dt.end_of_day(EndConvention::Exclusive),
+    ///     NaiveDate::from_ymd_opt(2020, 3, 1).unwrap().and_hms_opt(0, 0, 0)
+    /// );
+    #[inline]
+    fn end_of_day(&self, convention: EndConvention) -> Option<Self> {
+        match convention {
+            EndConvention::Inclusive => self.date().and_hms_nano_opt(23, 59, 59, 999_999_999),
+            EndConvention::Exclusive => Some(self.date().succ_opt()?.and_time(NaiveTime::MIN)),
+        }
+    }
+
+    /// Returns the datetime truncated to the start of its hour
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::datetime::{DateTimeTransitions, EndConvention};
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 2, 14).unwrap();
+    /// let dt = d.and_hms_milli_opt(17, 30, 5, 250).unwrap();
+    /// assert_eq!(dt.start_of_hour(), d.and_hms_opt(17, 0, 0));
+    /// assert_eq!(dt.start_of_minute(), d.and_hms_opt(17, 30, 0));
+    /// assert_eq!(dt.start_of_second(), d.and_hms_opt(17, 30, 5));
+    /// assert_eq!(dt.end_of_hour(EndConvention::Inclusive), d.and_hms_nano_opt(17, 59, 59, 999_999_999));
+    /// assert_eq!(dt.end_of_minute(EndConvention::Exclusive), d.and_hms_opt(17, 31, 0));
+    /// assert_eq!(dt.end_of_second(EndConvention::Inclusive), d.and_hms_nano_opt(17, 30, 5, 999_999_999));
+    #[inline]
+    fn start_of_hour(&self) -> Option<Self> {
+        self.date().and_hms_opt(self.hour(), 0, 0)
+    }
+
+    #[inline]
+    fn end_of_hour(&self, convention: EndConvention) -> Option<Self> {
+        match convention {
+            EndConvention::Inclusive => {
+                self.date()
+                    .and_hms_nano_opt(self.hour(), 59, 59, 999_999_999)
+            }
+            EndConvention::Exclusive => self
+                .start_of_hour()?
+                .checked_add_signed(TimeDelta::hours(1)),
+        }
+    }
+
+    #[inline]
+    fn start_of_minute(&self) -> Option<Self> {
+        self.date().and_hms_opt(self.hour(), self.minute(), 0)
+    }
+
+    #[inline]
+    fn end_of_minute(&self, convention: EndConvention) -> Option<Self> {
+        match convention {
+            EndConvention::Inclusive => {
+                self.date()
+                    .and_hms_nano_opt(self.hour(), self.minute(), 59, 999_999_999)
+            }
+            EndConvention::Exclusive => self
+                .start_of_minute()?
+                .checked_add_signed(TimeDelta::minutes(1)),
+        }
+    }
+
+    /// Returns the datetime truncated to the start of its second. A datetime within a leap
+    /// second is truncated to the start of the leap second.
+    #[inline]
+    fn start_of_second(&self) -> Option<Self> {
+        self.with_nanosecond(self.nanosecond() / 1_000_000_000 * 1_000_000_000)
+    }
+
+    #[inline]
+    fn end_of_second(&self, convention: EndConvention) -> Option<Self> {
+        let start = self.start_of_second()?;
+        match convention {
+            EndConvention::Inclusive => start.with_nanosecond(start.nanosecond() + 999_999_999),
+            EndConvention::Exclusive => start.checked_add_signed(TimeDelta::seconds(1)),
+        }
+    }
+
+    /// Returns the latest multiple of the interval on or before the datetime
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, TimeDelta};
+    /// use chrono_utilities::naive::datetime::DateTimeTransitions;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 2, 14).unwrap();
+    /// let dt = d.and_hms_opt(17, 37, 30).unwrap();
+    /// assert_eq!(dt.floor_to(TimeDelta::minutes(5)), d.and_hms_opt(17, 35, 0));
+    /// assert_eq!(dt.ceil_to(TimeDelta::minutes(15)), d.and_hms_opt(17, 45, 0));
+    /// assert_eq!(dt.round_to(TimeDelta::minutes(10)), d.and_hms_opt(17, 40, 0));
+    /// assert_eq!(dt.round_to(TimeDelta::hours(1)), d.and_hms_opt(18, 0, 0));
+    /// // Half-way points round up
+    /// assert_eq!(dt.round_to(TimeDelta::minutes(15)), d.and_hms_opt(17, 45, 0));
+    /// // Rounding may cross midnight
+    /// let late = d.and_hms_opt(23, 53, 0).unwrap();
+    /// assert_eq!(late.round_to(TimeDelta::minutes(15)), d.succ_opt().unwrap().and_hms_opt(0, 0, 0));
+    /// assert_eq!(dt.floor_to(TimeDelta::zero()), None);
+    #[inline]
+    fn floor_to(&self, interval: TimeDelta) -> Option<Self> {
+        round_with(self, interval, |nanos, _, rem| nanos - rem)
+    }
+
+    #[inline]
+    fn ceil_to(&self, interval: TimeDelta) -> Option<Self> {
+        round_with(self, interval, |nanos, interval, rem| {
+            if rem == 0 {
+                nanos
+            } else {
+                nanos - rem + interval
+            }
+        })
+    }
+
+    #[inline]
+    fn round_to(&self, interval: TimeDelta) -> Option<Self> {
+        round_with(self, interval, |nanos, interval, rem| {
+            if rem * 2 >= interval {
+                nanos - rem + interval
+            } else {
+                nanos - rem
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DateTimeTransitions;
+    use chrono::{NaiveDate, NaiveDateTime, TimeDelta, Timelike};
+
+    #[test]
+    fn test_rounding_before_epoch_and_at_bounds() {
+        let dt = NaiveDate::from_ymd_opt(1969, 12, 31)
+            .unwrap()
+            .and_hms_milli_opt(23, 59, 59, 500)
+            .unwrap();
+        let second = TimeDelta::seconds(1);
+        assert_eq!(dt.floor_to(second), dt.with_nanosecond(0));
+        assert_eq!(
+            dt.round_to(second),
+            NaiveDate::from_ymd_opt(1970, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+        );
+        assert_eq!(dt.ceil_to(-second), None);
+        assert_eq!(NaiveDateTime::MAX.ceil_to(TimeDelta::days(1)), None);
+        assert_eq!(
+            NaiveDateTime::MIN.floor_to(TimeDelta::days(1)),
+            Some(NaiveDateTime::MIN)
+        );
+    }
+}
@@ -0,0 +1,746 @@
+//! Calendar-aware durations made of years, months, weeks and days, optionally followed by a
+//! time part as in ISO 8601 durations
+use super::shift_months;
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, TimeDelta};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::num::TryFromIntError;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+/// A duration expressed in calendar units. Unlike a fixed number of days, adding a month to a
+/// date moves it to the same day of the following month, clamped to the length of that month.
+///
+/// Durations can be added to and subtracted from dates and datetimes with the `+` and `-`
+/// operators, which panic when the result is out of range, as well as added to, subtracted from
+/// and negated component by component, in the manner of Python's `relativedelta`.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::duration::CalendarDuration;
+///
+/// let d = NaiveDate::from_ymd_opt(2020, 1, 31).unwrap();
+/// let period = CalendarDuration::months(1) + CalendarDuration::days(2);
+/// assert_eq!(d + period, NaiveDate::from_ymd_opt(2020, 3, 2).unwrap());
+/// assert_eq!(d - period, NaiveDate::from_ymd_opt(2019, 12, 29).unwrap());
+/// assert_eq!(d.and_hms_opt(12, 0, 0).unwrap() + -period, NaiveDate::from_ymd_opt(2019, 12, 29).unwrap().and_hms_opt(12, 0, 0).unwrap());
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CalendarDuration {
+    years: i32,
+    months: i32,
+    weeks: i32,
+    days: i32,
+}
+
+impl CalendarDuration {
+    /// Creates a duration from its calendar components
+    #[inline]
+    pub fn new(years: i32, months: i32, weeks: i32, days: i32) -> Self {
+        CalendarDuration {
+            years,
+            months,
+            weeks,
+            days,
+        }
+    }
+
+    /// Creates a duration of the given number of years
+    #[inline]
+    pub fn years(years: i32) -> Self {
+        CalendarDuration::new(years, 0, 0, 0)
+    }
+
+    /// Creates a duration of the given number of months
+    #[inline]
+    pub fn months(months: i32) -> Self {
+        CalendarDuration::new(0, months, 0, 0)
+    }
+
+    /// Creates a duration of the given number of weeks
+    #[inline]
+    pub fn weeks(weeks: i32) -> Self {
+        CalendarDuration::new(0, 0, weeks, 0)
+    }
+
+    /// Creates a duration of the given number of days
+    #[inline]
+    pub fn days(days: i32) -> Self {
+        CalendarDuration::new(0, 0, 0, days)
+    }
+
+    /// Returns the years component of the duration
+    #[inline]
+    pub fn num_years(&self) -> i32 {
+        self.years
+    }
+
+    /// Returns the months component of the duration
+    #[inline]
+    pub fn num_months(&self) -> i32 {
+        self.months
+    }
+
+    /// Returns the weeks component of the duration
+    #[inline]
+    pub fn num_weeks(&self) -> i32 {
+        self.weeks
+    }
+
+    /// Returns the days component of the duration
+    #[inline]
+    pub fn num_days(&self) -> i32 {
+        self.days
+    }
+
+    /// Returns true if every component of the duration is zero
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        *self == CalendarDuration::default()
+    }
+
+    /// Multiplies every component of the duration by the given factor. Returns `None` on
+    /// overflow.
+    pub fn checked_mul(&self, factor: i32) -> Option<Self> {
+        Some(CalendarDuration {
+            years: self.years.checked_mul(factor)?,
+            months: self.months.checked_mul(factor)?,
+            weeks: self.weeks.checked_mul(factor)?,
+            days: self.days.checked_mul(factor)?,
+        })
+    }
+
+    /// Returns the duration with months folded into years and days into weeks, so that months
+    /// fall between -11 and 11 and days between -6 and 6. Components keep the sign of their
+    /// totals, so a year less a month becomes 11 months.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono_utilities::naive::duration::CalendarDuration;
+    ///
+    /// assert_eq!(CalendarDuration::new(0, 14, 0, 10).normalize(), CalendarDuration::new(1, 2, 1, 3));
+    /// assert_eq!(CalendarDuration::new(1, -1, 0, -8).normalize(), CalendarDuration::new(0, 11, -1, -1));
+    pub fn normalize(&self) -> Self {
+        let months = self.years as i64 * 12 + self.months as i64;
+        let days = self.weeks as i64 * 7 + self.days as i64;
+        CalendarDuration {
+            years: (months / 12) as i32,
+            months: (months % 12) as i32,
+            weeks: (days / 7) as i32,
+            days: (days % 7) as i32,
+        }
+    }
+
+    /// Adds the components of both durations. Returns `None` on overflow.
+    pub fn checked_add(&self, other: &CalendarDuration) -> Option<Self> {
+        Some(CalendarDuration {
+            years: self.years.checked_add(other.years)?,
+            months: self.months.checked_add(other.months)?,
+            weeks: self.weeks.checked_add(other.weeks)?,
+            days: self.days.checked_add(other.days)?,
+        })
+    }
+
+    /// Subtracts the components of the other duration from those of this one. Returns `None` on
+    /// overflow.
+    pub fn checked_sub(&self, other: &CalendarDuration) -> Option<Self> {
+        self.checked_add(&other.checked_mul(-1)?)
+    }
+
+    /// Adds the duration to the date. Years and months are added first, clamping the day to the
+    /// length of the resulting month, followed by weeks and days. Returns `None` if the result
+    /// is out of range.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::duration::CalendarDuration;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 1, 31).unwrap();
+    /// assert_eq!(CalendarDuration::months(1).add_to(d).unwrap(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap());
+    /// assert_eq!(CalendarDuration::new(1, 1, 0, 1).add_to(d).unwrap(), NaiveDate::from_ymd_opt(2021, 3, 1).unwrap());
+    /// assert_eq!(CalendarDuration::weeks(-1).add_to(d).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 24).unwrap());
+    pub fn add_to(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let months = i32::try_from(self.years as i64 * 12 + self.months as i64).ok()?;
+        let days = self.weeks as i64 * 7 + self.days as i64;
+        shift_months(date, months)?.checked_add_signed(TimeDelta::days(days))
+    }
+
+    /// Subtracts the duration from the date. Years and months are subtracted first, clamping the
+    /// day to the length of the resulting month, followed by weeks and days. Returns `None` if
+    /// the result is out of range.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::duration::CalendarDuration;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 3, 31).unwrap();
+    /// assert_eq!(CalendarDuration::months(1).sub_from(d).unwrap(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap());
+    pub fn sub_from(&self, date: NaiveDate) -> Option<NaiveDate> {
+        CalendarDuration {
+            years: self.years.checked_neg()?,
+            months: self.months.checked_neg()?,
+            weeks: self.weeks.checked_neg()?,
+            days: self.days.checked_neg()?,
+        }
+        .add_to(date)
+    }
+
+    /// Returns the duration as chrono's [Months](https://docs.rs/chrono/0.4.35/chrono/struct.Months.html)
+    /// and [Days](https://docs.rs/chrono/0.4.35/chrono/naive/struct.Days.html), years being
+    /// counted as 12 months and weeks as 7 days. Adding the months and then the days to a date
+    /// with chrono gives the same result as [add_to](#method.add_to). Returns `None` if either
+    /// part is negative or too large.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::duration::CalendarDuration;
+    ///
+    /// let duration = CalendarDuration::new(1, 1, 0, 1);
+    /// let (months, days) = duration.to_months_and_days().unwrap();
+    /// let d = NaiveDate::from_ymd_opt(2020, 1, 31).unwrap();
+    /// assert_eq!(d + months + days, duration.add_to(d).unwrap());
+    /// assert_eq!(CalendarDuration::months(-1).to_months_and_days(), None);
+    pub fn to_months_and_days(&self) -> Option<(Months, Days)> {
+        let months = self.years as i64 * 12 + self.months as i64;
+        let days = self.weeks as i64 * 7 + self.days as i64;
+        Some((
+            Months::new(u32::try_from(months).ok()?),
+            Days::new(u64::try_from(days).ok()?),
+        ))
+    }
+}
+
+/// Returns the difference between two dates broken down into years, months and days, such as
+/// the length of a tenancy or of a billing period. Weeks are not used.
+///
+/// The difference counts the whole months from `from` which do not go past `to`, then the
+/// days left, so that adding it to `from` with [add_to](struct.CalendarDuration.html#method.add_to)
+/// gives `to`. As adding months clamps the day to the length of the resulting month, a month
+/// counts from January 31 to the end of February, and March 1 is a month and a day after
+/// January 31. Days are thus borrowed from the month `from` falls in rather than from the month
+/// preceding `to`. If `to` falls before `from`, the result is the negated difference from `to`
+/// to `from`.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::duration::{difference, CalendarDuration};
+///
+/// let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+/// assert_eq!(difference(ymd(2019, 6, 15), ymd(2021, 8, 20)), CalendarDuration::new(2, 2, 0, 5));
+/// assert_eq!(difference(ymd(2021, 1, 31), ymd(2021, 2, 28)), CalendarDuration::months(1));
+/// assert_eq!(difference(ymd(2021, 1, 31), ymd(2021, 3, 1)), CalendarDuration::new(0, 1, 0, 1));
+/// assert_eq!(difference(ymd(2021, 3, 1), ymd(2021, 1, 31)), CalendarDuration::new(0, -1, 0, -1));
+pub fn difference(from: NaiveDate, to: NaiveDate) -> CalendarDuration {
+    if to < from {
+        return -difference(to, from);
+    }
+    let mut months = (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32;
+    let mut anchor = shift_months(from, months);
+    if anchor.map_or(true, |anchor| anchor > to) {
+        months -= 1;
+        anchor = shift_months(from, months);
+    }
+    let days = anchor.map_or(0, |anchor| to.signed_duration_since(anchor).num_days());
+    CalendarDuration::new(months / 12, months % 12, 0, days as i32)
+}
+
+/// A duration expressed in calendar units, as another name for
+/// [CalendarDuration](struct.CalendarDuration.html)
+pub type CalendarPeriod = CalendarDuration;
+
+impl Add for CalendarDuration {
+    type Output = CalendarDuration;
+
+    /// Adds the components of both durations
+    ///
+    /// # Panics
+    ///
+    /// Panics on overflow.
+    fn add(self, other: CalendarDuration) -> CalendarDuration {
+        self.checked_add(&other)
+            .expect("overflow when adding durations")
+    }
+}
+
+impl Sub for CalendarDuration {
+    type Output = CalendarDuration;
+
+    /// Subtracts the components of the other duration
+    ///
+    /// # Panics
+    ///
+    /// Panics on overflow.
+    fn sub(self, other: CalendarDuration) -> CalendarDuration {
+        self.checked_sub(&other)
+            .expect("overflow when subtracting durations")
+    }
+}
+
+impl Neg for CalendarDuration {
+    type Output = CalendarDuration;
+
+    /// Negates every component of the duration
+    ///
+    /// # Panics
+    ///
+    /// Panics on overflow.
+    fn neg(self) -> CalendarDuration {
+        self.checked_mul(-1)
+            .expect("overflow when negating duration")
+    }
+}
+
+impl Add<CalendarDuration> for NaiveDate {
+    type Output = NaiveDate;
+
+    /// Adds the duration to the date as [add_to](struct.CalendarDuration.html#method.add_to)
+    /// does
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is out of range.
+    fn add(self, duration: CalendarDuration) -> NaiveDate {
+        duration
+            .add_to(self)
+            .expect("`NaiveDate + CalendarDuration` out of range")
+    }
+}
+
+impl Sub<CalendarDuration> for NaiveDate {
+    type Output = NaiveDate;
+
+    /// Subtracts the duration from the date as
+    /// [sub_from](struct.CalendarDuration.html#method.sub_from) does
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is out of range.
+    fn sub(self, duration: CalendarDuration) -> NaiveDate {
+        duration
+            .sub_from(self)
+            .expect("`NaiveDate - CalendarDuration` out of range")
+    }
+}
+
+impl Add<CalendarDuration> for NaiveDateTime {
+    type Output = NaiveDateTime;
+
+    /// Adds the duration to the date of the datetime, keeping its time
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is out of range.
+    fn add(self, duration: CalendarDuration) -> NaiveDateTime {
+        (self.date() + duration).and_time(self.time())
+    }
+}
+
+impl Sub<CalendarDuration> for NaiveDateTime {
+    type Output = NaiveDateTime;
+
+    /// Subtracts the duration from the date of the datetime, keeping its time
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is out of range.
+    fn sub(self, duration: CalendarDuration) -> NaiveDateTime {
+        (self.date() - duration).and_time(self.time())
+    }
+}
+
+impl TryFrom<Months> for CalendarDuration {
+    type Error = TryFromIntError;
+
+    /// Converts chrono's months into a duration, failing if they do not fit in an `i32`
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::Months;
+    /// use chrono_utilities::naive::duration::CalendarDuration;
+    /// use std::convert::TryFrom;
+    ///
+    /// let duration = CalendarDuration::try_from(Months::new(18)).unwrap();
+    /// assert_eq!(duration, CalendarDuration::months(18));
+    fn try_from(months: Months) -> Result<Self, TryFromIntError> {
+        Ok(CalendarDuration::months(i32::try_from(months.as_u32())?))
+    }
+}
+
+/// An ISO 8601 duration such as `P1Y2M10D` or `PT2H30M`, made of a calendar part in years,
+/// months, weeks and days, and of a time part in hours, minutes and seconds. The calendar part
+/// is added first, as a [CalendarDuration](struct.CalendarDuration.html), followed by the time
+/// part as an exact amount of time.
+///
+/// Besides the standard format, weeks may be combined with other units (`P1M2W`), components
+/// may be negative (`P1Y-1M`), and a leading minus sign negates every component (`-P1D`).
+/// Seconds may have a fraction of up to 9 digits, separated by a dot or a comma.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, TimeDelta};
+/// use chrono_utilities::naive::duration::{CalendarDuration, IsoDuration};
+///
+/// let d: IsoDuration = "P1Y2M10DT2H30M".parse().unwrap();
+/// assert_eq!(d.calendar(), CalendarDuration::new(1, 2, 0, 10));
+/// assert_eq!(d.time(), TimeDelta::minutes(150));
+/// let start = NaiveDate::from_ymd_opt(2020, 1, 31).unwrap().and_hms_opt(23, 0, 0).unwrap();
+/// assert_eq!(d.add_to(start), NaiveDate::from_ymd_opt(2021, 4, 11).unwrap().and_hms_opt(1, 30, 0));
+/// assert_eq!(d.to_string(), "P1Y2M10DT2H30M");
+/// assert_eq!("-PT1.5S".parse::<IsoDuration>().unwrap().time(), TimeDelta::milliseconds(-1500));
+/// assert!("P1H".parse::<IsoDuration>().is_err());
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct IsoDuration {
+    calendar: CalendarDuration,
+    time: TimeDelta,
+}
+
+impl IsoDuration {
+    /// Creates a duration from its calendar part and its time part
+    #[inline]
+    pub fn new(calendar: CalendarDuration, time: TimeDelta) -> Self {
+        IsoDuration { calendar, time }
+    }
+
+    /// Returns the calendar part of the duration
+    #[inline]
+    pub fn calendar(&self) -> CalendarDuration {
+        self.calendar
+    }
+
+    /// Returns the time part of the duration
+    #[inline]
+    pub fn time(&self) -> TimeDelta {
+        self.time
+    }
+
+    /// Returns true if both parts of the duration are zero
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.calendar.is_zero() && self.time.is_zero()
+    }
+
+    /// Multiplies both parts of the duration by the given factor. Returns `None` on overflow.
+    pub fn checked_mul(&self, factor: i32) -> Option<Self> {
+        Some(IsoDuration {
+            calendar: self.calendar.checked_mul(factor)?,
+            time: self.time.checked_mul(factor)?,
+        })
+    }
+
+    /// Adds the duration to the datetime, the calendar part first. Returns `None` if the
+    /// result is out of range.
+    pub fn add_to(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.calendar
+            .add_to(datetime.date())?
+            .and_time(datetime.time())
+            .checked_add_signed(self.time)
+    }
+
+    /// Subtracts the duration from the datetime, the calendar part first. Returns `None` if the
+    /// result is out of range.
+    pub fn sub_from(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.calendar
+            .sub_from(datetime.date())?
+            .and_time(datetime.time())
+            .checked_sub_signed(self.time)
+    }
+
+    /// Adds the duration to the date. Returns `None` if the time part is not zero or if the
+    /// result is out of range.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::duration::IsoDuration;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 1, 31).unwrap();
+    /// let month: IsoDuration = "P1M".parse().unwrap();
+    /// assert_eq!(month.add_to_date(d), NaiveDate::from_ymd_opt(2020, 2, 29));
+    /// let hour: IsoDuration = "PT1H".parse().unwrap();
+    /// assert_eq!(hour.add_to_date(d), None);
+    pub fn add_to_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        if self.time.is_zero() {
+            self.calendar.add_to(date)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<CalendarDuration> for IsoDuration {
+    #[inline]
+    fn from(calendar: CalendarDuration) -> Self {
+        IsoDuration::new(calendar, TimeDelta::zero())
+    }
+}
+
+impl fmt::Display for IsoDuration {
+    /// Formats the duration in the ISO 8601 format, leaving out zero components. A zero
+    /// duration is formatted as `PT0S`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let calendar = [
+            (self.calendar.years as i64, 'Y'),
+            (self.calendar.months as i64, 'M'),
+            (self.calendar.weeks as i64, 'W'),
+            (self.calendar.days as i64, 'D'),
+        ];
+        let negative = calendar.iter().all(|(value, _)| *value <= 0)
+            && self.time <= TimeDelta::zero()
+            && !self.is_zero();
+        let (sign, time) = if negative {
+            f.write_str("-")?;
+            (-1, -self.time)
+        } else {
+            (1, self.time)
+        };
+        f.write_str("P")?;
+        for (value, designator) in calendar.iter().filter(|(value, _)| *value != 0) {
+            write!(f, "{}{}", value * sign, designator)?;
+        }
+        if time.is_zero() {
+            return if self.calendar.is_zero() {
+                f.write_str("T0S")
+            } else {
+                Ok(())
+            };
+        }
+        f.write_str("T")?;
+        let seconds = time.num_seconds();
+        let nanos = time.subsec_nanos();
+        if seconds / 3600 != 0 {
+            write!(f, "{}H", seconds / 3600)?;
+        }
+        if seconds % 3600 / 60 != 0 {
+            write!(f, "{}M", seconds % 3600 / 60)?;
+        }
+        if seconds % 60 != 0 || nanos != 0 {
+            if seconds % 60 < 0 || nanos < 0 {
+                f.write_str("-")?;
+            }
+            write!(f, "{}", (seconds % 60).abs())?;
+            if nanos != 0 {
+                let fraction = format!("{:09}", nanos.abs());
+                write!(f, ".{}", fraction.trim_end_matches('0'))?;
+            }
+            f.write_str("S")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for IsoDuration {
+    type Err = ParseDurationError;
+
+    /// Parses an ISO 8601 duration such as `P3W`, `P1Y2M10DT2H30M` or `PT0.5S`
+    fn from_str(s: &str) -> Result<Self, ParseDurationError> {
+        let err = |part| ParseDurationError { part };
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let s = s.strip_prefix('P').ok_or(err("designator"))?;
+        let (date, time) = match s.split_once('T') {
+            Some((_, "")) => return Err(err("time")),
+            Some((date, time)) => (date, Some(time)),
+            None => (s, None),
+        };
+        if date.is_empty() && time.is_none() {
+            return Err(err("duration"));
+        }
+        let mut calendar = [0i32; 4];
+        let mut next = 0;
+        for (value, designator) in components(date).ok_or(err("date"))? {
+            let index = "YMWD"
+                .find(designator)
+                .filter(|index| *index >= next)
+                .ok_or(err("date"))?;
+            calendar[index] = value.parse().map_err(|_| err("date"))?;
+            next = index + 1;
+        }
+        let mut seconds: i64 = 0;
+        let mut nanos: i64 = 0;
+        let mut next = 0;
+        for (value, designator) in components(time.unwrap_or("")).ok_or(err("time"))? {
+            let index = "HMS"
+                .find(designator)
+                .filter(|index| *index >= next)
+                .ok_or(err("time"))?;
+            next = index + 1;
+            let (whole, fraction) = match value.split_once(['.', ',']) {
+                Some((whole, fraction)) if index == 2 => (whole, Some(fraction)),
+                Some(_) => return Err(err("time")),
+                None => (value, None),
+            };
+            let whole: i64 = whole.parse().map_err(|_| err("time"))?;
+            let factor = [3600, 60, 1][index];
+            seconds = whole
+                .checked_mul(factor)
+                .and_then(|whole| seconds.checked_add(whole))
+                .ok_or(err("time"))?;
+            if let Some(fraction) = fraction {
+                if fraction.is_empty()
+                    || fraction.len() > 9
+                    || !fraction.bytes().all(|b| b.is_ascii_digit())
+                {
+                    return Err(err("time"));
+                }
+                nanos = format!("{:0<9}", fraction)
+                    .parse()
+                    .map_err(|_| err("time"))?;
+                if whole < 0 || value.starts_with('-') {
+                    nanos = -nanos;
+                }
+            }
+        }
+        let time = TimeDelta::try_seconds(seconds)
+            .and_then(|time| time.checked_add(&TimeDelta::nanoseconds(nanos)))
+            .ok_or(err("time"))?;
+        let duration = IsoDuration::new(
+            CalendarDuration::new(calendar[0], calendar[1], calendar[2], calendar[3]),
+            time,
+        );
+        if negative {
+            Ok(IsoDuration::new(
+                duration.calendar.checked_mul(-1).ok_or(err("date"))?,
+                -duration.time,
+            ))
+        } else {
+            Ok(duration)
+        }
+    }
+}
+
+/// Splits a part of a duration into its values and their designators, such as `10` and `D`.
+/// Returns `None` if a value is empty or a designator is missing.
+fn components(part: &str) -> Option<Vec<(&str, char)>> {
+    let mut components = Vec::new();
+    let mut rest = part;
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| c.is_ascii_alphabetic())?;
+        let designator = rest[end..].chars().next()?;
+        if end == 0 {
+            return None;
+        }
+        components.push((&rest[..end], designator));
+        rest = &rest[end + 1..];
+    }
+    Some(components)
+}
+
+/// Error returned when parsing an invalid ISO 8601 duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseDurationError {
+    part: &'static str,
+}
+
+impl fmt::Display for ParseDurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid duration {}", self.part)
+    }
+}
+
+impl Error for ParseDurationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{difference, CalendarDuration, IsoDuration};
+    use chrono::{NaiveDate, TimeDelta};
+
+    #[test]
+    fn test_difference_adds_back() {
+        let start = NaiveDate::from_ymd_opt(2019, 12, 1).unwrap();
+        let dates: Vec<NaiveDate> = start.iter_days().take(500).step_by(7).collect();
+        for from in dates.iter().take(20) {
+            for to in dates.iter() {
+                let diff = difference(*from, *to);
+                assert_eq!(diff.num_weeks(), 0);
+                assert!((0..31).contains(&diff.num_days().abs()));
+                if from <= to {
+                    assert_eq!(diff.add_to(*from), Some(*to), "{} {}", from, to);
+                } else {
+                    assert_eq!(diff.sub_from(*to), Some(*from), "{} {}", from, to);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_to_out_of_range() {
+        let d = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        // 357913942 years are more months than fit in an i32
+        assert_eq!(CalendarDuration::years(357_913_942).add_to(d), None);
+        assert_eq!(CalendarDuration::years(-357_913_942).add_to(d), None);
+        assert_eq!(
+            CalendarDuration::new(i32::MAX, i32::MAX, 0, 0).sub_from(d),
+            None
+        );
+    }
+
+    #[test]
+    fn test_iso_round_trip() {
+        let cases = [
+            ("PT0S", IsoDuration::default()),
+            ("P3W", CalendarDuration::weeks(3).into()),
+            ("P1Y-1M", CalendarDuration::new(1, -1, 0, 0).into()),
+            (
+                "-P1DT1H",
+                IsoDuration::new(CalendarDuration::days(-1), TimeDelta::hours(-1)),
+            ),
+            (
+                "PT25H0.000000001S",
+                IsoDuration::new(
+                    CalendarDuration::default(),
+                    TimeDelta::hours(25) + TimeDelta::nanoseconds(1),
+                ),
+            ),
+            (
+                "-PT0.25S",
+                IsoDuration::new(CalendarDuration::days(0), TimeDelta::milliseconds(-250)),
+            ),
+            (
+                "P1DT-1M",
+                IsoDuration::new(CalendarDuration::days(1), TimeDelta::minutes(-1)),
+            ),
+        ];
+        for (s, duration) in cases.iter() {
+            assert_eq!(s.parse(), Ok(*duration), "{}", s);
+            assert_eq!(duration.to_string(), *s);
+        }
+        assert_eq!(
+            "PT1,5S".parse::<IsoDuration>().unwrap().time(),
+            TimeDelta::milliseconds(1500)
+        );
+        assert_eq!("PT-0.25S".parse(), "-PT0.25S".parse::<IsoDuration>());
+        let invalid = [
+            "",
+            "P",
+            "PT",
+            "1D",
+            "P1",
+            "PD",
+            "P1D1Y",
+            "P1DT",
+            "PT1D",
+            "PT1.5M",
+            "PT1.S",
+            "PT1.0000000001S",
+        ];
+        for invalid in invalid.iter() {
+            assert!(invalid.parse::<IsoDuration>().is_err(), "{}", invalid);
+        }
+    }
+}
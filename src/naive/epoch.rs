@@ -0,0 +1,713 @@
+//! Dates counted in days since the Unix epoch, for heavy date crunching in the integer domain
+use super::business::WeekendSet;
+use super::{days_in_month, is_leap_year, DatePredicates, DateTransitions};
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/// Number of days between 0001-01-01 and 1970-01-01
+const UNIX_EPOCH_FROM_CE: i32 = 719_163;
+
+/// A date stored as the number of days since 1970-01-01. Converting from and to
+/// [NaiveDate](https://docs.rs/chrono/0.4.35/chrono/naive/struct.NaiveDate.html) is cheap, and
+/// its [DateTransitions](../trait.DateTransitions.html) and
+/// [DatePredicates](../trait.DatePredicates.html) are computed arithmetically, so that batches
+/// of dates can be processed as plain integers and only converted at the edges. Transitions
+/// return `None` when their result falls outside of the range of dates supported by chrono, like
+/// their `NaiveDate` counterparts. Enabling the `verify` feature checks every transition and
+/// predicate against its `NaiveDate` counterpart in debug builds.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::epoch::EpochDay;
+/// use chrono_utilities::naive::DateTransitions;
+///
+/// let day = EpochDay::from(NaiveDate::from_ymd_opt(1970, 1, 2).unwrap());
+/// assert_eq!(day, EpochDay(1));
+/// let end = (day + 45).end_of_month().unwrap();
+/// assert_eq!(end.to_date(), Some(NaiveDate::from_ymd_opt(1970, 2, 28).unwrap()));
+/// assert_eq!(end - day, 57);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct EpochDay(pub i32);
+
+impl EpochDay {
+    /// Returns the day of the given date of the proleptic Gregorian calendar, or `None` if the
+    /// date is invalid or out of the range supported by chrono
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        EpochDay::checked(days_from_civil(year as i64, month, day))
+    }
+
+    /// Returns the date of the day, or `None` if it is out of the range supported by chrono
+    #[inline]
+    pub fn to_date(self) -> Option<NaiveDate> {
+        NaiveDate::from_num_days_from_ce_opt(self.0.checked_add(UNIX_EPOCH_FROM_CE)?)
+    }
+
+    /// Returns the year, month and day of the day
+    #[inline]
+    pub fn ymd(self) -> (i32, u32, u32) {
+        let (year, month, day) = civil_from_days(self.0 as i64);
+        (year as i32, month, day)
+    }
+
+    /// Returns the day of the week
+    #[inline]
+    pub fn weekday(self) -> Weekday {
+        match (self.0 as i64 + 3).rem_euclid(7) {
+            0 => Weekday::Mon,
+            1 => Weekday::Tue,
+            2 => Weekday::Wed,
+            3 => Weekday::Thu,
+            4 => Weekday::Fri,
+            5 => Weekday::Sat,
+            _ => Weekday::Sun,
+        }
+    }
+
+    /// Converts a batch of dates
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::epoch::EpochDay;
+    ///
+    /// let dates = [NaiveDate::from_ymd_opt(1969, 12, 31).unwrap(), NaiveDate::from_ymd_opt(2000, 3, 1).unwrap()];
+    /// let days = EpochDay::from_dates(&dates);
+    /// assert_eq!(days, vec![EpochDay(-1), EpochDay(11017)]);
+    /// assert_eq!(EpochDay::to_dates(&days), Some(dates.to_vec()));
+    pub fn from_dates(dates: &[NaiveDate]) -> Vec<EpochDay> {
+        dates.iter().map(|date| EpochDay::from(*date)).collect()
+    }
+
+    /// Converts a batch of days back to dates. Returns `None` if any of the days is out of the
+    /// range supported by chrono.
+    pub fn to_dates(days: &[EpochDay]) -> Option<Vec<NaiveDate>> {
+        days.iter().map(|day| day.to_date()).collect()
+    }
+
+    /// Returns the day the given number of days after this one, or `None` if it falls outside of
+    /// the range supported by chrono
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::epoch::EpochDay;
+    ///
+    /// assert_eq!(EpochDay(0).checked_add_days(-1), Some(EpochDay(-1)));
+    /// assert_eq!(EpochDay::from(NaiveDate::MAX).checked_add_days(1), None);
+    /// assert_eq!(EpochDay(i32::MAX).checked_add_days(1), None);
+    #[inline]
+    pub fn checked_add_days(self, days: i32) -> Option<Self> {
+        self.offset(days as i64)
+    }
+
+    /// Returns the day the given number of days before this one, or `None` if it falls outside of
+    /// the range supported by chrono
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::epoch::EpochDay;
+    ///
+    /// assert_eq!(EpochDay(0).checked_sub_days(1), Some(EpochDay(-1)));
+    /// assert_eq!(EpochDay::from(NaiveDate::MIN).checked_sub_days(1), None);
+    #[inline]
+    pub fn checked_sub_days(self, days: i32) -> Option<Self> {
+        self.offset(-(days as i64))
+    }
+
+    /// Returns the day if it lies within the range supported by chrono
+    fn checked(days: i64) -> Option<Self> {
+        let min = EpochDay::from(NaiveDate::MIN).0 as i64;
+        let max = EpochDay::from(NaiveDate::MAX).0 as i64;
+        if min <= days && days <= max {
+            Some(EpochDay(days as i32))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the result of an arithmetic transition. With the `verify` feature enabled, the
+    /// result is debug asserted to be equal to the one of the chrono based implementation.
+    #[inline]
+    fn verified<T, F>(self, fast: T, reference: F) -> T
+    where
+        T: PartialEq + fmt::Debug,
+        F: FnOnce(&NaiveDate) -> T,
+    {
+        if cfg!(feature = "verify") {
+            if let Some(date) = self.to_date() {
+                debug_assert_eq!(fast, reference(&date), "diverging results for {}", date);
+            }
+        }
+        fast
+    }
+
+    /// Same as [verified](#method.verified) for transitions returning a day
+    #[inline]
+    fn verified_day(
+        self,
+        fast: Option<EpochDay>,
+        reference: fn(&NaiveDate) -> Option<NaiveDate>,
+    ) -> Option<EpochDay> {
+        if cfg!(feature = "verify") {
+            if let Some(date) = self.to_date() {
+                let expected = reference(&date).map(EpochDay::from);
+                debug_assert_eq!(fast, expected, "diverging results for {}", date);
+            }
+        }
+        fast
+    }
+
+    /// Returns the day the given number of days after this one, if within range
+    #[inline]
+    fn offset(self, days: i64) -> Option<Self> {
+        EpochDay::checked(self.0 as i64 + days)
+    }
+}
+
+impl From<NaiveDate> for EpochDay {
+    #[inline]
+    fn from(date: NaiveDate) -> Self {
+        EpochDay(date.num_days_from_ce() - UNIX_EPOCH_FROM_CE)
+    }
+}
+
+impl Add<i32> for EpochDay {
+    type Output = EpochDay;
+
+    /// Returns the day the given number of days after this one, which may fall outside of the
+    /// range supported by chrono
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of days overflows. Use
+    /// [checked_add_days](struct.EpochDay.html#method.checked_add_days) to get `None` instead.
+    #[inline]
+    fn add(self, days: i32) -> EpochDay {
+        EpochDay(
+            self.0
+                .checked_add(days)
+                .expect("`EpochDay + i32` overflowed"),
+        )
+    }
+}
+
+impl Sub<i32> for EpochDay {
+    type Output = EpochDay;
+
+    /// Returns the day the given number of days before this one, which may fall outside of the
+    /// range supported by chrono
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of days overflows. Use
+    /// [checked_sub_days](struct.EpochDay.html#method.checked_sub_days) to get `None` instead.
+    #[inline]
+    fn sub(self, days: i32) -> EpochDay {
+        EpochDay(
+            self.0
+                .checked_sub(days)
+                .expect("`EpochDay - i32` overflowed"),
+        )
+    }
+}
+
+impl Sub<EpochDay> for EpochDay {
+    type Output = i32;
+
+    /// Returns the number of days between both days
+    #[inline]
+    fn sub(self, rhs: EpochDay) -> i32 {
+        self.0 - rhs.0
+    }
+}
+
+impl DateTransitions for EpochDay {
+    #[inline]
+    fn is_leap_year(&self) -> bool {
+        self.verified(is_leap_year(self.ymd().0), NaiveDate::is_leap_year)
+    }
+
+    #[inline]
+    fn last_day_of_month(&self) -> u32 {
+        let (year, month, _) = self.ymd();
+        self.verified(days_in_month(year, month), NaiveDate::last_day_of_month)
+    }
+
+    #[inline]
+    fn days_in_year(&self) -> u32 {
+        let days = if is_leap_year(self.ymd().0) { 366 } else { 365 };
+        self.verified(days, NaiveDate::days_in_year)
+    }
+
+    #[inline]
+    fn days_in_quarter(&self) -> u32 {
+        let (year, month, _) = self.ymd();
+        let first = (month - 1) / 3 * 3 + 1;
+        self.verified(
+            (first..first + 3)
+                .map(|month| days_in_month(year, month))
+                .sum(),
+            NaiveDate::days_in_quarter,
+        )
+    }
+
+    fn start_of_year(&self) -> Option<Self> {
+        self.verified_day(
+            EpochDay::checked(days_from_civil(self.ymd().0 as i64, 1, 1)),
+            NaiveDate::start_of_year,
+        )
+    }
+
+    fn end_of_year(&self) -> Option<Self> {
+        self.verified_day(
+            EpochDay::checked(days_from_civil(self.ymd().0 as i64, 12, 31)),
+            NaiveDate::end_of_year,
+        )
+    }
+
+    fn start_of_month(&self) -> Option<Self> {
+        let (_, _, day) = self.ymd();
+        self.verified_day(self.offset(1 - day as i64), NaiveDate::start_of_month)
+    }
+
+    fn end_of_month(&self) -> Option<Self> {
+        let (year, month, day) = self.ymd();
+        self.verified_day(
+            self.offset(days_in_month(year, month) as i64 - day as i64),
+            NaiveDate::end_of_month,
+        )
+    }
+
+    fn start_of_iso8601_week(&self) -> Option<Self> {
+        self.verified_day(
+            self.offset(-(self.weekday().num_days_from_monday() as i64)),
+            NaiveDate::start_of_iso8601_week,
+        )
+    }
+
+    fn end_of_iso8601_week(&self) -> Option<Self> {
+        self.verified_day(
+            self.offset(6 - self.weekday().num_days_from_monday() as i64),
+            NaiveDate::end_of_iso8601_week,
+        )
+    }
+
+    fn start_of_pred_year(&self) -> Option<Self> {
+        self.verified_day(
+            EpochDay::checked(days_from_civil(self.ymd().0 as i64 - 1, 1, 1)),
+            NaiveDate::start_of_pred_year,
+        )
+    }
+
+    fn end_of_pred_year(&self) -> Option<Self> {
+        self.verified_day(
+            EpochDay::checked(days_from_civil(self.ymd().0 as i64 - 1, 12, 31)),
+            NaiveDate::end_of_pred_year,
+        )
+    }
+
+    fn start_of_pred_month(&self) -> Option<Self> {
+        let (year, month) = month_offset(self.ymd(), -1);
+        self.verified_day(
+            EpochDay::checked(days_from_civil(year, month, 1)),
+            NaiveDate::start_of_pred_month,
+        )
+    }
+
+    fn end_of_pred_month(&self) -> Option<Self> {
+        let (year, month) = month_offset(self.ymd(), -1);
+        self.verified_day(
+            EpochDay::checked(days_from_civil(
+                year,
+                month,
+                days_in_month(year as i32, month),
+            )),
+            NaiveDate::end_of_pred_month,
+        )
+    }
+
+    fn start_of_pred_iso8601_week(&self) -> Option<Self> {
+        self.verified_day(
+            self.start_of_iso8601_week()
+                .and_then(|start| start.offset(-7)),
+            NaiveDate::start_of_pred_iso8601_week,
+        )
+    }
+
+    fn end_of_pred_iso8601_week(&self) -> Option<Self> {
+        self.verified_day(
+            self.start_of_iso8601_week()
+                .and_then(|start| start.offset(-1)),
+            NaiveDate::end_of_pred_iso8601_week,
+        )
+    }
+
+    fn start_of_succ_year(&self) -> Option<Self> {
+        self.verified_day(
+            EpochDay::checked(days_from_civil(self.ymd().0 as i64 + 1, 1, 1)),
+            NaiveDate::start_of_succ_year,
+        )
+    }
+
+    fn end_of_succ_year(&self) -> Option<Self> {
+        self.verified_day(
+            EpochDay::checked(days_from_civil(self.ymd().0 as i64 + 1, 12, 31)),
+            NaiveDate::end_of_succ_year,
+        )
+    }
+
+    fn start_of_succ_month(&self) -> Option<Self> {
+        let (year, month) = month_offset(self.ymd(), 1);
+        self.verified_day(
+            EpochDay::checked(days_from_civil(year, month, 1)),
+            NaiveDate::start_of_succ_month,
+        )
+    }
+
+    fn end_of_succ_month(&self) -> Option<Self> {
+        let (year, month) = month_offset(self.ymd(), 1);
+        self.verified_day(
+            EpochDay::checked(days_from_civil(
+                year,
+                month,
+                days_in_month(year as i32, month),
+            )),
+            NaiveDate::end_of_succ_month,
+        )
+    }
+
+    fn start_of_succ_iso8601_week(&self) -> Option<Self> {
+        self.verified_day(
+            self.start_of_iso8601_week()
+                .and_then(|start| start.offset(7)),
+            NaiveDate::start_of_succ_iso8601_week,
+        )
+    }
+
+    fn end_of_succ_iso8601_week(&self) -> Option<Self> {
+        self.verified_day(
+            self.start_of_iso8601_week()
+                .and_then(|start| start.offset(13)),
+            NaiveDate::end_of_succ_iso8601_week,
+        )
+    }
+
+    #[inline]
+    fn is_same_iso8601_week_as(&self, other: &Self) -> bool {
+        let fast = self.start_of_iso8601_week() == other.start_of_iso8601_week();
+        self.verified(fast, |date| {
+            other
+                .to_date()
+                .map_or(fast, |other| date.is_same_iso8601_week_as(&other))
+        })
+    }
+
+    #[inline]
+    fn is_same_month_as(&self, other: &Self) -> bool {
+        let (year, month, _) = self.ymd();
+        let (other_year, other_month, _) = other.ymd();
+        let fast = year == other_year && month == other_month;
+        self.verified(fast, |date| {
+            other
+                .to_date()
+                .map_or(fast, |other| date.is_same_month_as(&other))
+        })
+    }
+
+    #[inline]
+    fn is_same_quarter_as(&self, other: &Self) -> bool {
+        let (year, month, _) = self.ymd();
+        let (other_year, other_month, _) = other.ymd();
+        let fast = year == other_year && (month - 1) / 3 == (other_month - 1) / 3;
+        self.verified(fast, |date| {
+            other
+                .to_date()
+                .map_or(fast, |other| date.is_same_quarter_as(&other))
+        })
+    }
+
+    #[inline]
+    fn is_same_year_as(&self, other: &Self) -> bool {
+        let fast = self.ymd().0 == other.ymd().0;
+        self.verified(fast, |date| {
+            other
+                .to_date()
+                .map_or(fast, |other| date.is_same_year_as(&other))
+        })
+    }
+}
+
+impl DatePredicates for EpochDay {
+    #[inline]
+    fn is_weekend(&self) -> bool {
+        self.verified(
+            self.is_weekend_in(WeekendSet::SATURDAY_SUNDAY),
+            NaiveDate::is_weekend,
+        )
+    }
+
+    #[inline]
+    fn is_weekday(&self) -> bool {
+        self.verified(!self.is_weekend(), NaiveDate::is_weekday)
+    }
+
+    #[inline]
+    fn is_weekend_in(&self, weekend: WeekendSet) -> bool {
+        self.verified(weekend.contains(self.weekday()), |date| {
+            date.is_weekend_in(weekend)
+        })
+    }
+
+    #[inline]
+    fn is_weekday_in(&self, weekend: WeekendSet) -> bool {
+        self.verified(!weekend.contains(self.weekday()), |date| {
+            date.is_weekday_in(weekend)
+        })
+    }
+
+    #[inline]
+    fn is_first_day_of_month(&self) -> bool {
+        self.verified(self.ymd().2 == 1, NaiveDate::is_first_day_of_month)
+    }
+
+    #[inline]
+    fn is_last_day_of_month(&self) -> bool {
+        let (year, month, day) = self.ymd();
+        self.verified(
+            day == days_in_month(year, month),
+            NaiveDate::is_last_day_of_month,
+        )
+    }
+
+    #[inline]
+    fn is_first_day_of_quarter(&self) -> bool {
+        let (_, month, day) = self.ymd();
+        self.verified(
+            day == 1 && month % 3 == 1,
+            NaiveDate::is_first_day_of_quarter,
+        )
+    }
+
+    #[inline]
+    fn is_last_day_of_quarter(&self) -> bool {
+        let (_, month, _) = self.ymd();
+        self.verified(
+            month % 3 == 0 && self.is_last_day_of_month(),
+            NaiveDate::is_last_day_of_quarter,
+        )
+    }
+
+    #[inline]
+    fn is_first_day_of_year(&self) -> bool {
+        let (_, month, day) = self.ymd();
+        self.verified(month == 1 && day == 1, NaiveDate::is_first_day_of_year)
+    }
+
+    #[inline]
+    fn is_last_day_of_year(&self) -> bool {
+        let (_, month, day) = self.ymd();
+        self.verified(month == 12 && day == 31, NaiveDate::is_last_day_of_year)
+    }
+
+    #[inline]
+    fn is_start_of_iso8601_week(&self) -> bool {
+        self.verified(
+            self.weekday() == Weekday::Mon,
+            NaiveDate::is_start_of_iso8601_week,
+        )
+    }
+
+    #[inline]
+    fn is_end_of_iso8601_week(&self) -> bool {
+        self.verified(
+            self.weekday() == Weekday::Sun,
+            NaiveDate::is_end_of_iso8601_week,
+        )
+    }
+}
+
+/// Returns the year and month the given number of months away from the date
+fn month_offset((year, month, _): (i32, u32, u32), months: i64) -> (i64, u32) {
+    let total = year as i64 * 12 + month as i64 - 1 + months;
+    (total.div_euclid(12), total.rem_euclid(12) as u32 + 1)
+}
+
+/// Returns the number of days between 1970-01-01 and the given date. Years are shifted to start
+/// in March so that leap days fall at the end of the year, and counted in 400 year eras.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the year, month and day of the date the given number of days after 1970-01-01. This
+/// is the inverse of [days_from_civil](fn.days_from_civil.html).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EpochDay;
+    use crate::naive::business::WeekendSet;
+    use crate::naive::{DatePredicates, DateTransitions};
+    use chrono::{Datelike, NaiveDate};
+
+    fn assert_transitions_match(date: NaiveDate) {
+        let day = EpochDay::from(date);
+        let convert = |day: Option<EpochDay>| day.and_then(EpochDay::to_date);
+        assert_eq!(day.to_date(), Some(date));
+        assert_eq!(day.ymd(), (date.year(), date.month(), date.day()));
+        assert_eq!(day.weekday(), date.weekday());
+        assert_eq!(day.is_leap_year(), date.is_leap_year());
+        assert_eq!(day.last_day_of_month(), date.last_day_of_month());
+        assert_eq!(day.days_in_year(), date.days_in_year());
+        assert_eq!(day.days_in_quarter(), date.days_in_quarter());
+        assert_eq!(convert(day.start_of_year()), date.start_of_year());
+        assert_eq!(convert(day.end_of_year()), date.end_of_year());
+        assert_eq!(convert(day.start_of_month()), date.start_of_month());
+        assert_eq!(convert(day.end_of_month()), date.end_of_month());
+        assert_eq!(
+            convert(day.start_of_iso8601_week()),
+            date.start_of_iso8601_week()
+        );
+        assert_eq!(
+            convert(day.end_of_iso8601_week()),
+            date.end_of_iso8601_week()
+        );
+        assert_eq!(convert(day.start_of_pred_year()), date.start_of_pred_year());
+        assert_eq!(convert(day.end_of_pred_year()), date.end_of_pred_year());
+        assert_eq!(
+            convert(day.start_of_pred_month()),
+            date.start_of_pred_month()
+        );
+        assert_eq!(convert(day.end_of_pred_month()), date.end_of_pred_month());
+        assert_eq!(
+            convert(day.start_of_pred_iso8601_week()),
+            date.start_of_pred_iso8601_week()
+        );
+        assert_eq!(
+            convert(day.end_of_pred_iso8601_week()),
+            date.end_of_pred_iso8601_week()
+        );
+        assert_eq!(convert(day.start_of_succ_year()), date.start_of_succ_year());
+        assert_eq!(convert(day.end_of_succ_year()), date.end_of_succ_year());
+        assert_eq!(
+            convert(day.start_of_succ_month()),
+            date.start_of_succ_month()
+        );
+        assert_eq!(convert(day.end_of_succ_month()), date.end_of_succ_month());
+        assert_eq!(
+            convert(day.start_of_succ_iso8601_week()),
+            date.start_of_succ_iso8601_week()
+        );
+        assert_eq!(
+            convert(day.end_of_succ_iso8601_week()),
+            date.end_of_succ_iso8601_week()
+        );
+        assert_eq!(day.is_weekend(), date.is_weekend());
+        assert_eq!(
+            day.is_weekend_in(WeekendSet::FRIDAY_SATURDAY),
+            date.is_weekend_in(WeekendSet::FRIDAY_SATURDAY)
+        );
+        assert_eq!(
+            day.is_weekday_in(WeekendSet::SUNDAY),
+            date.is_weekday_in(WeekendSet::SUNDAY)
+        );
+        assert_eq!(day.is_first_day_of_month(), date.is_first_day_of_month());
+        assert_eq!(day.is_last_day_of_month(), date.is_last_day_of_month());
+        assert_eq!(
+            day.is_first_day_of_quarter(),
+            date.is_first_day_of_quarter()
+        );
+        assert_eq!(day.is_last_day_of_quarter(), date.is_last_day_of_quarter());
+        assert_eq!(day.is_first_day_of_year(), date.is_first_day_of_year());
+        assert_eq!(day.is_last_day_of_year(), date.is_last_day_of_year());
+        assert_eq!(
+            day.is_start_of_iso8601_week(),
+            date.is_start_of_iso8601_week()
+        );
+        if let Some(next_date) = date.succ_opt() {
+            let next = day + 1;
+            assert_eq!(
+                day.is_same_iso8601_week_as(&next),
+                date.is_same_iso8601_week_as(&next_date)
+            );
+            assert_eq!(
+                day.is_same_quarter_as(&next),
+                date.is_same_quarter_as(&next_date)
+            );
+            assert_eq!(
+                day.is_same_month_as(&next),
+                date.is_same_month_as(&next_date)
+            );
+            assert_eq!(day.is_same_year_as(&next), date.is_same_year_as(&next_date));
+        }
+    }
+
+    #[test]
+    fn test_transitions_match_naive_date() {
+        let mut date = NaiveDate::from_ymd_opt(1895, 1, 1).unwrap();
+        while date.year() < 2105 {
+            assert_transitions_match(date);
+            date = date.succ_opt().unwrap();
+        }
+        let mut date = NaiveDate::MIN;
+        for _ in 0..800 {
+            assert_transitions_match(date);
+            date = date.succ_opt().unwrap();
+        }
+        let mut date = NaiveDate::MAX;
+        for _ in 0..800 {
+            assert_transitions_match(date);
+            date = date.pred_opt().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let first = EpochDay::from(NaiveDate::MIN);
+        let last = EpochDay::from(NaiveDate::MAX);
+        assert_eq!(first.checked_add_days(last - first), Some(last));
+        assert_eq!(last.checked_sub_days(last - first), Some(first));
+        assert_eq!(last.checked_add_days(1), None);
+        assert_eq!(first.checked_add_days(-1), None);
+        assert_eq!(first.checked_sub_days(i32::MIN), None);
+        assert_eq!(last + 1 - 1, last);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn test_add_overflow() {
+        let _ = EpochDay(i32::MAX) + 1;
+    }
+}
@@ -0,0 +1,252 @@
+//! Calendar features of dates, meant for feeding analytics and machine learning pipelines
+use super::business::HolidayCalendar;
+use super::fiscal::FiscalCalendar;
+use super::period::{WeekConvention, YearDivisions};
+use super::range::DateRange;
+use super::{days_in_month, DateTransitions};
+use chrono::{Datelike, NaiveDate};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Returns the signed number of days from the date to the closest holiday of the calendar. The
+/// number is negative when the closest holiday lies in the past, zero when the date is itself a
+/// holiday, and the upcoming holiday wins ties. Returns `None` if the calendar has no holidays.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::business::HolidayCalendar;
+/// use chrono_utilities::naive::features::days_to_nearest_holiday;
+///
+/// let cal: HolidayCalendar = vec![
+///     NaiveDate::from_ymd_opt(2020, 12, 25).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+/// ].into_iter().collect();
+/// assert_eq!(days_to_nearest_holiday(NaiveDate::from_ymd_opt(2020, 12, 20).unwrap(), &cal), Some(5));
+/// assert_eq!(days_to_nearest_holiday(NaiveDate::from_ymd_opt(2020, 12, 27).unwrap(), &cal), Some(-2));
+/// assert_eq!(days_to_nearest_holiday(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), &cal), Some(0));
+/// assert_eq!(days_to_nearest_holiday(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), &HolidayCalendar::new()), None);
+pub fn days_to_nearest_holiday(date: NaiveDate, cal: &HolidayCalendar) -> Option<i64> {
+    let until = days_until_next_holiday(date, cal);
+    let since = days_since_prev_holiday(date, cal).map(|days| -days);
+    match (until, since) {
+        (Some(until), Some(since)) if -since < until => Some(since),
+        (Some(until), _) => Some(until),
+        (None, since) => since,
+    }
+}
+
+/// Returns true if the day following the date is a holiday
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::business::HolidayCalendar;
+/// use chrono_utilities::naive::features::{is_day_after_holiday, is_holiday_eve};
+///
+/// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()].into_iter().collect();
+/// assert!(is_holiday_eve(NaiveDate::from_ymd_opt(2020, 12, 24).unwrap(), &cal));
+/// assert!(!is_holiday_eve(NaiveDate::from_ymd_opt(2020, 12, 25).unwrap(), &cal));
+/// assert!(is_day_after_holiday(NaiveDate::from_ymd_opt(2020, 12, 26).unwrap(), &cal));
+#[inline]
+pub fn is_holiday_eve(date: NaiveDate, cal: &HolidayCalendar) -> bool {
+    date.succ_opt().is_some_and(|next| cal.is_holiday(next))
+}
+
+/// Returns true if the day preceding the date is a holiday
+#[inline]
+pub fn is_day_after_holiday(date: NaiveDate, cal: &HolidayCalendar) -> bool {
+    date.pred_opt().is_some_and(|prev| cal.is_holiday(prev))
+}
+
+/// Holiday related features of a single date. See
+/// [holiday_features](fn.holiday_features.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct HolidayFeatures {
+    /// The date the features describe
+    pub date: NaiveDate,
+    /// Whether the date is a holiday
+    pub is_holiday: bool,
+    /// Whether the following day is a holiday
+    pub is_holiday_eve: bool,
+    /// Whether the preceding day is a holiday
+    pub is_day_after_holiday: bool,
+    /// Number of days until the next holiday, zero on holidays
+    pub days_until_next: Option<i64>,
+    /// Number of days since the previous holiday, zero on holidays
+    pub days_since_prev: Option<i64>,
+    /// Signed number of days to the closest holiday, as per
+    /// [days_to_nearest_holiday](fn.days_to_nearest_holiday.html)
+    pub days_to_nearest: Option<i64>,
+}
+
+/// Returns the holiday related features of every date of the range
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::business::HolidayCalendar;
+/// use chrono_utilities::naive::features::holiday_features;
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()].into_iter().collect();
+/// let range = DateRange::new(NaiveDate::from_ymd_opt(2020, 12, 23).unwrap(), NaiveDate::from_ymd_opt(2020, 12, 27).unwrap()).unwrap();
+/// let features = holiday_features(range, &cal);
+/// assert_eq!(features.len(), 5);
+/// assert!(features[1].is_holiday_eve);
+/// assert!(features[2].is_holiday);
+/// assert_eq!(features[0].days_until_next, Some(2));
+/// assert_eq!(features[0].days_since_prev, None);
+/// assert_eq!(features[4].days_to_nearest, Some(-2));
+pub fn holiday_features(range: DateRange, cal: &HolidayCalendar) -> Vec<HolidayFeatures> {
+    range
+        .iter()
+        .map(|date| HolidayFeatures {
+            date,
+            is_holiday: cal.is_holiday(date),
+            is_holiday_eve: is_holiday_eve(date, cal),
+            is_day_after_holiday: is_day_after_holiday(date, cal),
+            days_until_next: days_until_next_holiday(date, cal),
+            days_since_prev: days_since_prev_holiday(date, cal),
+            days_to_nearest: days_to_nearest_holiday(date, cal),
+        })
+        .collect()
+}
+
+fn days_until_next_holiday(date: NaiveDate, cal: &HolidayCalendar) -> Option<i64> {
+    cal.next_holiday(date)
+        .map(|holiday| holiday.signed_duration_since(date).num_days())
+}
+
+fn days_since_prev_holiday(date: NaiveDate, cal: &HolidayCalendar) -> Option<i64> {
+    cal.prev_holiday(date)
+        .map(|holiday| date.signed_duration_since(holiday).num_days())
+}
+
+/// Conventions used to derive calendar features
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Conventions {
+    /// How weeks are numbered
+    pub week: WeekConvention,
+    /// How fiscal years and quarters are laid out
+    pub fiscal: FiscalCalendar,
+}
+
+impl Default for Conventions {
+    /// ISO 8601 weeks and fiscal years coinciding with calendar years
+    fn default() -> Self {
+        Conventions {
+            week: WeekConvention::ISO,
+            fiscal: FiscalCalendar::new(1).unwrap(),
+        }
+    }
+}
+
+/// Calendar features of a single date. See [calendar_features](fn.calendar_features.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CalendarFeatures {
+    /// The date the features describe
+    pub date: NaiveDate,
+    /// Calendar year
+    pub year: i32,
+    /// Quarter of the year, from 1 to 4
+    pub quarter: u32,
+    /// Month of the year, from 1 to 12
+    pub month: u32,
+    /// Week-numbering year, which differs from `year` for some days around January 1. Dates
+    /// too close to the bounds of `NaiveDate` for the convention to number their week fall back
+    /// to their ISO 8601 week-numbering year and week.
+    pub week_year: i32,
+    /// Week of the week-numbering year, starting from 1
+    pub week: u32,
+    /// Day of the week, from 1 for the first day of the week to 7
+    pub day_of_week: u32,
+    /// Day of the month, starting from 1
+    pub day_of_month: u32,
+    /// Day of the year, from 1 to 366
+    pub day_of_year: u32,
+    /// Fiscal year as per the conventions, named after the calendar year in which it ends
+    pub fiscal_year: i32,
+    /// Quarter of the fiscal year, from 1 to 4
+    pub fiscal_quarter: u32,
+    /// Whether the date falls on a weekend day of the holiday calendar
+    pub is_weekend: bool,
+    /// Whether the date is a holiday of the holiday calendar
+    pub is_holiday: bool,
+    /// Whether the date is neither a weekend day nor a holiday
+    pub is_business_day: bool,
+    /// Whether the date is the first day of its month
+    pub is_month_start: bool,
+    /// Whether the date is the last day of its month
+    pub is_month_end: bool,
+    /// Whether the date is the last day of a calendar quarter
+    pub is_quarter_end: bool,
+    /// Whether the date is December 31
+    pub is_year_end: bool,
+    /// Number of days left until the last day of the month, zero on the last day
+    pub days_to_month_end: u32,
+}
+
+/// Returns the calendar features of every date of the range, derived as per the given
+/// conventions and holiday calendar. Enabling the `serde` feature makes the rows serializable.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::business::HolidayCalendar;
+/// use chrono_utilities::naive::features::{calendar_features, Conventions};
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()].into_iter().collect();
+/// let range = DateRange::new(NaiveDate::from_ymd_opt(2020, 12, 25).unwrap(), NaiveDate::from_ymd_opt(2020, 12, 31).unwrap()).unwrap();
+/// let rows = calendar_features(range, &Conventions::default(), &cal);
+/// assert_eq!(rows.len(), 7);
+/// assert!(rows[0].is_holiday);
+/// assert_eq!(rows[0].day_of_week, 5);
+/// assert_eq!(rows[0].days_to_month_end, 6);
+/// assert_eq!(rows[6].week, 53);
+/// assert!(rows[6].is_month_end && rows[6].is_quarter_end && rows[6].is_year_end);
+pub fn calendar_features(
+    range: DateRange,
+    conventions: &Conventions,
+    cal: &HolidayCalendar,
+) -> Vec<CalendarFeatures> {
+    range
+        .iter()
+        .map(|date| {
+            let (week_year, week) = conventions
+                .week
+                .week_of_year(date)
+                .unwrap_or_else(|| (date.iso_week().year(), date.iso_week().week()));
+            let days_to_month_end = days_in_month(date.year(), date.month()) - date.day();
+            let is_month_end = days_to_month_end == 0;
+            CalendarFeatures {
+                date,
+                year: date.year(),
+                quarter: date.quarter_of_year(),
+                month: date.month(),
+                week_year,
+                week,
+                day_of_week: conventions.week.days_into_week(date) + 1,
+                day_of_month: date.day(),
+                day_of_year: date.ordinal(),
+                fiscal_year: conventions.fiscal.fiscal_year(date),
+                fiscal_quarter: conventions.fiscal.fiscal_quarter(date),
+                is_weekend: cal.is_weekend(date),
+                is_holiday: cal.is_holiday(date),
+                is_business_day: cal.is_business_day(date),
+                is_month_start: date.day() == 1,
+                is_month_end,
+                is_quarter_end: is_month_end && date.month() % 3 == 0,
+                is_year_end: date.end_of_year() == Some(date),
+                days_to_month_end,
+            }
+        })
+        .collect()
+}
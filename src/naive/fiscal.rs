@@ -0,0 +1,750 @@
+//! Fiscal calendars whose years do not coincide with the calendar year
+use super::key::PeriodKey;
+use super::period::{ParsePeriodError, PeriodCalendar, Unit};
+use super::range::DateRange;
+use super::weekday::last_weekday_of_month;
+use super::{shift_months, DateTransitions};
+use chrono::{Datelike, NaiveDate, TimeDelta, Weekday};
+use std::fmt;
+use std::str::FromStr;
+
+/// A numbered period of a fiscal year, such as the third month of fiscal year 2025, labelled
+/// `FY2025-P03`. The label does not depend on a calendar: the dates it spans are given by the
+/// fiscal calendar in use. Periods are numbered from 1 to 13 to accommodate 13 period years.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::fiscal::{FiscalCalendar, FiscalPeriod};
+///
+/// let period: FiscalPeriod = "FY2025-P03".parse().unwrap();
+/// assert_eq!(period, FiscalPeriod::new(2025, 3).unwrap());
+/// assert_eq!(period.to_string(), "FY2025-P03");
+/// let cal = FiscalCalendar::new(7).unwrap();
+/// let range = cal.fiscal_period(period).unwrap();
+/// assert_eq!(range.start(), NaiveDate::from_ymd_opt(2024, 9, 1).unwrap());
+/// assert_eq!(range.end(), NaiveDate::from_ymd_opt(2024, 9, 30).unwrap());
+/// assert!("FY2025-P3".parse::<FiscalPeriod>().is_err());
+/// assert!("FY2025-P14".parse::<FiscalPeriod>().is_err());
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FiscalPeriod {
+    year: i32,
+    period: u32,
+}
+
+impl FiscalPeriod {
+    /// Returns the given period of the given fiscal year. Returns `None` if the period does not
+    /// lie within `1..=13`.
+    pub fn new(year: i32, period: u32) -> Option<Self> {
+        if (1..=13).contains(&period) {
+            Some(FiscalPeriod { year, period })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the fiscal year
+    #[inline]
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the number of the period within its fiscal year, from 1 to 13
+    #[inline]
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+}
+
+impl fmt::Display for FiscalPeriod {
+    /// Formats the period as in `FY2025-P03`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        PeriodKey::from(*self).write_to(f)
+    }
+}
+
+impl FromStr for FiscalPeriod {
+    type Err = ParsePeriodError;
+
+    /// Parses a period formatted as in `FY2025-P03`
+    fn from_str(s: &str) -> Result<Self, ParsePeriodError> {
+        let err = ParsePeriodError::new("fiscal period");
+        let (year, period) = s
+            .strip_prefix("FY")
+            .and_then(|rest| rest.split_once("-P"))
+            .ok_or(err)?;
+        let year = year.parse().map_err(|_| err)?;
+        let period = period.parse().map_err(|_| err)?;
+        FiscalPeriod::new(year, period)
+            .filter(|parsed| parsed.to_string() == s)
+            .ok_or(err)
+    }
+}
+
+/// The style of the labels of fiscal years and quarters given by
+/// [FiscalCalendar::label](struct.FiscalCalendar.html#method.label)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LabelStyle {
+    /// The fiscal year, as in `FY2024`
+    Year,
+    /// The last two digits of the fiscal year, as in `FY24`, which only name years from 1970 to
+    /// 2069 unambiguously
+    ShortYear,
+    /// The fiscal year and quarter, as in `FY2024-Q3`
+    Quarter,
+    /// The initials of the months of the fiscal quarter, as in `AMJ`
+    QuarterMonths,
+}
+
+/// A fiscal year or quarter parsed from its label by
+/// [FiscalCalendar::parse_label](struct.FiscalCalendar.html#method.parse_label)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FiscalLabel {
+    /// A fiscal year
+    Year(i32),
+    /// A quarter, from 1 to 4, of a fiscal year
+    Quarter { year: i32, quarter: u32 },
+    /// A quarter, from 1 to 4, of an unspecified fiscal year
+    QuarterMonths(u32),
+}
+
+/// Formats a year with at least four digits, as in period keys
+struct YearLabel(i32);
+
+impl fmt::Display for YearLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 < 0 {
+            f.write_str("-")?;
+        }
+        write!(f, "{:04}", self.0.unsigned_abs())
+    }
+}
+
+/// Parses a year formatted as by `YearLabel`
+fn parse_year_label(s: &str) -> Option<i32> {
+    let year = s.parse().ok()?;
+    Some(year).filter(|_| YearLabel(year).to_string() == s)
+}
+
+/// A fiscal calendar whose years start on the first day of a given month. Months are calendar
+/// months and quarters are made of three consecutive months counting from the start of the
+/// fiscal year. Fiscal years are named after the calendar year in which they end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FiscalCalendar {
+    start_month: u32,
+}
+
+impl FiscalCalendar {
+    /// Creates a fiscal calendar whose years start in the given month. Returns `None` if the
+    /// month does not lie within `1..=12`.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::fiscal::FiscalCalendar;
+    /// use chrono_utilities::naive::period::{PeriodCalendar, Unit};
+    ///
+    /// let cal = FiscalCalendar::new(7).unwrap();
+    /// let d = NaiveDate::from_ymd_opt(2020, 2, 14).unwrap();
+    /// assert_eq!(cal.fiscal_year(d), 2020);
+    /// assert_eq!(cal.fiscal_quarter(d), 3);
+    /// let year = cal.period_of(d, Unit::Year).unwrap();
+    /// assert_eq!(year.start(), NaiveDate::from_ymd_opt(2019, 7, 1).unwrap());
+    /// assert_eq!(year.end(), NaiveDate::from_ymd_opt(2020, 6, 30).unwrap());
+    /// let quarters = cal.forecast_periods(d, 2, Unit::Quarter);
+    /// assert_eq!(quarters[0].start(), NaiveDate::from_ymd_opt(2020, 4, 1).unwrap());
+    /// assert_eq!(quarters[1].start(), NaiveDate::from_ymd_opt(2020, 7, 1).unwrap());
+    pub fn new(start_month: u32) -> Option<Self> {
+        if (1..=12).contains(&start_month) {
+            Some(FiscalCalendar { start_month })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the month in which fiscal years start
+    #[inline]
+    pub fn start_month(&self) -> u32 {
+        self.start_month
+    }
+
+    /// Returns the fiscal year the date falls in, named after the calendar year in which the
+    /// fiscal year ends
+    #[inline]
+    pub fn fiscal_year(&self, date: NaiveDate) -> i32 {
+        if self.start_month == 1 || date.month() < self.start_month {
+            date.year()
+        } else {
+            date.year() + 1
+        }
+    }
+
+    /// Returns the fiscal quarter, from 1 to 4, the date falls in
+    #[inline]
+    pub fn fiscal_quarter(&self, date: NaiveDate) -> u32 {
+        self.months_into_year(date) / 3 + 1
+    }
+
+    /// Returns the month spanned by the given fiscal period, or `None` for the 13th period as
+    /// fiscal years of this calendar are made of 12 months
+    pub fn fiscal_period(&self, period: FiscalPeriod) -> Option<DateRange> {
+        let start = shift_months(self.year_start(period.year)?, period.period as i32 - 1)?;
+        self.period_of(start, Unit::Month)
+            .filter(|_| period.period <= 12)
+    }
+
+    /// Returns the label of the fiscal year or quarter the date falls in, in the given style
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::fiscal::{FiscalCalendar, LabelStyle};
+    ///
+    /// let cal = FiscalCalendar::new(10).unwrap();
+    /// let d = NaiveDate::from_ymd_opt(2024, 5, 14).unwrap();
+    /// assert_eq!(cal.label(d, LabelStyle::Year), "FY2024");
+    /// assert_eq!(cal.label(d, LabelStyle::ShortYear), "FY24");
+    /// assert_eq!(cal.label(d, LabelStyle::Quarter), "FY2024-Q3");
+    /// assert_eq!(cal.label(d, LabelStyle::QuarterMonths), "AMJ");
+    /// assert_eq!(cal.label(NaiveDate::from_ymd_opt(2024, 11, 2).unwrap(), LabelStyle::QuarterMonths), "OND");
+    pub fn label(&self, date: NaiveDate, style: LabelStyle) -> String {
+        let year = self.fiscal_year(date);
+        match style {
+            LabelStyle::Year => format!("FY{}", YearLabel(year)),
+            LabelStyle::ShortYear => format!("FY{:02}", year.rem_euclid(100)),
+            LabelStyle::Quarter => {
+                format!("FY{}-Q{}", YearLabel(year), self.fiscal_quarter(date))
+            }
+            LabelStyle::QuarterMonths => self.quarter_months(self.fiscal_quarter(date)),
+        }
+    }
+
+    /// Parses a label in any of the [LabelStyle](enum.LabelStyle.html)s. Two-digit years, as
+    /// in `FY24`, are read as years from 1970 to 2069, as chrono does for `%y`.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::fiscal::{FiscalCalendar, FiscalLabel};
+    ///
+    /// let cal = FiscalCalendar::new(10).unwrap();
+    /// assert_eq!(cal.parse_label("FY24"), Ok(FiscalLabel::Year(2024)));
+    /// assert_eq!(cal.parse_label("FY99"), Ok(FiscalLabel::Year(1999)));
+    /// assert_eq!(cal.parse_label("FY2024"), Ok(FiscalLabel::Year(2024)));
+    /// assert_eq!(cal.parse_label("AMJ"), Ok(FiscalLabel::QuarterMonths(3)));
+    /// let label = cal.parse_label("FY2024-Q3").unwrap();
+    /// assert_eq!(label, FiscalLabel::Quarter { year: 2024, quarter: 3 });
+    /// let range = cal.label_period(label).unwrap();
+    /// assert_eq!(range.start(), NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+    /// assert_eq!(range.end(), NaiveDate::from_ymd_opt(2024, 6, 30).unwrap());
+    /// assert_eq!(cal.parse_label("JFM"), Ok(FiscalLabel::QuarterMonths(2)));
+    /// assert!(cal.parse_label("MAM").is_err());
+    /// assert!(cal.parse_label("FY2024-Q5").is_err());
+    pub fn parse_label(&self, s: &str) -> Result<FiscalLabel, ParsePeriodError> {
+        let err = ParsePeriodError::new("fiscal label");
+        let rest = match s.strip_prefix("FY") {
+            Some(rest) => rest,
+            None => {
+                return (1..=4)
+                    .find(|quarter| self.quarter_months(*quarter) == s)
+                    .map(FiscalLabel::QuarterMonths)
+                    .ok_or(err)
+            }
+        };
+        if let Some((year, quarter)) = rest.split_once("-Q") {
+            let year = parse_year_label(year).ok_or(err)?;
+            return match quarter {
+                "1" | "2" | "3" | "4" => Ok(FiscalLabel::Quarter {
+                    year,
+                    quarter: quarter.parse().map_err(|_| err)?,
+                }),
+                _ => Err(err),
+            };
+        }
+        if rest.len() == 2 && rest.bytes().all(|b| b.is_ascii_digit()) {
+            let year: i32 = rest.parse().map_err(|_| err)?;
+            return Ok(FiscalLabel::Year(
+                if year < 70 { 2000 } else { 1900 } + year,
+            ));
+        }
+        parse_year_label(rest).map(FiscalLabel::Year).ok_or(err)
+    }
+
+    /// Returns the dates spanned by the fiscal year or quarter of the label, or `None` for
+    /// quarter months labels as they do not name a year
+    pub fn label_period(&self, label: FiscalLabel) -> Option<DateRange> {
+        match label {
+            FiscalLabel::Year(year) => self.period_of(self.year_start(year)?, Unit::Year),
+            FiscalLabel::Quarter { year, quarter } if (1..=4).contains(&quarter) => {
+                let start = shift_months(self.year_start(year)?, (quarter as i32 - 1) * 3)?;
+                self.period_of(start, Unit::Quarter)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the first day of the given fiscal year
+    #[inline]
+    fn year_start(&self, fiscal_year: i32) -> Option<NaiveDate> {
+        let year = if self.start_month == 1 {
+            fiscal_year
+        } else {
+            fiscal_year.checked_sub(1)?
+        };
+        NaiveDate::from_ymd_opt(year, self.start_month, 1)
+    }
+
+    /// Returns the initials of the months of the given fiscal quarter
+    fn quarter_months(&self, quarter: u32) -> String {
+        (0..3)
+            .map(|month| {
+                let month0 = (self.start_month - 1 + (quarter - 1) * 3 + month) % 12;
+                char::from(b"JFMAMJJASOND"[month0 as usize])
+            })
+            .collect()
+    }
+
+    /// Returns the number of whole months between the start of the fiscal year and the month of
+    /// the date
+    #[inline]
+    fn months_into_year(&self, date: NaiveDate) -> u32 {
+        (date.month0() + 13 - self.start_month) % 12
+    }
+}
+
+impl PeriodCalendar for FiscalCalendar {
+    fn period_of(&self, date: NaiveDate, unit: Unit) -> Option<DateRange> {
+        let months = self.months_into_year(date);
+        let (offset, length) = match unit {
+            Unit::Quarter => (months % 3, 3),
+            Unit::Year => (months, 12),
+            _ => return unit.period_of(date),
+        };
+        let start = shift_months(date.start_of_month()?, -(offset as i32))?;
+        DateRange::new(start, shift_months(start, length)?.pred_opt()?)
+    }
+}
+
+/// Distribution of the 13 weeks of a quarter among its three months in a
+/// [Calendar445](struct.Calendar445.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeekPattern {
+    FourFourFive,
+    FourFiveFour,
+    FiveFourFour,
+}
+
+impl WeekPattern {
+    /// Returns the number of weeks in each month of a quarter
+    #[inline]
+    fn weeks(&self) -> [u32; 3] {
+        match self {
+            WeekPattern::FourFourFive => [4, 4, 5],
+            WeekPattern::FourFiveFour => [4, 5, 4],
+            WeekPattern::FiveFourFour => [5, 4, 4],
+        }
+    }
+}
+
+/// Returns the fiscal year the date falls in, for fiscal years ending on the last occurrence of
+/// the weekday in the month of the calendar year they are named after
+fn week_fiscal_year(
+    date: NaiveDate,
+    year_end_month: u32,
+    year_end_weekday: Weekday,
+) -> Option<i32> {
+    if date > last_weekday_of_month(date.year(), year_end_month, year_end_weekday)? {
+        Some(date.year() + 1)
+    } else {
+        Some(date.year())
+    }
+}
+
+/// Returns the span of the given fiscal year, for fiscal years ending on the last occurrence of
+/// the weekday in the month of the calendar year they are named after
+fn week_fiscal_year_span(
+    fiscal_year: i32,
+    year_end_month: u32,
+    year_end_weekday: Weekday,
+) -> Option<DateRange> {
+    let previous = fiscal_year.checked_sub(1)?;
+    let start = last_weekday_of_month(previous, year_end_month, year_end_weekday)?.succ_opt()?;
+    DateRange::new(
+        start,
+        last_weekday_of_month(fiscal_year, year_end_month, year_end_weekday)?,
+    )
+}
+
+/// A 52-53 week fiscal calendar, commonly used in retail, where every fiscal year ends on the
+/// last occurrence of a given weekday in a given month. Years are made of whole weeks starting
+/// on the day after that weekday, and every quarter is made of 13 weeks split among its months
+/// as per the [WeekPattern](enum.WeekPattern.html). The extra week of a 53 week year is added to
+/// its last month. Fiscal years are named after the calendar year in which they end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Calendar445 {
+    year_end_month: u32,
+    year_end_weekday: Weekday,
+    pattern: WeekPattern,
+}
+
+impl Calendar445 {
+    /// Creates a calendar whose fiscal years end on the last `year_end_weekday` of
+    /// `year_end_month`. Returns `None` if the month does not lie within `1..=12`.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::fiscal::{Calendar445, WeekPattern};
+    /// use chrono_utilities::naive::period::{PeriodCalendar, Unit};
+    ///
+    /// let cal = Calendar445::new(12, Weekday::Sat, WeekPattern::FourFourFive).unwrap();
+    /// let d = NaiveDate::from_ymd_opt(2020, 2, 14).unwrap();
+    /// let year = cal.period_of(d, Unit::Year).unwrap();
+    /// assert_eq!(year.start(), NaiveDate::from_ymd_opt(2019, 12, 29).unwrap());
+    /// assert_eq!(year.end(), NaiveDate::from_ymd_opt(2020, 12, 26).unwrap());
+    /// let month = cal.period_of(d, Unit::Month).unwrap();
+    /// assert_eq!(month.start(), NaiveDate::from_ymd_opt(2020, 1, 26).unwrap());
+    /// assert_eq!(month.end(), NaiveDate::from_ymd_opt(2020, 2, 22).unwrap());
+    /// let months = cal.forecast_periods(d, 2, Unit::Month);
+    /// assert_eq!(months[0].num_days(), 35);
+    /// assert_eq!(months[1].num_days(), 28);
+    pub fn new(
+        year_end_month: u32,
+        year_end_weekday: Weekday,
+        pattern: WeekPattern,
+    ) -> Option<Self> {
+        if (1..=12).contains(&year_end_month) {
+            Some(Calendar445 {
+                year_end_month,
+                year_end_weekday,
+                pattern,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the fiscal year the date falls in
+    pub fn fiscal_year(&self, date: NaiveDate) -> Option<i32> {
+        week_fiscal_year(date, self.year_end_month, self.year_end_weekday)
+    }
+
+    /// Returns the span of the given fiscal year
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::fiscal::{Calendar445, WeekPattern};
+    ///
+    /// let cal = Calendar445::new(12, Weekday::Sat, WeekPattern::FourFourFive).unwrap();
+    /// let year = cal.year_of(2016).unwrap();
+    /// assert_eq!(year.start(), NaiveDate::from_ymd_opt(2015, 12, 27).unwrap());
+    /// assert_eq!(year.end(), NaiveDate::from_ymd_opt(2016, 12, 31).unwrap());
+    /// assert_eq!(year.num_days(), 53 * 7);
+    pub fn year_of(&self, fiscal_year: i32) -> Option<DateRange> {
+        week_fiscal_year_span(fiscal_year, self.year_end_month, self.year_end_weekday)
+    }
+
+    /// Returns the span of the given fiscal period, or `None` for the 13th period as fiscal
+    /// years of this calendar are made of 12 months
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::fiscal::{Calendar445, FiscalPeriod, WeekPattern};
+    ///
+    /// let cal = Calendar445::new(12, Weekday::Sat, WeekPattern::FourFourFive).unwrap();
+    /// let march = cal.fiscal_period("FY2016-P03".parse().unwrap()).unwrap();
+    /// assert_eq!(march.start(), NaiveDate::from_ymd_opt(2016, 2, 21).unwrap());
+    /// assert_eq!(march.num_days(), 35);
+    /// let december = cal.fiscal_period(FiscalPeriod::new(2016, 12).unwrap()).unwrap();
+    /// assert_eq!(december.end(), NaiveDate::from_ymd_opt(2016, 12, 31).unwrap());
+    /// assert_eq!(cal.fiscal_period(FiscalPeriod::new(2016, 13).unwrap()), None);
+    pub fn fiscal_period(&self, period: FiscalPeriod) -> Option<DateRange> {
+        if period.period > 12 {
+            return None;
+        }
+        let mut month = self.period_of(self.year_of(period.year)?.start(), Unit::Month)?;
+        for _ in 1..period.period {
+            month = self.period_of(month.end().succ_opt()?, Unit::Month)?;
+        }
+        Some(month)
+    }
+}
+
+impl PeriodCalendar for Calendar445 {
+    fn period_of(&self, date: NaiveDate, unit: Unit) -> Option<DateRange> {
+        if unit == Unit::Day {
+            return unit.period_of(date);
+        }
+        let year = self.year_of(self.fiscal_year(date)?)?;
+        let week = date.signed_duration_since(year.start()).num_days() / 7;
+        let (first_week, num_weeks) = match unit {
+            Unit::Year => return Some(year),
+            Unit::Week => (week, 1),
+            _ => {
+                let months_per_period = if unit == Unit::Quarter { 3 } else { 1 };
+                let extra_week = year.num_days() / 7 - 52;
+                let weeks = self.pattern.weeks();
+                let mut first_week = 0;
+                let mut month = 0;
+                loop {
+                    let mut num_weeks = 0;
+                    for _ in 0..months_per_period {
+                        num_weeks += weeks[month % 3] as i64;
+                        month += 1;
+                    }
+                    if month == 12 {
+                        num_weeks += extra_week;
+                    }
+                    if week < first_week + num_weeks {
+                        break (first_week, num_weeks);
+                    }
+                    first_week += num_weeks;
+                }
+            }
+        };
+        let start = year.start() + TimeDelta::weeks(first_week);
+        DateRange::new(start, start + TimeDelta::days(num_weeks * 7 - 1))
+    }
+}
+
+/// The period receiving the extra week of a 53 week year in a
+/// [ThirteenPeriodCalendar](struct.ThirteenPeriodCalendar.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeapWeek {
+    FirstPeriod,
+    LastPeriod,
+}
+
+/// A 52-53 week fiscal calendar dividing every year into 13 periods of 4 weeks. As in a
+/// [Calendar445](struct.Calendar445.html), every fiscal year ends on the last occurrence of a
+/// given weekday in a given month and starts on the day after the end of the previous one, so
+/// that some years are made of 53 weeks. The extra week is added to the first or last period of
+/// the year as per the [LeapWeek](enum.LeapWeek.html). Quarters are made of 3, 3, 3 and 4
+/// periods. Fiscal years are named after the calendar year in which they end.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, Weekday};
+/// use chrono_utilities::naive::fiscal::{FiscalPeriod, LeapWeek, ThirteenPeriodCalendar};
+/// use chrono_utilities::naive::period::{PeriodCalendar, Unit};
+///
+/// let cal = ThirteenPeriodCalendar::new(12, Weekday::Sat, LeapWeek::LastPeriod).unwrap();
+/// let d = NaiveDate::from_ymd_opt(2020, 2, 14).unwrap();
+/// assert_eq!(cal.fiscal_period_of(d), FiscalPeriod::new(2020, 2));
+/// let period = cal.period_of(d, Unit::Month).unwrap();
+/// assert_eq!(period.start(), NaiveDate::from_ymd_opt(2020, 1, 26).unwrap());
+/// assert_eq!(period.end(), NaiveDate::from_ymd_opt(2020, 2, 22).unwrap());
+/// // Fiscal year 2016 is made of 53 weeks
+/// let last = cal.fiscal_period("FY2016-P13".parse().unwrap()).unwrap();
+/// assert_eq!(last.start(), NaiveDate::from_ymd_opt(2016, 11, 27).unwrap());
+/// assert_eq!(last.end(), NaiveDate::from_ymd_opt(2016, 12, 31).unwrap());
+/// assert_eq!(last.num_days(), 35);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ThirteenPeriodCalendar {
+    year_end_month: u32,
+    year_end_weekday: Weekday,
+    leap_week: LeapWeek,
+}
+
+impl ThirteenPeriodCalendar {
+    /// Creates a calendar whose fiscal years end on the last `year_end_weekday` of
+    /// `year_end_month`. Returns `None` if the month does not lie within `1..=12`.
+    pub fn new(
+        year_end_month: u32,
+        year_end_weekday: Weekday,
+        leap_week: LeapWeek,
+    ) -> Option<Self> {
+        if (1..=12).contains(&year_end_month) {
+            Some(ThirteenPeriodCalendar {
+                year_end_month,
+                year_end_weekday,
+                leap_week,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the fiscal year the date falls in
+    pub fn fiscal_year(&self, date: NaiveDate) -> Option<i32> {
+        week_fiscal_year(date, self.year_end_month, self.year_end_weekday)
+    }
+
+    /// Returns the span of the given fiscal year
+    pub fn year_of(&self, fiscal_year: i32) -> Option<DateRange> {
+        week_fiscal_year_span(fiscal_year, self.year_end_month, self.year_end_weekday)
+    }
+
+    /// Returns the fiscal period the date falls in
+    pub fn fiscal_period_of(&self, date: NaiveDate) -> Option<FiscalPeriod> {
+        let fiscal_year = self.fiscal_year(date)?;
+        let year = self.year_of(fiscal_year)?;
+        let week = date.signed_duration_since(year.start()).num_days() / 7;
+        let extra_week = year.num_days() / 7 - 52;
+        let period = match self.leap_week {
+            LeapWeek::FirstPeriod => (week - extra_week).max(0) / 4,
+            LeapWeek::LastPeriod => (week / 4).min(12),
+        };
+        FiscalPeriod::new(fiscal_year, period as u32 + 1)
+    }
+
+    /// Returns the span of the given fiscal period
+    pub fn fiscal_period(&self, period: FiscalPeriod) -> Option<DateRange> {
+        let year = self.year_of(period.year)?;
+        let (first_week, num_weeks) =
+            self.period_weeks(period.period - 1, year.num_days() / 7 - 52);
+        let start = year
+            .start()
+            .checked_add_signed(TimeDelta::weeks(first_week))?;
+        DateRange::new(
+            start,
+            start.checked_add_signed(TimeDelta::days(num_weeks * 7 - 1))?,
+        )
+    }
+
+    /// Returns the first week, counting from 0, and the number of weeks of the given period,
+    /// counting from 0, in a year with the given number of extra weeks
+    fn period_weeks(&self, period: u32, extra_week: i64) -> (i64, i64) {
+        let first_week = period as i64 * 4;
+        match self.leap_week {
+            LeapWeek::FirstPeriod if period == 0 => (0, 4 + extra_week),
+            LeapWeek::FirstPeriod => (first_week + extra_week, 4),
+            LeapWeek::LastPeriod if period == 12 => (first_week, 4 + extra_week),
+            LeapWeek::LastPeriod => (first_week, 4),
+        }
+    }
+}
+
+impl PeriodCalendar for ThirteenPeriodCalendar {
+    fn period_of(&self, date: NaiveDate, unit: Unit) -> Option<DateRange> {
+        match unit {
+            Unit::Day => unit.period_of(date),
+            Unit::Week => {
+                let year = self.year_of(self.fiscal_year(date)?)?;
+                let week = date.signed_duration_since(year.start()).num_days() / 7;
+                let start = year.start() + TimeDelta::weeks(week);
+                DateRange::new(start, start + TimeDelta::days(6))
+            }
+            Unit::Month => self.fiscal_period(self.fiscal_period_of(date)?),
+            Unit::Quarter => {
+                let period = self.fiscal_period_of(date)?;
+                let (first, last) = match period.period {
+                    1..=3 => (1, 3),
+                    4..=6 => (4, 6),
+                    7..=9 => (7, 9),
+                    _ => (10, 13),
+                };
+                let start = self.fiscal_period(FiscalPeriod::new(period.year, first)?)?;
+                let end = self.fiscal_period(FiscalPeriod::new(period.year, last)?)?;
+                DateRange::new(start.start(), end.end())
+            }
+            Unit::Year => self.year_of(self.fiscal_year(date)?),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::FiscalPeriod;
+    use crate::naive::period::serde_impls::CanonicalVisitor;
+    use serde::de::{Deserialize, Deserializer};
+    use serde::ser::{Serialize, Serializer};
+
+    impl Serialize for FiscalPeriod {
+        /// Serializes the period as in `"FY2025-P03"`
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for FiscalPeriod {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer
+                .deserialize_str(CanonicalVisitor::new("a fiscal period such as FY2025-P03"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Calendar445, FiscalCalendar, FiscalPeriod, LabelStyle, LeapWeek, ThirteenPeriodCalendar,
+        WeekPattern,
+    };
+    use crate::naive::period::{PeriodCalendar, Unit};
+    use chrono::{NaiveDate, Weekday};
+
+    #[test]
+    fn test_labels_round_trip() {
+        let cal = FiscalCalendar::new(10).unwrap();
+        let styles = [LabelStyle::Year, LabelStyle::ShortYear, LabelStyle::Quarter];
+        for year in [1970, 1998, 1999, 2000, 2024, 2068].iter() {
+            let date = NaiveDate::from_ymd_opt(*year, 11, 15).unwrap();
+            for style in styles.iter() {
+                let label = cal.parse_label(&cal.label(date, *style)).unwrap();
+                assert!(cal.label_period(label).unwrap().contains(date));
+            }
+        }
+    }
+
+    #[test]
+    fn test_445_periods_tile_year() {
+        let cal = Calendar445::new(1, Weekday::Sat, WeekPattern::FiveFourFour).unwrap();
+        for fiscal_year in 2010..2030 {
+            let year = cal.year_of(fiscal_year).unwrap();
+            assert!(year.num_days() == 364 || year.num_days() == 371);
+            for unit in [Unit::Week, Unit::Month, Unit::Quarter].iter() {
+                let mut next = year.start();
+                while next <= year.end() {
+                    let period = cal.period_of(next, *unit).unwrap();
+                    assert_eq!(period.start(), next);
+                    assert_eq!(cal.period_of(period.end(), *unit), Some(period));
+                    next = period.end().succ_opt().unwrap();
+                }
+                assert_eq!(next, year.end().succ_opt().unwrap());
+            }
+        }
+        // Jan 31, 2015 is a Saturday while the last one of January 2014 is the 25th
+        let year = cal.year_of(2015).unwrap();
+        assert_eq!(year.num_days(), 371);
+        let last_month = cal.period_of(year.end(), Unit::Month).unwrap();
+        assert_eq!(last_month.num_days(), 35);
+    }
+
+    #[test]
+    fn test_thirteen_periods_tile_year() {
+        for leap_week in [LeapWeek::FirstPeriod, LeapWeek::LastPeriod].iter() {
+            let cal = ThirteenPeriodCalendar::new(6, Weekday::Sun, *leap_week).unwrap();
+            for fiscal_year in 2010..2030 {
+                let year = cal.year_of(fiscal_year).unwrap();
+                let mut next = year.start();
+                for period in 1..=13 {
+                    let key = FiscalPeriod::new(fiscal_year, period).unwrap();
+                    let range = cal.fiscal_period(key).unwrap();
+                    assert_eq!(range.start(), next);
+                    assert_eq!(range.num_days() % 7, 0);
+                    assert_eq!(cal.fiscal_period_of(range.start()), Some(key));
+                    assert_eq!(cal.fiscal_period_of(range.end()), Some(key));
+                    next = range.end().succ_opt().unwrap();
+                }
+                assert_eq!(next, year.end().succ_opt().unwrap());
+                let first = cal.period_of(year.start(), Unit::Quarter).unwrap();
+                let last = cal.period_of(year.end(), Unit::Quarter).unwrap();
+                assert_eq!(
+                    (first.num_days() + last.num_days()) / 7,
+                    year.num_days() / 7 - 24
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Fortnights, periods of two weeks aligned on an anchor date, for biweekly cadences such as
+//! sprints and pay periods, and the parity of ISO 8601 weeks
+use super::start_of_cycle;
+use chrono::{Datelike, NaiveDate, TimeDelta};
+
+/// Whether the number of an ISO 8601 week is odd or even
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeekParity {
+    /// Weeks 1, 3, 5 and so on
+    Odd,
+    /// Weeks 2, 4, 6 and so on
+    Even,
+}
+
+/// Transitions between fortnights. Fortnights are the periods of fourteen days repeating in
+/// both directions from an anchor date, on which one of them starts.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::fortnight::{FortnightTransitions, WeekParity};
+///
+/// let anchor = NaiveDate::from_ymd_opt(2020, 1, 6).unwrap();
+/// let d = NaiveDate::from_ymd_opt(2020, 3, 4).unwrap();
+/// assert_eq!(d.start_of_fortnight(anchor), NaiveDate::from_ymd_opt(2020, 3, 2));
+/// assert_eq!(d.end_of_fortnight(anchor), NaiveDate::from_ymd_opt(2020, 3, 15));
+/// assert_eq!(d.start_of_pred_fortnight(anchor), NaiveDate::from_ymd_opt(2020, 2, 17));
+/// assert_eq!(d.end_of_succ_fortnight(anchor), NaiveDate::from_ymd_opt(2020, 3, 29));
+/// // Fortnights extend before the anchor
+/// let d = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+/// assert_eq!(d.start_of_fortnight(anchor), NaiveDate::from_ymd_opt(2019, 12, 23));
+/// assert_eq!(d.iso_week_parity(), WeekParity::Odd);
+pub trait FortnightTransitions: Sized {
+    /// Returns the first day of the fortnight containing the date
+    fn start_of_fortnight(&self, anchor: NaiveDate) -> Option<Self>;
+
+    /// Returns the last day of the fortnight containing the date
+    fn end_of_fortnight(&self, anchor: NaiveDate) -> Option<Self>;
+
+    /// Returns the first day of the fortnight preceding the one containing the date
+    fn start_of_pred_fortnight(&self, anchor: NaiveDate) -> Option<Self>;
+
+    /// Returns the last day of the fortnight preceding the one containing the date
+    fn end_of_pred_fortnight(&self, anchor: NaiveDate) -> Option<Self>;
+
+    /// Returns the first day of the fortnight following the one containing the date
+    fn start_of_succ_fortnight(&self, anchor: NaiveDate) -> Option<Self>;
+
+    /// Returns the last day of the fortnight following the one containing the date
+    fn end_of_succ_fortnight(&self, anchor: NaiveDate) -> Option<Self>;
+
+    /// Returns the parity of the number of the ISO 8601 week of the date. Parity does not
+    /// alternate at the start of a year following one with 53 weeks, as weeks 53 and 1 are
+    /// both odd.
+    fn iso_week_parity(&self) -> WeekParity;
+}
+
+impl FortnightTransitions for NaiveDate {
+    #[inline]
+    fn start_of_fortnight(&self, anchor: NaiveDate) -> Option<Self> {
+        start_of_cycle(*self, anchor, 14)
+    }
+
+    #[inline]
+    fn end_of_fortnight(&self, anchor: NaiveDate) -> Option<Self> {
+        self.start_of_fortnight(anchor)?
+            .checked_add_signed(TimeDelta::days(13))
+    }
+
+    #[inline]
+    fn start_of_pred_fortnight(&self, anchor: NaiveDate) -> Option<Self> {
+        self.start_of_fortnight(anchor)?
+            .checked_sub_signed(TimeDelta::days(14))
+    }
+
+    #[inline]
+    fn end_of_pred_fortnight(&self, anchor: NaiveDate) -> Option<Self> {
+        self.start_of_fortnight(anchor)?.pred_opt()
+    }
+
+    #[inline]
+    fn start_of_succ_fortnight(&self, anchor: NaiveDate) -> Option<Self> {
+        self.end_of_fortnight(anchor)?.succ_opt()
+    }
+
+    #[inline]
+    fn end_of_succ_fortnight(&self, anchor: NaiveDate) -> Option<Self> {
+        self.start_of_fortnight(anchor)?
+            .checked_add_signed(TimeDelta::days(27))
+    }
+
+    #[inline]
+    fn iso_week_parity(&self) -> WeekParity {
+        if self.iso_week().week() % 2 == 0 {
+            WeekParity::Even
+        } else {
+            WeekParity::Odd
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FortnightTransitions, WeekParity};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_fortnights_tile_around_anchor() {
+        let anchor = NaiveDate::from_ymd_opt(2020, 1, 6).unwrap();
+        let mut date = NaiveDate::from_ymd_opt(2019, 11, 1).unwrap();
+        while date < NaiveDate::from_ymd_opt(2020, 3, 1).unwrap() {
+            let start = date.start_of_fortnight(anchor).unwrap();
+            let end = date.end_of_fortnight(anchor).unwrap();
+            assert!(start <= date && date <= end);
+            assert_eq!(end.signed_duration_since(start).num_days(), 13);
+            assert_eq!(start.signed_duration_since(anchor).num_days() % 14, 0);
+            assert_eq!(date.end_of_pred_fortnight(anchor), start.pred_opt());
+            assert_eq!(date.start_of_succ_fortnight(anchor), end.succ_opt());
+            assert_eq!(
+                date.start_of_pred_fortnight(anchor),
+                start.pred_opt().unwrap().start_of_fortnight(anchor)
+            );
+            assert_eq!(
+                date.end_of_succ_fortnight(anchor),
+                end.succ_opt().unwrap().end_of_fortnight(anchor)
+            );
+            date = date.succ_opt().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_iso_week_parity_across_years() {
+        // December 31, 2020 falls in week 53 and January 4, 2021 in week 1
+        let week_53 = NaiveDate::from_ymd_opt(2020, 12, 31).unwrap();
+        let week_1 = NaiveDate::from_ymd_opt(2021, 1, 4).unwrap();
+        let week_2 = NaiveDate::from_ymd_opt(2021, 1, 11).unwrap();
+        assert_eq!(week_53.iso_week_parity(), WeekParity::Odd);
+        assert_eq!(week_1.iso_week_parity(), WeekParity::Odd);
+        assert_eq!(week_2.iso_week_parity(), WeekParity::Even);
+    }
+}
@@ -0,0 +1,284 @@
+//! Rendering of the distance between two dates or datetimes as English phrases such as
+//! `in 3 weeks`, `2 months ago` or `yesterday`
+use super::duration::difference;
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+
+/// The units a [Humanizer](struct.Humanizer.html) may express distances in, from the finest
+/// to the coarsest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Granularity {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// Names of the granularities, in the singular
+const NAMES: [&str; 7] = ["second", "minute", "hour", "day", "week", "month", "year"];
+
+/// Renders distances in the coarsest unit of which the distance spans at least one, among the
+/// units from `finest` to `coarsest`. Amounts are truncated, so that 13 days are `a week`, and
+/// months and years are counted in calendar months, so that Jan 31 to Feb 29 is `a month`.
+/// Distances shorter than one unit of the finest granularity are rendered as `just now` below
+/// a day, `today` for days, and `within a week` beyond. By default, every unit from seconds to
+/// years may be used.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::humanize::{Granularity, Humanizer};
+///
+/// let today = NaiveDate::from_ymd_opt(2020, 1, 15).unwrap();
+/// let days = Humanizer::new(Granularity::Day, Granularity::Day).unwrap();
+/// assert_eq!(days.date(NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(), today), "in 46 days");
+/// let months = Humanizer::new(Granularity::Month, Granularity::Year).unwrap();
+/// assert_eq!(months.date(NaiveDate::from_ymd_opt(2020, 1, 20).unwrap(), today), "within a month");
+/// assert_eq!(months.date(NaiveDate::from_ymd_opt(2019, 12, 14).unwrap(), today), "a month ago");
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Humanizer {
+    finest: Granularity,
+    coarsest: Granularity,
+}
+
+impl Default for Humanizer {
+    fn default() -> Self {
+        Humanizer {
+            finest: Granularity::Second,
+            coarsest: Granularity::Year,
+        }
+    }
+}
+
+impl Humanizer {
+    /// Creates a humanizer using the units from `finest` to `coarsest`. Returns `None` if
+    /// `finest` is coarser than `coarsest`.
+    pub fn new(finest: Granularity, coarsest: Granularity) -> Option<Self> {
+        if finest <= coarsest {
+            Some(Humanizer { finest, coarsest })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the finest unit distances may be expressed in
+    #[inline]
+    pub fn finest(&self) -> Granularity {
+        self.finest
+    }
+
+    /// Returns the coarsest unit distances may be expressed in
+    #[inline]
+    pub fn coarsest(&self) -> Granularity {
+        self.coarsest
+    }
+
+    /// Renders the distance from the reference date to the date. Units finer than a day are
+    /// not used, distances being counted in days when every unit of the humanizer is finer.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::humanize::Humanizer;
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2020, 1, 15).unwrap();
+    /// let h = Humanizer::default();
+    /// assert_eq!(h.date(today, today), "today");
+    /// assert_eq!(h.date(NaiveDate::from_ymd_opt(2020, 1, 14).unwrap(), today), "yesterday");
+    /// assert_eq!(h.date(NaiveDate::from_ymd_opt(2020, 2, 5).unwrap(), today), "in 3 weeks");
+    /// assert_eq!(h.date(NaiveDate::from_ymd_opt(2019, 11, 1).unwrap(), today), "2 months ago");
+    pub fn date(&self, date: NaiveDate, reference: NaiveDate) -> String {
+        let (earlier, later) = if date < reference {
+            (date, reference)
+        } else {
+            (reference, date)
+        };
+        let days = later.signed_duration_since(earlier).num_days();
+        self.describe(
+            earlier,
+            days * 86_400,
+            days,
+            date > reference,
+            Granularity::Day,
+        )
+    }
+
+    /// Renders the distance from the reference datetime to the datetime. Days and coarser units
+    /// are counted between the calendar dates of the datetimes, so that 23:00 to 01:00 the next
+    /// day is `tomorrow` and 23:00 to 01:00 two days later is `in 2 days`.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::humanize::{Granularity, Humanizer};
+    ///
+    /// let now = NaiveDate::from_ymd_opt(2020, 1, 15).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    /// let at = |d, h, m, s| NaiveDate::from_ymd_opt(2020, 1, d).unwrap().and_hms_opt(h, m, s).unwrap();
+    /// let h = Humanizer::default();
+    /// assert_eq!(h.datetime(at(15, 11, 59, 30), now), "30 seconds ago");
+    /// assert_eq!(h.datetime(at(15, 13, 5, 0), now), "in an hour");
+    /// assert_eq!(h.datetime(at(16, 20, 0, 0), now), "tomorrow");
+    /// let h = Humanizer::new(Granularity::Minute, Granularity::Hour).unwrap();
+    /// assert_eq!(h.datetime(at(15, 11, 59, 30), now), "just now");
+    /// assert_eq!(h.datetime(at(17, 12, 0, 0), now), "in 48 hours");
+    pub fn datetime(&self, datetime: NaiveDateTime, reference: NaiveDateTime) -> String {
+        let (earlier, later) = if datetime < reference {
+            (datetime, reference)
+        } else {
+            (reference, datetime)
+        };
+        let seconds = later.signed_duration_since(earlier).num_seconds();
+        let days = later
+            .date()
+            .signed_duration_since(earlier.date())
+            .num_days();
+        self.describe(
+            earlier.date(),
+            seconds,
+            days,
+            datetime > reference,
+            Granularity::Second,
+        )
+    }
+
+    /// Renders a distance of the given number of seconds, spanning the given number of calendar
+    /// days from the earlier date, using units no finer than `limit`
+    fn describe(
+        &self,
+        earlier: NaiveDate,
+        seconds: i64,
+        days: i64,
+        future: bool,
+        limit: Granularity,
+    ) -> String {
+        let finest = self.finest.max(limit);
+        let months = || {
+            let later = earlier.checked_add_signed(TimeDelta::days(days))?;
+            let difference = difference(earlier, later);
+            Some(difference.num_years() as i64 * 12 + difference.num_months() as i64)
+        };
+        let mut granularity = self.coarsest.max(finest);
+        loop {
+            let amount = match granularity {
+                Granularity::Second => seconds,
+                Granularity::Minute => seconds / 60,
+                Granularity::Hour => seconds / 3600,
+                Granularity::Day => days,
+                Granularity::Week => days / 7,
+                Granularity::Month => months().unwrap_or(0),
+                Granularity::Year => months().unwrap_or(0) / 12,
+            };
+            if amount >= 1 {
+                return phrase(amount, granularity, future);
+            }
+            if granularity == finest {
+                break;
+            }
+            granularity = GRANULARITIES[granularity as usize - 1];
+        }
+        match finest {
+            Granularity::Second | Granularity::Minute | Granularity::Hour => "just now".to_string(),
+            Granularity::Day => "today".to_string(),
+            _ => format!("within a {}", NAMES[finest as usize]),
+        }
+    }
+}
+
+/// The granularities from the finest to the coarsest
+const GRANULARITIES: [Granularity; 7] = [
+    Granularity::Second,
+    Granularity::Minute,
+    Granularity::Hour,
+    Granularity::Day,
+    Granularity::Week,
+    Granularity::Month,
+    Granularity::Year,
+];
+
+/// Renders a positive amount of units in the future or in the past
+fn phrase(amount: i64, granularity: Granularity, future: bool) -> String {
+    let name = NAMES[granularity as usize];
+    let quantity = match amount {
+        1 if granularity == Granularity::Day => {
+            return if future { "tomorrow" } else { "yesterday" }.to_string();
+        }
+        1 if granularity == Granularity::Hour => format!("an {}", name),
+        1 => format!("a {}", name),
+        _ => format!("{} {}s", amount, name),
+    };
+    if future {
+        format!("in {}", quantity)
+    } else {
+        format!("{} ago", quantity)
+    }
+}
+
+/// Renders the distance from the reference date to the date with the default
+/// [Humanizer](struct.Humanizer.html)
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::humanize::humanize_date;
+///
+/// let today = NaiveDate::from_ymd_opt(2020, 1, 15).unwrap();
+/// assert_eq!(humanize_date(NaiveDate::from_ymd_opt(2022, 1, 14).unwrap(), today), "in a year");
+#[inline]
+pub fn humanize_date(date: NaiveDate, reference: NaiveDate) -> String {
+    Humanizer::default().date(date, reference)
+}
+
+/// Renders the distance from the reference datetime to the datetime with the default
+/// [Humanizer](struct.Humanizer.html)
+#[inline]
+pub fn humanize_datetime(datetime: NaiveDateTime, reference: NaiveDateTime) -> String {
+    Humanizer::default().datetime(datetime, reference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Granularity, Humanizer};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_granularities() {
+        let today = NaiveDate::from_ymd_opt(2020, 1, 31).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let h = Humanizer::default();
+        assert_eq!(h.date(date(2020, 2, 28), today), "in 4 weeks");
+        assert_eq!(h.date(date(2020, 2, 29), today), "in a month");
+        assert_eq!(h.date(date(2019, 1, 31), today), "a year ago");
+        assert_eq!(h.date(date(2019, 2, 1), today), "11 months ago");
+        let h = Humanizer::new(Granularity::Second, Granularity::Hour).unwrap();
+        assert_eq!(h.date(date(2020, 2, 2), today), "in 2 days");
+        let h = Humanizer::new(Granularity::Week, Granularity::Week).unwrap();
+        assert_eq!(h.date(date(2020, 2, 5), today), "within a week");
+        assert_eq!(h.date(date(2021, 1, 31), today), "in 52 weeks");
+        assert_eq!(Humanizer::new(Granularity::Year, Granularity::Day), None);
+    }
+
+    #[test]
+    fn test_datetime_days_across_midnight() {
+        let at = |d, h| {
+            NaiveDate::from_ymd_opt(2020, 1, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+        };
+        let h = Humanizer::default();
+        assert_eq!(h.datetime(at(17, 1), at(15, 23)), "in 2 days");
+        assert_eq!(h.datetime(at(15, 23), at(17, 1)), "2 days ago");
+        let h = Humanizer::new(Granularity::Day, Granularity::Year).unwrap();
+        assert_eq!(h.datetime(at(16, 1), at(15, 23)), "tomorrow");
+        assert_eq!(h.datetime(at(15, 23), at(16, 1)), "yesterday");
+        assert_eq!(h.datetime(at(15, 1), at(15, 23)), "today");
+        let h = Humanizer::new(Granularity::Hour, Granularity::Hour).unwrap();
+        assert_eq!(h.datetime(at(16, 1), at(15, 23)), "in 2 hours");
+    }
+}
@@ -0,0 +1,854 @@
+//! Intervals of datetimes, and sets of dates stored as coalesced
+//! [DateRange](../range/struct.DateRange.html)s
+use super::duration::IsoDuration;
+use super::range::DateRange;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, Sub};
+use std::slice;
+use std::str::FromStr;
+
+/// A half-open span of datetimes, from `start` inclusive to `end` exclusive. Intervals are never
+/// empty. See [DateRange](../range/struct.DateRange.html) for spans of whole dates.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, TimeDelta};
+/// use chrono_utilities::naive::interval::DateTimeInterval;
+///
+/// let at = |h, m| NaiveDate::from_ymd_opt(2020, 6, 1).unwrap().and_hms_opt(h, m, 0).unwrap();
+/// let meeting = DateTimeInterval::new(at(9, 0), at(10, 0)).unwrap();
+/// let lunch = DateTimeInterval::new(at(12, 0), at(13, 0)).unwrap();
+/// let call = DateTimeInterval::new(at(9, 30), at(12, 0)).unwrap();
+/// assert!(meeting.contains(at(9, 59)));
+/// assert!(!meeting.contains(at(10, 0)));
+/// assert!(!meeting.overlaps(&lunch));
+/// assert_eq!(meeting.gap(&lunch), DateTimeInterval::new(at(10, 0), at(12, 0)));
+/// assert_eq!(meeting.intersection(&call), DateTimeInterval::new(at(9, 30), at(10, 0)));
+/// assert_eq!(call.intersection(&lunch), None);
+/// assert_eq!(call.union(&lunch), DateTimeInterval::new(at(9, 30), at(13, 0)));
+/// assert_eq!(meeting.union(&lunch), None);
+/// assert_eq!(meeting.duration(), TimeDelta::hours(1));
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTimeInterval {
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+}
+
+impl DateTimeInterval {
+    /// Creates an interval spanning from `start`, inclusive, to `end`, exclusive. Returns `None`
+    /// unless `start` falls before `end`.
+    pub fn new(start: NaiveDateTime, end: NaiveDateTime) -> Option<Self> {
+        if start < end {
+            Some(DateTimeInterval { start, end })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the start of the interval, which is part of it
+    #[inline]
+    pub fn start(&self) -> NaiveDateTime {
+        self.start
+    }
+
+    /// Returns the end of the interval, which is not part of it
+    #[inline]
+    pub fn end(&self) -> NaiveDateTime {
+        self.end
+    }
+
+    /// Returns the length of the interval
+    #[inline]
+    pub fn duration(&self) -> TimeDelta {
+        self.end.signed_duration_since(self.start)
+    }
+
+    /// Returns true if the datetime falls within the interval
+    #[inline]
+    pub fn contains(&self, datetime: NaiveDateTime) -> bool {
+        self.start <= datetime && datetime < self.end
+    }
+
+    /// Returns true if the intervals share at least one instant
+    #[inline]
+    pub fn overlaps(&self, other: &DateTimeInterval) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    /// Returns the instants shared by both intervals, if any
+    #[inline]
+    pub fn intersection(&self, other: &DateTimeInterval) -> Option<DateTimeInterval> {
+        DateTimeInterval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Returns the interval covering both intervals, or `None` if they neither overlap nor touch
+    /// as the result would then include instants from neither interval
+    pub fn union(&self, other: &DateTimeInterval) -> Option<DateTimeInterval> {
+        if self.end < other.start || other.end < self.start {
+            return None;
+        }
+        DateTimeInterval::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    /// Returns the interval lying strictly between the intervals, or `None` if they overlap or
+    /// touch
+    #[inline]
+    pub fn gap(&self, other: &DateTimeInterval) -> Option<DateTimeInterval> {
+        DateTimeInterval::new(self.end.min(other.end), self.start.max(other.start))
+    }
+}
+
+impl fmt::Display for DateTimeInterval {
+    /// Formats the interval as an ISO 8601 interval made of its start and its end, such as
+    /// `2020-06-01T09:00:00/2020-06-01T10:00:00`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            self.start.format(ISO_DATETIME),
+            self.end.format(ISO_DATETIME)
+        )
+    }
+}
+
+impl FromStr for DateTimeInterval {
+    type Err = ParseIntervalError;
+
+    /// Parses an ISO 8601 interval made of a start and an end, a start and a duration, or a
+    /// duration and an end, separated by a slash. Datetimes may leave out their seconds, and
+    /// dates stand for midnight. Timezone designators are not supported.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::interval::DateTimeInterval;
+    ///
+    /// let at = |h, m| NaiveDate::from_ymd_opt(2020, 6, 1).unwrap().and_hms_opt(h, m, 0).unwrap();
+    /// let meeting = DateTimeInterval::new(at(9, 0), at(10, 30)).unwrap();
+    /// assert_eq!("2020-06-01T09:00:00/2020-06-01T10:30:00".parse(), Ok(meeting));
+    /// assert_eq!("2020-06-01T09:00/PT1H30M".parse(), Ok(meeting));
+    /// assert_eq!("PT1H30M/2020-06-01T10:30".parse(), Ok(meeting));
+    /// assert_eq!(meeting.to_string(), "2020-06-01T09:00:00/2020-06-01T10:30:00");
+    /// assert!("2020-06-01T10:30/2020-06-01T09:00".parse::<DateTimeInterval>().is_err());
+    fn from_str(s: &str) -> Result<Self, ParseIntervalError> {
+        let err = |part| ParseIntervalError { part };
+        let (first, second) = s.split_once('/').ok_or(err("separator"))?;
+        let (start, end) = match (first.starts_with('P'), second.starts_with('P')) {
+            (true, true) => return Err(err("interval")),
+            (true, false) => {
+                let duration: IsoDuration = first.parse().map_err(|_| err("duration"))?;
+                let end = parse_datetime(second).ok_or(err("end"))?;
+                (duration.sub_from(end).ok_or(err("start"))?, end)
+            }
+            (false, true) => {
+                let start = parse_datetime(first).ok_or(err("start"))?;
+                let duration: IsoDuration = second.parse().map_err(|_| err("duration"))?;
+                (start, duration.add_to(start).ok_or(err("end"))?)
+            }
+            (false, false) => (
+                parse_datetime(first).ok_or(err("start"))?,
+                parse_datetime(second).ok_or(err("end"))?,
+            ),
+        };
+        DateTimeInterval::new(start, end).ok_or(err("interval"))
+    }
+}
+
+/// Format of the datetimes of ISO 8601 intervals
+const ISO_DATETIME: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+/// Parses an ISO 8601 datetime, with or without seconds, or a date standing for midnight
+pub(crate) fn parse_datetime(s: &str) -> Option<NaiveDateTime> {
+    if let Ok(date) = s.parse::<NaiveDate>() {
+        return Some(date.and_time(NaiveTime::MIN));
+    }
+    NaiveDateTime::parse_from_str(s, ISO_DATETIME)
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M"))
+        .ok()
+}
+
+/// Error returned when parsing an invalid ISO 8601 interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseIntervalError {
+    part: &'static str,
+}
+
+impl ParseIntervalError {
+    #[inline]
+    pub(crate) fn new(part: &'static str) -> Self {
+        ParseIntervalError { part }
+    }
+}
+
+impl fmt::Display for ParseIntervalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid interval {}", self.part)
+    }
+}
+
+impl Error for ParseIntervalError {}
+
+/// A set of dates kept as sorted, non-overlapping ranges. Ranges which overlap or are adjacent
+/// to each other are merged as soon as they are inserted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet {
+    ranges: Vec<DateRange>,
+}
+
+impl IntervalSet {
+    /// Creates an empty set
+    #[inline]
+    pub fn new() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    /// Returns true if the set holds no dates
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the number of disjoint ranges in the set
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns an iterator over the disjoint ranges of the set in chronological order
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, DateRange> {
+        self.ranges.iter()
+    }
+
+    /// Adds the range to the set, merging it with the ranges it overlaps or touches
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::interval::IntervalSet;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert(DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 5).unwrap()).unwrap());
+    /// set.insert(DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 10).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 12).unwrap()).unwrap());
+    /// assert_eq!(set.len(), 2);
+    /// set.insert(DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 6).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 9).unwrap()).unwrap());
+    /// assert_eq!(set.len(), 1);
+    /// assert!(set.contains(NaiveDate::from_ymd_opt(2020, 1, 7).unwrap()));
+    /// assert!(!set.contains(NaiveDate::from_ymd_opt(2020, 1, 13).unwrap()));
+    pub fn insert(&mut self, range: DateRange) {
+        // Ranges ending more than a day before the new one starts are left untouched, as are
+        // ranges starting more than a day after it ends
+        let lo = self
+            .ranges
+            .partition_point(|r| r.end().succ_opt().is_some_and(|next| next < range.start()));
+        let hi = self
+            .ranges
+            .partition_point(|r| r.start().pred_opt().map_or(true, |prev| prev <= range.end()));
+        let mut merged = range;
+        if lo < hi {
+            let start = merged.start().min(self.ranges[lo].start());
+            let end = merged.end().max(self.ranges[hi - 1].end());
+            merged = DateRange::new(start, end).unwrap_or(merged);
+        }
+        self.ranges.splice(lo..hi, Some(merged));
+    }
+
+    /// Removes the dates of the range from the set, splitting the ranges it falls within
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::interval::IntervalSet;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert(DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap());
+    /// set.remove(DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 10).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 20).unwrap()).unwrap());
+    /// assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 9).unwrap()).unwrap(),
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 21).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap(),
+    /// ]);
+    /// set.remove(DateRange::new(NaiveDate::from_ymd_opt(2019, 12, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 25).unwrap()).unwrap());
+    /// assert_eq!(set.len(), 1);
+    /// assert!(!set.contains(NaiveDate::from_ymd_opt(2020, 1, 25).unwrap()));
+    /// assert!(set.contains(NaiveDate::from_ymd_opt(2020, 1, 26).unwrap()));
+    pub fn remove(&mut self, range: DateRange) {
+        let lo = self.ranges.partition_point(|r| r.end() < range.start());
+        let hi = self.ranges.partition_point(|r| r.start() <= range.end());
+        if lo >= hi {
+            return;
+        }
+        let before = range
+            .start()
+            .pred_opt()
+            .and_then(|end| DateRange::new(self.ranges[lo].start(), end));
+        let after = range
+            .end()
+            .succ_opt()
+            .and_then(|start| DateRange::new(start, self.ranges[hi - 1].end()));
+        self.ranges.splice(lo..hi, before.into_iter().chain(after));
+    }
+
+    /// Returns the dates supported by chrono which are not in the set
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::interval::IntervalSet;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert(DateRange::new(NaiveDate::MIN, NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap());
+    /// let complement = set.complement();
+    /// assert_eq!(complement.iter().cloned().collect::<Vec<_>>(), vec![
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 2, 1).unwrap(), NaiveDate::MAX).unwrap(),
+    /// ]);
+    /// assert_eq!(complement.complement(), set);
+    #[inline]
+    pub fn complement(&self) -> IntervalSet {
+        self.complement_within(DateRange::new(NaiveDate::MIN, NaiveDate::MAX).unwrap())
+    }
+
+    /// Returns true if the date falls within one of the ranges of the set
+    #[inline]
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.range_containing(date).is_some()
+    }
+
+    /// Returns the range of the set containing the date, if any
+    pub fn range_containing(&self, date: NaiveDate) -> Option<DateRange> {
+        let index = self.ranges.partition_point(|r| r.end() < date);
+        self.ranges
+            .get(index)
+            .filter(|range| range.start() <= date)
+            .copied()
+    }
+
+    /// Returns the parts of the given range which are not covered by the set
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::interval::IntervalSet;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert(DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 5).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 10).unwrap()).unwrap());
+    /// let range = DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap();
+    /// let gaps: Vec<DateRange> = set.complement_within(range).iter().cloned().collect();
+    /// assert_eq!(gaps, vec![
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 4).unwrap()).unwrap(),
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 11).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap(),
+    /// ]);
+    pub fn complement_within(&self, range: DateRange) -> IntervalSet {
+        let mut gaps = IntervalSet::new();
+        let mut next = Some(range.start());
+        let first = self.ranges.partition_point(|r| r.end() < range.start());
+        for covered in self.ranges[first..].iter() {
+            let start = match next {
+                Some(start) if start <= range.end() => start,
+                _ => break,
+            };
+            if covered.end() < start {
+                continue;
+            }
+            if covered.start() > range.end() {
+                break;
+            }
+            if let Some(gap) = covered
+                .start()
+                .pred_opt()
+                .and_then(|end| DateRange::new(start, end))
+            {
+                gaps.ranges.push(gap);
+            }
+            next = covered.end().succ_opt();
+        }
+        if let Some(gap) = next.and_then(|start| DateRange::new(start, range.end())) {
+            gaps.ranges.push(gap);
+        }
+        gaps
+    }
+}
+
+impl FromIterator<DateRange> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = DateRange>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<DateRange> for IntervalSet {
+    fn extend<I: IntoIterator<Item = DateRange>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a IntervalSet {
+    type Item = &'a DateRange;
+    type IntoIter = slice::Iter<'a, DateRange>;
+
+    fn into_iter(self) -> slice::Iter<'a, DateRange> {
+        self.iter()
+    }
+}
+
+/// A set of individual dates, such as blackout dates or holidays, supporting set operations. Dates
+/// are stored as the coalesced ranges of an [IntervalSet](struct.IntervalSet.html), so that
+/// long runs of dates take little space.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::interval::DateSet;
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let mut available = DateSet::new();
+/// available.insert_range(DateRange::new(NaiveDate::from_ymd_opt(2020, 7, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 7, 31).unwrap()).unwrap());
+/// let blackout: DateSet = vec![
+///     NaiveDate::from_ymd_opt(2020, 7, 4).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 8, 1).unwrap(),
+/// ].into_iter().collect();
+/// let bookable = &available - &blackout;
+/// assert_eq!(bookable.len(), 30);
+/// assert!(!bookable.contains(NaiveDate::from_ymd_opt(2020, 7, 4).unwrap()));
+/// assert_eq!((&available & &blackout).iter().collect::<Vec<_>>(), vec![NaiveDate::from_ymd_opt(2020, 7, 4).unwrap()]);
+/// assert_eq!((&available | &blackout).len(), 32);
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DateSet {
+    ranges: IntervalSet,
+}
+
+impl DateSet {
+    /// Creates an empty set
+    #[inline]
+    pub fn new() -> Self {
+        DateSet {
+            ranges: IntervalSet::new(),
+        }
+    }
+
+    /// Returns true if the set holds no dates
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the number of dates in the set
+    pub fn len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|range| range.num_days() as usize)
+            .sum()
+    }
+
+    /// Adds the date to the set. Returns true if the date was not already in the set.
+    pub fn insert(&mut self, date: NaiveDate) -> bool {
+        if self.contains(date) {
+            return false;
+        }
+        self.ranges.insert(DateRange::new(date, date).unwrap());
+        true
+    }
+
+    /// Adds every date of the range to the set
+    #[inline]
+    pub fn insert_range(&mut self, range: DateRange) {
+        self.ranges.insert(range);
+    }
+
+    /// Returns true if the date is in the set
+    #[inline]
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.ranges.contains(date)
+    }
+
+    /// Returns an iterator over the dates of the set in chronological order
+    pub fn iter(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.ranges.iter().flat_map(|range| range.iter())
+    }
+
+    /// Returns the runs of consecutive dates making up the set
+    #[inline]
+    pub fn ranges(&self) -> &IntervalSet {
+        &self.ranges
+    }
+
+    /// Returns the dates in either set
+    pub fn union(&self, other: &DateSet) -> DateSet {
+        let mut union = self.clone();
+        union.ranges.extend(other.ranges.iter().copied());
+        union
+    }
+
+    /// Returns the dates in both sets
+    pub fn intersection(&self, other: &DateSet) -> DateSet {
+        let mut ranges = Vec::new();
+        for range in self.ranges.iter() {
+            let first = other
+                .ranges
+                .ranges
+                .partition_point(|r| r.end() < range.start());
+            for covered in other.ranges.ranges[first..].iter() {
+                if covered.start() > range.end() {
+                    break;
+                }
+                let start = covered.start().max(range.start());
+                let end = covered.end().min(range.end());
+                ranges.extend(DateRange::new(start, end));
+            }
+        }
+        DateSet {
+            ranges: IntervalSet { ranges },
+        }
+    }
+
+    /// Returns the dates in this set which are not in the other one
+    pub fn difference(&self, other: &DateSet) -> DateSet {
+        let ranges = self
+            .ranges
+            .iter()
+            .flat_map(|range| other.ranges.complement_within(*range).ranges)
+            .collect();
+        DateSet {
+            ranges: IntervalSet { ranges },
+        }
+    }
+}
+
+impl From<IntervalSet> for DateSet {
+    #[inline]
+    fn from(ranges: IntervalSet) -> Self {
+        DateSet { ranges }
+    }
+}
+
+impl FromIterator<NaiveDate> for DateSet {
+    fn from_iter<I: IntoIterator<Item = NaiveDate>>(iter: I) -> Self {
+        let mut set = DateSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<NaiveDate> for DateSet {
+    fn extend<I: IntoIterator<Item = NaiveDate>>(&mut self, iter: I) {
+        for date in iter {
+            self.insert(date);
+        }
+    }
+}
+
+impl BitOr for &DateSet {
+    type Output = DateSet;
+
+    #[inline]
+    fn bitor(self, other: &DateSet) -> DateSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for &DateSet {
+    type Output = DateSet;
+
+    #[inline]
+    fn bitand(self, other: &DateSet) -> DateSet {
+        self.intersection(other)
+    }
+}
+
+impl Sub for &DateSet {
+    type Output = DateSet;
+
+    #[inline]
+    fn sub(self, other: &DateSet) -> DateSet {
+        self.difference(other)
+    }
+}
+
+/// An index over possibly overlapping date ranges, each carrying a value, answering which of
+/// them contain a date or overlap a range in logarithmic time plus the number of matches. The
+/// ranges are kept sorted by their start, along with a tree of the latest end of every run of
+/// ranges, so that runs ending before a query are skipped as a whole.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::interval::IntervalIndex;
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let index: IntervalIndex<&str> = vec![
+///     (DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 12, 31).unwrap()).unwrap(), "contract"),
+///     (DateRange::new(NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 15).unwrap()).unwrap(), "leave"),
+///     (DateRange::new(NaiveDate::from_ymd_opt(2020, 3, 10).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 10).unwrap()).unwrap(), "project"),
+/// ].into_iter().collect();
+/// fn values(matches: Vec<(DateRange, &&'static str)>) -> Vec<&'static str> {
+///     matches.into_iter().map(|(_, value)| *value).collect()
+/// }
+/// assert_eq!(values(index.containing(NaiveDate::from_ymd_opt(2020, 3, 12).unwrap())), vec!["contract", "leave", "project"]);
+/// assert_eq!(values(index.containing(NaiveDate::from_ymd_opt(2020, 4, 1).unwrap())), vec!["contract", "project"]);
+/// assert!(index.containing(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()).is_empty());
+/// let april = DateRange::new(NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 30).unwrap()).unwrap();
+/// assert_eq!(values(index.overlapping(april)), vec!["contract", "project"]);
+#[derive(Debug, Clone)]
+pub struct IntervalIndex<T> {
+    entries: Vec<(DateRange, T)>,
+    /// Latest end of the entries covered by every node of a binary tree over the entries, the
+    /// root being at index 0 and the children of node `i` at `2i + 1` and `2i + 2`
+    max_ends: Vec<NaiveDate>,
+}
+
+impl<T> IntervalIndex<T> {
+    /// Indexes the ranges along with their values
+    pub fn new<I: IntoIterator<Item = (DateRange, T)>>(entries: I) -> Self {
+        let mut entries: Vec<(DateRange, T)> = entries.into_iter().collect();
+        entries.sort_by_key(|(range, _)| range.start());
+        let mut index = IntervalIndex {
+            max_ends: vec![NaiveDate::MIN; 4 * entries.len().max(1)],
+            entries,
+        };
+        if !index.entries.is_empty() {
+            index.build(0, 0, index.entries.len());
+        }
+        index
+    }
+
+    /// Returns true if no ranges are indexed
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of indexed ranges
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the indexed ranges containing the date along with their values, ordered by the
+    /// start of the ranges
+    #[inline]
+    pub fn containing(&self, date: NaiveDate) -> Vec<(DateRange, &T)> {
+        self.overlapping(DateRange::new(date, date).unwrap())
+    }
+
+    /// Returns the indexed ranges overlapping the range along with their values, ordered by the
+    /// start of the ranges
+    pub fn overlapping(&self, range: DateRange) -> Vec<(DateRange, &T)> {
+        let mut found = Vec::new();
+        // Only the entries starting no later than the end of the range may overlap it
+        let candidates = self
+            .entries
+            .partition_point(|(entry, _)| entry.start() <= range.end());
+        if candidates > 0 {
+            self.collect(0, 0, self.entries.len(), candidates, range, &mut found);
+        }
+        found
+    }
+
+    /// Computes the latest end of the entries within `lo..hi`, covered by the node
+    fn build(&mut self, node: usize, lo: usize, hi: usize) -> NaiveDate {
+        let max_end = if hi - lo == 1 {
+            self.entries[lo].0.end()
+        } else {
+            let mid = lo + (hi - lo) / 2;
+            let left = self.build(2 * node + 1, lo, mid);
+            left.max(self.build(2 * node + 2, mid, hi))
+        };
+        self.max_ends[node] = max_end;
+        max_end
+    }
+
+    /// Collects the entries within `lo..hi`, covered by the node, which are among the first
+    /// `candidates` entries and overlap the range
+    fn collect<'a>(
+        &'a self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        candidates: usize,
+        range: DateRange,
+        found: &mut Vec<(DateRange, &'a T)>,
+    ) {
+        if lo >= candidates || self.max_ends[node] < range.start() {
+            return;
+        }
+        if hi - lo == 1 {
+            let (entry, value) = &self.entries[lo];
+            found.push((*entry, value));
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.collect(2 * node + 1, lo, mid, candidates, range, found);
+        self.collect(2 * node + 2, mid, hi, candidates, range, found);
+    }
+}
+
+impl<T> FromIterator<(DateRange, T)> for IntervalIndex<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (DateRange, T)>>(iter: I) -> Self {
+        IntervalIndex::new(iter)
+    }
+}
+
+/// Returns the free gaps of the window lasting at least `min_duration`, in chronological order.
+/// Busy intervals may overlap each other and extend beyond the window.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, TimeDelta};
+/// use chrono_utilities::naive::interval::{free_slots, DateTimeInterval};
+///
+/// let at = |h, m| NaiveDate::from_ymd_opt(2020, 6, 1).unwrap().and_hms_opt(h, m, 0).unwrap();
+/// let busy = vec![
+///     DateTimeInterval::new(at(10, 0), at(11, 0)).unwrap(),
+///     DateTimeInterval::new(at(8, 0), at(9, 30)).unwrap(),
+///     DateTimeInterval::new(at(10, 30), at(11, 15)).unwrap(),
+///     DateTimeInterval::new(at(11, 30), at(13, 0)).unwrap(),
+/// ];
+/// let day = DateTimeInterval::new(at(9, 0), at(17, 0)).unwrap();
+/// assert_eq!(free_slots(&busy, day, TimeDelta::minutes(30)), vec![
+///     DateTimeInterval::new(at(9, 30), at(10, 0)).unwrap(),
+///     DateTimeInterval::new(at(13, 0), at(17, 0)).unwrap(),
+/// ]);
+pub fn free_slots<I>(
+    busy: I,
+    window: DateTimeInterval,
+    min_duration: TimeDelta,
+) -> Vec<DateTimeInterval>
+where
+    I: IntoIterator,
+    I::Item: Borrow<DateTimeInterval>,
+{
+    let mut busy: Vec<DateTimeInterval> = busy
+        .into_iter()
+        .filter_map(|interval| interval.borrow().intersection(&window))
+        .collect();
+    busy.sort();
+    let mut slots = Vec::new();
+    let mut free_from = window.start();
+    let ends = busy
+        .iter()
+        .map(|interval| (interval.start(), interval.end()))
+        .chain(Some((window.end(), window.end())));
+    for (busy_from, busy_until) in ends {
+        if let Some(slot) = DateTimeInterval::new(free_from, busy_from)
+            .filter(|slot| slot.duration() >= min_duration)
+        {
+            slots.push(slot);
+        }
+        free_from = free_from.max(busy_until);
+    }
+    slots
+}
+
+/// Returns the set of dates lying within `before` days before or `after` days after any of the
+/// given dates. Windows of nearby dates are merged together. Windows are clamped to the range
+/// of dates supported by chrono.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::interval::expand;
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let promotions = vec![
+///     NaiveDate::from_ymd_opt(2020, 3, 10).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 3, 13).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(),
+/// ];
+/// let windows: Vec<DateRange> = expand(&promotions, 2, 2).iter().cloned().collect();
+/// assert_eq!(windows, vec![
+///     DateRange::new(NaiveDate::from_ymd_opt(2020, 3, 8).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 15).unwrap()).unwrap(),
+///     DateRange::new(NaiveDate::from_ymd_opt(2020, 3, 30).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 3).unwrap()).unwrap(),
+/// ]);
+pub fn expand<I>(dates: I, before: u32, after: u32) -> IntervalSet
+where
+    I: IntoIterator,
+    I::Item: Borrow<NaiveDate>,
+{
+    dates
+        .into_iter()
+        .filter_map(|date| {
+            let date = *date.borrow();
+            let start = date
+                .checked_sub_signed(TimeDelta::days(before as i64))
+                .unwrap_or(NaiveDate::MIN);
+            let end = date
+                .checked_add_signed(TimeDelta::days(after as i64))
+                .unwrap_or(NaiveDate::MAX);
+            DateRange::new(start, end)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalIndex;
+    use crate::naive::range::DateRange;
+    use chrono::{NaiveDate, TimeDelta};
+
+    #[test]
+    fn test_index_matches_linear_scan() {
+        let origin = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let day = |n: i64| origin + TimeDelta::days(n);
+        // Deterministic pseudo-random ranges of varying lengths
+        let mut seed: i64 = 17;
+        let mut next = || {
+            seed = (seed * 1_103_515_245 + 12_345) % 2_147_483_648;
+            seed
+        };
+        let ranges: Vec<(DateRange, usize)> = (0..500)
+            .map(|i| {
+                let start = next() % 1000;
+                let length = next() % if i % 10 == 0 { 400 } else { 20 };
+                (DateRange::new(day(start), day(start + length)).unwrap(), i)
+            })
+            .collect();
+        let index: IntervalIndex<usize> = ranges.iter().cloned().collect();
+        assert_eq!(index.len(), ranges.len());
+        for offset in -10..1450 {
+            let mut expected: Vec<usize> = ranges
+                .iter()
+                .filter(|(range, _)| range.contains(day(offset)))
+                .map(|(_, i)| *i)
+                .collect();
+            let mut found: Vec<usize> = index
+                .containing(day(offset))
+                .into_iter()
+                .map(|(_, i)| *i)
+                .collect();
+            expected.sort_unstable();
+            found.sort_unstable();
+            assert_eq!(found, expected);
+        }
+        let query = DateRange::new(day(100), day(130)).unwrap();
+        let found = index.overlapping(query);
+        assert!(found.windows(2).all(|w| w[0].0.start() <= w[1].0.start()));
+        assert_eq!(
+            found.len(),
+            ranges
+                .iter()
+                .filter(|(range, _)| range.overlaps(&query))
+                .count()
+        );
+    }
+}
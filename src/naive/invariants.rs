@@ -0,0 +1,232 @@
+//! Self-tests checking the invariants of periods and calendars over a range of dates, meant to
+//! be run from downstream test suites against the conventions and calendars they configure
+use super::period::{
+    Gregorian, Month, Period, PeriodCalendar, Quarter, Unit, Week, WeekConvention, Year,
+};
+use super::range::DateRange;
+use chrono::{Datelike, NaiveDate};
+use std::error::Error;
+use std::fmt;
+
+/// An invariant found not to hold for a date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvariantViolation {
+    /// The date for which the invariant does not hold
+    pub date: NaiveDate,
+    /// Description of the invariant
+    pub invariant: &'static str,
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invariant violated on {}: {}", self.date, self.invariant)
+    }
+}
+
+impl Error for InvariantViolation {}
+
+/// Checks the invariants of the typed [periods](../period/trait.Period.html), of the
+/// [Gregorian](../period/struct.Gregorian.html) calendar and of ISO 8601 weeks for every date
+/// of the range. Returns the first violation found, if any. Checks involving dates outside of
+/// the range supported by chrono are skipped.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::invariants::self_test;
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let range = DateRange::new(NaiveDate::from_ymd_opt(2019, 12, 1).unwrap(), NaiveDate::from_ymd_opt(2021, 1, 31).unwrap()).unwrap();
+/// assert_eq!(self_test(range), Ok(()));
+pub fn self_test(range: DateRange) -> Result<(), InvariantViolation> {
+    for date in range.iter() {
+        check_period::<Week>(date)?;
+        check_period::<Month>(date)?;
+        check_period::<Quarter>(date)?;
+        check_period::<Year>(date)?;
+    }
+    self_test_calendar(&Gregorian, range)?;
+    self_test_week_convention(WeekConvention::ISO, range)
+}
+
+/// Checks the invariants of a calendar for every date of the range: the period of every
+/// granularity containing a date starts no later than it and ends no earlier than it, periods
+/// follow each other without gaps, weeks are 7 days long, and the months and quarters of a year
+/// exactly cover it. Returns the first violation found, if any.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, Weekday};
+/// use chrono_utilities::naive::fiscal::{Calendar445, WeekPattern};
+/// use chrono_utilities::naive::invariants::self_test_calendar;
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let cal = Calendar445::new(1, Weekday::Sat, WeekPattern::FourFourFive).unwrap();
+/// let range = DateRange::new(NaiveDate::from_ymd_opt(2014, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2016, 12, 31).unwrap()).unwrap();
+/// assert_eq!(self_test_calendar(&cal, range), Ok(()));
+pub fn self_test_calendar<C: PeriodCalendar>(
+    cal: &C,
+    range: DateRange,
+) -> Result<(), InvariantViolation> {
+    let units = [
+        Unit::Day,
+        Unit::Week,
+        Unit::Month,
+        Unit::Quarter,
+        Unit::Year,
+    ];
+    for date in range.iter() {
+        for unit in units.iter() {
+            let period = match cal.period_of(date, *unit) {
+                Some(period) => period,
+                None => continue,
+            };
+            check(
+                date,
+                period.start() <= date && date <= period.end(),
+                "period contains the date",
+            )?;
+            if *unit == Unit::Week {
+                check(date, period.num_days() == 7, "week is 7 days long")?;
+            }
+            let next = match period.end().succ_opt() {
+                Some(next) => next,
+                None => continue,
+            };
+            if let Some(succ) = cal.period_of(next, *unit) {
+                check(date, succ.start() == next, "period succeeds without a gap")?;
+                check(
+                    date,
+                    cal.period_of(period.end(), *unit) == Some(period),
+                    "period is the same across its dates",
+                )?;
+            }
+            if *unit == Unit::Year && date == period.start() {
+                check_partition(cal, period, Unit::Month)?;
+                check_partition(cal, period, Unit::Quarter)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks a week convention for every date of the range: weeks start on the first day of the
+/// convention and are numbered consecutively, restarting from 1 with each year. Returns the
+/// first violation found, if any.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::invariants::self_test_week_convention;
+/// use chrono_utilities::naive::period::WeekConvention;
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let range = DateRange::new(NaiveDate::from_ymd_opt(2015, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()).unwrap();
+/// assert_eq!(self_test_week_convention(WeekConvention::US, range), Ok(()));
+pub fn self_test_week_convention(
+    convention: WeekConvention,
+    range: DateRange,
+) -> Result<(), InvariantViolation> {
+    for date in range.iter() {
+        if let Some(start) = convention.start_of_week(date) {
+            check(
+                date,
+                start.weekday() == convention.first_day(),
+                "week starts on the first day of the convention",
+            )?;
+        }
+        let next = match date.succ_opt() {
+            Some(next) => next,
+            None => continue,
+        };
+        if let (Some((year, week)), Some(following)) =
+            (convention.week_of_year(date), convention.week_of_year(next))
+        {
+            let expected = if next.weekday() != convention.first_day() {
+                following == (year, week)
+            } else {
+                following == (year, week + 1) || following == (year + 1, 1)
+            };
+            check(date, expected, "weeks are numbered consecutively")?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks the invariants of the typed period containing the date
+fn check_period<P: Period>(date: NaiveDate) -> Result<(), InvariantViolation> {
+    let period = match P::containing(date) {
+        Some(period) => period,
+        None => return Ok(()),
+    };
+    check(
+        date,
+        period.start() <= period.end(),
+        "period starts before it ends",
+    )?;
+    check(date, period.contains(date), "period contains the date")?;
+    check(
+        date,
+        P::from_key(period.to_key()) == Some(period),
+        "period round-trips through its key",
+    )?;
+    if let Some(succ) = period.succ() {
+        check(
+            date,
+            period.end().succ_opt() == Some(succ.start()),
+            "period succeeds without a gap",
+        )?;
+        check(
+            date,
+            succ.pred() == Some(period),
+            "pred of succ is the period",
+        )?;
+        check(
+            date,
+            succ.to_key() == period.to_key() + 1,
+            "keys of successive periods are consecutive",
+        )?;
+    }
+    if let Some(pred) = period.pred() {
+        check(
+            date,
+            pred.succ() == Some(period),
+            "succ of pred is the period",
+        )?;
+    }
+    Ok(())
+}
+
+/// Checks that the periods of the given granularity exactly cover the year
+fn check_partition<C: PeriodCalendar>(
+    cal: &C,
+    year: DateRange,
+    unit: Unit,
+) -> Result<(), InvariantViolation> {
+    let mut next = year.start();
+    while next <= year.end() {
+        let period = cal.period_of(next, unit);
+        check(
+            next,
+            period.is_some_and(|period| period.start() == next && period.end() <= year.end()),
+            "periods partition the year",
+        )?;
+        next = match period.and_then(|period| period.end().succ_opt()) {
+            Some(next) => next,
+            None => return Ok(()),
+        };
+    }
+    Ok(())
+}
+
+#[inline]
+fn check(date: NaiveDate, holds: bool, invariant: &'static str) -> Result<(), InvariantViolation> {
+    if holds {
+        Ok(())
+    } else {
+        Err(InvariantViolation { date, invariant })
+    }
+}
@@ -0,0 +1,370 @@
+//! Period keys of any granularity, parsed from and formatted to their canonical strings.
+//!
+//! The canonical strings are stable and meant to be exchanged with services written in other
+//! languages. Years are written with at least 4 digits, zero-padded, and negative years are
+//! prefixed with `-`. Every other number is zero-padded to 2 digits, except for quarters.
+//!
+//! | Period | Format | Example |
+//! |--------|--------|---------|
+//! | Year | `YYYY` | `2024` |
+//! | Quarter | `YYYY-Qq` | `2024-Q3` |
+//! | Month | `YYYY-MM` | `2024-07` |
+//! | ISO 8601 week | `YYYY-Www` | `2024-W31` |
+//! | Day | `YYYY-MM-DD` | `2024-07-05` |
+//! | Fiscal period | `FY` then `YYYY-Ppp` | `FY2025-P03` |
+//!
+//! Formatting a key and parsing the result back always yields the same key. Parsing only accepts
+//! canonical strings, so that formatting a parsed key always yields the string it was parsed
+//! from and every period has exactly one key.
+use super::fiscal::FiscalPeriod;
+use super::period::{Month, ParsePeriodError, Period, Quarter, Unit, Week, Year};
+use super::range::DateRange;
+use chrono::{Datelike, NaiveDate};
+use std::fmt;
+use std::str::FromStr;
+
+/// A period of any granularity. Parsing detects the granularity from the canonical form of the
+/// string: `2024-03-05` is a day, `2024-W10` an ISO 8601 week, `2024-03` a month, `2024-Q1` a
+/// quarter, `2024` a year and `FY2025-P03` a fiscal period.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::key::PeriodKey;
+/// use chrono_utilities::naive::period::{Quarter, Unit};
+///
+/// let key: PeriodKey = "2024-Q1".parse().unwrap();
+/// assert_eq!(key, PeriodKey::Quarter(Quarter::new(2024, 1).unwrap()));
+/// assert_eq!(key.unit(), Some(Unit::Quarter));
+/// assert_eq!(key.end(), NaiveDate::from_ymd_opt(2024, 3, 31));
+/// let key: PeriodKey = "2024-03-05".parse().unwrap();
+/// assert_eq!(key, PeriodKey::Day(NaiveDate::from_ymd_opt(2024, 3, 5).unwrap()));
+/// assert_eq!("2024-W10".parse::<PeriodKey>().unwrap().unit(), Some(Unit::Week));
+/// assert_eq!("2024-03".parse::<PeriodKey>().unwrap().unit(), Some(Unit::Month));
+/// assert_eq!("2024".parse::<PeriodKey>().unwrap().to_string(), "2024");
+/// let fiscal: PeriodKey = "FY2025-P03".parse().unwrap();
+/// assert_eq!(fiscal.to_string(), "FY2025-P03");
+/// assert_eq!(fiscal.start(), None);
+/// assert!("2024-3-5".parse::<PeriodKey>().is_err());
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PeriodKey {
+    Day(NaiveDate),
+    Week(Week),
+    Month(Month),
+    Quarter(Quarter),
+    Year(Year),
+    Fiscal(FiscalPeriod),
+}
+
+impl PeriodKey {
+    /// The maximum length, in bytes, of the canonical string of a key
+    pub const MAX_LEN: usize = 17;
+
+    /// Writes the canonical string of the key into the buffer, without allocating, and returns
+    /// the written part of the buffer. Returns `None` if the buffer is too short, which never
+    /// happens with a buffer of [MAX_LEN](#associatedconstant.MAX_LEN) bytes.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono_utilities::naive::key::PeriodKey;
+    /// use chrono_utilities::naive::period::Month;
+    ///
+    /// let mut buf = [0; PeriodKey::MAX_LEN];
+    /// let key = PeriodKey::from(Month::new(2024, 3).unwrap());
+    /// assert_eq!(key.format_into(&mut buf), Some("2024-03"));
+    /// assert_eq!(key.format_into(&mut [0; 4]), None);
+    pub fn format_into<'a>(&self, buf: &'a mut [u8]) -> Option<&'a str> {
+        let mut out = KeyBuf { buf, len: 0 };
+        match self {
+            PeriodKey::Day(date) => {
+                out.push_year(date.year())?;
+                out.push(b"-")?;
+                out.push_number(date.month(), 2)?;
+                out.push(b"-")?;
+                out.push_number(date.day(), 2)?;
+            }
+            PeriodKey::Week(week) => {
+                out.push_year(week.year())?;
+                out.push(b"-W")?;
+                out.push_number(week.week(), 2)?;
+            }
+            PeriodKey::Month(month) => {
+                out.push_year(month.year())?;
+                out.push(b"-")?;
+                out.push_number(month.month(), 2)?;
+            }
+            PeriodKey::Quarter(quarter) => {
+                out.push_year(quarter.year())?;
+                out.push(b"-Q")?;
+                out.push_number(quarter.quarter(), 1)?;
+            }
+            PeriodKey::Year(year) => out.push_year(year.year())?,
+            PeriodKey::Fiscal(period) => {
+                out.push(b"FY")?;
+                out.push_year(period.year())?;
+                out.push(b"-P")?;
+                out.push_number(period.period(), 2)?;
+            }
+        }
+        let KeyBuf { buf, len } = out;
+        let buf: &'a [u8] = buf;
+        std::str::from_utf8(&buf[..len]).ok()
+    }
+
+    /// Writes the canonical string of the key to the writer, without allocating
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use std::fmt::Write;
+    /// use chrono_utilities::naive::key::PeriodKey;
+    /// use chrono_utilities::naive::period::{Quarter, Year};
+    ///
+    /// let mut out = String::with_capacity(64);
+    /// for key in [PeriodKey::from(Year::new(2024).unwrap()), Quarter::new(2024, 3).unwrap().into()].iter() {
+    ///     key.write_to(&mut out).unwrap();
+    ///     out.push(',');
+    /// }
+    /// assert_eq!(out, "2024,2024-Q3,");
+    #[inline]
+    pub fn write_to<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let mut buf = [0; PeriodKey::MAX_LEN];
+        out.write_str(self.format_into(&mut buf).ok_or(fmt::Error)?)
+    }
+
+    /// Returns the granularity of the period, or `None` for a fiscal period
+    pub fn unit(&self) -> Option<Unit> {
+        match self {
+            PeriodKey::Day(_) => Some(Unit::Day),
+            PeriodKey::Week(_) => Some(Unit::Week),
+            PeriodKey::Month(_) => Some(Unit::Month),
+            PeriodKey::Quarter(_) => Some(Unit::Quarter),
+            PeriodKey::Year(_) => Some(Unit::Year),
+            PeriodKey::Fiscal(_) => None,
+        }
+    }
+
+    /// Returns the first date of the period, or `None` for a fiscal period whose dates depend on
+    /// the fiscal calendar
+    #[inline]
+    pub fn start(&self) -> Option<NaiveDate> {
+        self.range().map(|range| range.start())
+    }
+
+    /// Returns the last date of the period, or `None` for a fiscal period whose dates depend on
+    /// the fiscal calendar
+    #[inline]
+    pub fn end(&self) -> Option<NaiveDate> {
+        self.range().map(|range| range.end())
+    }
+
+    /// Returns the dates spanned by the period, or `None` for a fiscal period whose dates depend
+    /// on the fiscal calendar
+    pub fn range(&self) -> Option<DateRange> {
+        match self {
+            PeriodKey::Day(date) => DateRange::new(*date, *date),
+            PeriodKey::Week(week) => Some(week.range()),
+            PeriodKey::Month(month) => Some(month.range()),
+            PeriodKey::Quarter(quarter) => Some(quarter.range()),
+            PeriodKey::Year(year) => Some(year.range()),
+            PeriodKey::Fiscal(_) => None,
+        }
+    }
+}
+
+impl From<NaiveDate> for PeriodKey {
+    #[inline]
+    fn from(date: NaiveDate) -> Self {
+        PeriodKey::Day(date)
+    }
+}
+
+impl From<Week> for PeriodKey {
+    #[inline]
+    fn from(week: Week) -> Self {
+        PeriodKey::Week(week)
+    }
+}
+
+impl From<Month> for PeriodKey {
+    #[inline]
+    fn from(month: Month) -> Self {
+        PeriodKey::Month(month)
+    }
+}
+
+impl From<Quarter> for PeriodKey {
+    #[inline]
+    fn from(quarter: Quarter) -> Self {
+        PeriodKey::Quarter(quarter)
+    }
+}
+
+impl From<Year> for PeriodKey {
+    #[inline]
+    fn from(year: Year) -> Self {
+        PeriodKey::Year(year)
+    }
+}
+
+impl From<FiscalPeriod> for PeriodKey {
+    #[inline]
+    fn from(period: FiscalPeriod) -> Self {
+        PeriodKey::Fiscal(period)
+    }
+}
+
+impl fmt::Display for PeriodKey {
+    /// Formats the key as its canonical string
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
+
+/// A byte buffer being filled with the canonical string of a key
+struct KeyBuf<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl KeyBuf<'_> {
+    #[inline]
+    fn push(&mut self, bytes: &[u8]) -> Option<()> {
+        let end = self.len.checked_add(bytes.len())?;
+        self.buf.get_mut(self.len..end)?.copy_from_slice(bytes);
+        self.len = end;
+        Some(())
+    }
+
+    /// Pushes the number zero-padded to the given number of digits
+    fn push_number(&mut self, mut n: u32, width: usize) -> Option<()> {
+        let mut digits = [b'0'; 10];
+        let mut start = digits.len();
+        while n > 0 || start == digits.len() {
+            start -= 1;
+            digits[start] = b'0' + (n % 10) as u8;
+            n /= 10;
+        }
+        self.push(&digits[start.min(digits.len() - width)..])
+    }
+
+    /// Pushes the year zero-padded to 4 digits, preceded by its sign if negative
+    fn push_year(&mut self, year: i32) -> Option<()> {
+        if year < 0 {
+            self.push(b"-")?;
+        }
+        self.push_number(year.unsigned_abs(), 4)
+    }
+}
+
+impl FromStr for PeriodKey {
+    type Err = ParsePeriodError;
+
+    fn from_str(s: &str) -> Result<Self, ParsePeriodError> {
+        if s.starts_with("FY") {
+            return s.parse().map(PeriodKey::Fiscal);
+        }
+        if s.contains("-W") {
+            return s.parse().map(PeriodKey::Week);
+        }
+        if s.contains("-Q") {
+            return s.parse().map(PeriodKey::Quarter);
+        }
+        // A leading minus sign belongs to the year
+        match s.get(1..).map_or(0, |rest| rest.matches('-').count()) {
+            0 => s.parse().map(PeriodKey::Year),
+            1 => s.parse().map(PeriodKey::Month),
+            _ => parse_day(s).map(PeriodKey::Day),
+        }
+    }
+}
+
+/// Parses a day formatted as in `2024-03-05`
+fn parse_day(s: &str) -> Result<NaiveDate, ParsePeriodError> {
+    let err = ParsePeriodError::new("day");
+    let (month, day) = s.rsplit_once('-').ok_or(err)?;
+    let month: Month = month.parse().map_err(|_| err)?;
+    let day = day.parse().map_err(|_| err)?;
+    let date = month.start().with_day(day).ok_or(err)?;
+    Some(date)
+        .filter(|date| PeriodKey::Day(*date).to_string() == s)
+        .ok_or(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PeriodKey;
+    use crate::naive::fiscal::FiscalPeriod;
+    use crate::naive::period::{Month, Period, Quarter, Week, Year};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_keys_round_trip() {
+        for date in [
+            NaiveDate::MIN,
+            NaiveDate::from_ymd_opt(-1, 12, 31).unwrap(),
+            NaiveDate::from_ymd_opt(0, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(7, 7, 5).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 7, 5).unwrap(),
+            NaiveDate::from_ymd_opt(10000, 10, 10).unwrap(),
+            NaiveDate::MAX,
+        ]
+        .iter()
+        {
+            let mut keys = vec![
+                PeriodKey::Day(*date),
+                Month::from(*date).into(),
+                Quarter::from(*date).into(),
+                Year::from(*date).into(),
+            ];
+            keys.extend(Week::containing(*date).map(PeriodKey::from));
+            for key in keys {
+                assert_eq!(key.to_string().parse(), Ok(key));
+            }
+        }
+        for year in [i32::MIN, -1, 0, 12, 2025, i32::MAX].iter() {
+            for period in [1, 9, 10, 13].iter() {
+                let key = PeriodKey::from(FiscalPeriod::new(*year, *period).unwrap());
+                assert_eq!(key.to_string().parse(), Ok(key));
+            }
+        }
+        let key = PeriodKey::Day(NaiveDate::from_ymd_opt(-1, 2, 3).unwrap());
+        assert_eq!(key.to_string(), "-0001-02-03");
+        let key = PeriodKey::from(FiscalPeriod::new(i32::MIN, 13).unwrap());
+        assert_eq!(key.to_string().len(), PeriodKey::MAX_LEN);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::PeriodKey;
+    use crate::naive::period::serde_impls::CanonicalVisitor;
+    use serde::de::{Deserialize, Deserializer};
+    use serde::ser::{Serialize, Serializer};
+
+    impl Serialize for PeriodKey {
+        /// Serializes the key as its canonical string, such as `"2024-Q3"`
+        ///
+        /// # Example
+        ///
+        /// ~~~~
+        /// use chrono_utilities::naive::key::PeriodKey;
+        ///
+        /// let keys: Vec<PeriodKey> = serde_json::from_str(r#"["2024-07-05", "FY2025-P03"]"#).unwrap();
+        /// assert_eq!(serde_json::to_string(&keys).unwrap(), r#"["2024-07-05","FY2025-P03"]"#);
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PeriodKey {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(CanonicalVisitor::new(
+                "a period key such as 2024-07-05, 2024-W31, 2024-07, 2024-Q3, 2024 or FY2025-P03",
+            ))
+        }
+    }
+}
@@ -1,6 +1,42 @@
-//! Utility structs and traits related to chrono's [NaiveDate](https://docs.rs/chrono/0.4.11/chrono/naive/struct.NaiveDate.html)
-use crate::oldtime::Duration as OldDuration;
-use chrono::{Datelike, NaiveDate};
+//! Utility structs and traits related to chrono's [NaiveDate](https://docs.rs/chrono/0.4.35/chrono/naive/struct.NaiveDate.html)
+use business::WeekendSet;
+use period::YearDivisions;
+use chrono::{Datelike, NaiveDate, TimeDelta, Weekday};
+
+pub mod academic;
+pub mod aggregate;
+pub mod business;
+pub mod clock;
+pub mod compare;
+pub mod countdown;
+pub mod cron;
+pub mod datetime;
+pub mod duration;
+pub mod epoch;
+pub mod features;
+pub mod fiscal;
+pub mod fortnight;
+pub mod humanize;
+pub mod interval;
+pub mod invariants;
+pub mod key;
+pub mod natural;
+pub mod partition;
+pub mod payroll;
+pub mod period;
+pub mod progress;
+pub mod proration;
+pub mod range;
+pub mod recurrence;
+pub mod relative;
+pub mod repeating;
+pub mod rota;
+pub mod schedule;
+pub mod sequence;
+pub mod strict;
+pub mod summary;
+pub mod timeline;
+pub mod weekday;
 
 /// Value at index `i` is the minimum number of days in the month `i+1`
 static MONTH_MIN_DAYS: [u8; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
@@ -8,6 +44,42 @@ static MONTH_MIN_DAYS: [u8; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 3
 /// Value at index `i` is the maximum number of days in the month `i+1`
 static MONTH_MAX_DAYS: [u8; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
 
+/// Returns true if the year of the proleptic Gregorian calendar is leap, including years out of
+/// the range supported by chrono
+pub(crate) fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Returns the number of days in the given month of the given year
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    let index = (month - 1) as usize;
+    if is_leap_year(year) {
+        MONTH_MAX_DAYS[index] as u32
+    } else {
+        MONTH_MIN_DAYS[index] as u32
+    }
+}
+
+/// Shifts the date by the given number of months. The day is clamped to the last day of the
+/// target month, so that Jan 31 shifted by one month becomes the last day of February.
+pub(crate) fn shift_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    let total = date.year() as i64 * 12 + date.month0() as i64 + months as i64;
+    let year = total.div_euclid(12);
+    let month = total.rem_euclid(12) as u32 + 1;
+    if year < i32::MIN as i64 || year > i32::MAX as i64 {
+        return None;
+    }
+    let year = year as i32;
+    NaiveDate::from_ymd_opt(year, month, date.day().min(days_in_month(year, month)))
+}
+
+/// Returns the first day of the cycle of `length` days containing the date, cycles repeating in
+/// both directions from the anchor date, on which one of them starts
+pub(crate) fn start_of_cycle(date: NaiveDate, anchor: NaiveDate, length: i64) -> Option<NaiveDate> {
+    let offset = date.signed_duration_since(anchor).num_days().rem_euclid(length);
+    date.checked_sub_signed(TimeDelta::days(offset))
+}
+
 /// Common set of methods for transitioning dates into newer ones
 pub trait DateTransitions: Sized {
     /// Returns true if leap year
@@ -16,6 +88,27 @@ pub trait DateTransitions: Sized {
     /// Returns the last day of the month
     fn last_day_of_month(&self) -> u32;
 
+    /// Returns the number of days in the year
+    fn days_in_year(&self) -> u32 {
+        if self.is_leap_year() {
+            366
+        } else {
+            365
+        }
+    }
+
+    /// Returns the number of days in the quarter, adding up the last days of its months
+    ///
+    /// # Panics
+    ///
+    /// Panics if a month of the year of the date has no start.
+    fn days_in_quarter(&self) -> u32
+    where
+        Self: PartialEq,
+    {
+        quarter_days(self).expect("months of the year out of range")
+    }
+
     /// Returns the date as on the start of the current year
     fn start_of_year(&self) -> Option<Self>;
 
@@ -69,6 +162,116 @@ pub trait DateTransitions: Sized {
 
     /// Returns the date as on the end of the succeeding week
     fn end_of_succ_iso8601_week(&self) -> Option<Self>;
+
+    /// Returns true if both dates fall within the same ISO 8601 week
+    fn is_same_iso8601_week_as(&self, other: &Self) -> bool
+    where
+        Self: PartialEq,
+    {
+        same_start(self.start_of_iso8601_week(), other.start_of_iso8601_week())
+    }
+
+    /// Returns true if both dates fall within the same month of the same year
+    fn is_same_month_as(&self, other: &Self) -> bool
+    where
+        Self: PartialEq,
+    {
+        same_start(self.start_of_month(), other.start_of_month())
+    }
+
+    /// Returns true if both dates fall within the same quarter of the same year
+    fn is_same_quarter_as(&self, other: &Self) -> bool
+    where
+        Self: PartialEq,
+    {
+        self.is_same_year_as(other)
+            && matches!(
+                (month0_of(self), month0_of(other)),
+                (Some(month0), Some(other_month0)) if month0 / 3 == other_month0 / 3
+            )
+    }
+
+    /// Returns true if both dates fall within the same year
+    fn is_same_year_as(&self, other: &Self) -> bool
+    where
+        Self: PartialEq,
+    {
+        same_start(self.start_of_year(), other.start_of_year())
+    }
+}
+
+/// Returns true if both periods start on the same date, which neither of them lacks
+fn same_start<D: PartialEq>(start: Option<D>, other_start: Option<D>) -> bool {
+    matches!((start, other_start), (Some(start), Some(other_start)) if start == other_start)
+}
+
+/// Returns the number of months between the start of the year of the date and its month,
+/// stepping through the months of the year
+fn month0_of<D: DateTransitions + PartialEq>(date: &D) -> Option<u32> {
+    let month = date.start_of_month()?;
+    let mut start = date.start_of_year()?;
+    for month0 in 0..12 {
+        if start == month {
+            return Some(month0);
+        }
+        start = start.start_of_succ_month()?;
+    }
+    None
+}
+
+/// Returns the number of days in the quarter of the date, stepping through the months of its
+/// year
+fn quarter_days<D: DateTransitions + PartialEq>(date: &D) -> Option<u32> {
+    let mut month = date.start_of_year()?;
+    for _ in 0..month0_of(date)? / 3 * 3 {
+        month = month.start_of_succ_month()?;
+    }
+    let mut days = month.last_day_of_month();
+    for _ in 1..3 {
+        month = month.start_of_succ_month()?;
+        days += month.last_day_of_month();
+    }
+    Some(days)
+}
+
+/// Common set of predicates on the day of the week of dates and on their position within
+/// weeks, months, quarters and years
+pub trait DatePredicates {
+    /// Returns true if the date falls on a Saturday or Sunday
+    fn is_weekend(&self) -> bool;
+
+    /// Returns true if the date falls from Monday to Friday
+    fn is_weekday(&self) -> bool;
+
+    /// Returns true if the date falls on a day of the given weekend
+    fn is_weekend_in(&self, weekend: WeekendSet) -> bool;
+
+    /// Returns true if the date falls on a day outside of the given weekend
+    fn is_weekday_in(&self, weekend: WeekendSet) -> bool;
+
+    /// Returns true if the date is the first day of its month
+    fn is_first_day_of_month(&self) -> bool;
+
+    /// Returns true if the date is the last day of its month
+    fn is_last_day_of_month(&self) -> bool;
+
+    /// Returns true if the date is the first day of its quarter
+    fn is_first_day_of_quarter(&self) -> bool;
+
+    /// Returns true if the date is the last day of its quarter
+    fn is_last_day_of_quarter(&self) -> bool;
+
+    /// Returns true if the date is the first day of its year
+    fn is_first_day_of_year(&self) -> bool;
+
+    /// Returns true if the date is the last day of its year
+    fn is_last_day_of_year(&self) -> bool;
+
+    /// Returns true if the date is the first day of its ISO 8601 week, a Monday
+    fn is_start_of_iso8601_week(&self) -> bool;
+
+    /// Returns true if the date is the last day of its ISO 8601 week, a Sunday
+    fn is_end_of_iso8601_week(&self) -> bool;
 }
 
 impl DateTransitions for NaiveDate {
@@ -80,13 +283,13 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(1996, 8, 14);
+    /// let d1 = NaiveDate::from_ymd_opt(1996, 8, 14).unwrap();
     /// assert_eq!(d1.is_leap_year(), true);
-    /// let d2 = NaiveDate::from_ymd(1900, 2, 28);
+    /// let d2 = NaiveDate::from_ymd_opt(1900, 2, 28).unwrap();
     /// assert_eq!(d2.is_leap_year(), false);
-    /// let d3 = NaiveDate::from_ymd(2000, 2, 29);
+    /// let d3 = NaiveDate::from_ymd_opt(2000, 2, 29).unwrap();
     /// assert_eq!(d3.is_leap_year(), true);
-    /// let d4 = NaiveDate::from_ymd(1997, 11, 3);
+    /// let d4 = NaiveDate::from_ymd_opt(1997, 11, 3).unwrap();
     /// assert_eq!(d4.is_leap_year(), false);
     #[inline]
     fn is_leap_year(&self) -> bool {
@@ -105,11 +308,11 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(1996, 2, 23);
+    /// let d1 = NaiveDate::from_ymd_opt(1996, 2, 23).unwrap();
     /// assert_eq!(d1.last_day_of_month(), 29);
-    /// let d2 = NaiveDate::from_ymd(1993, 2, 1);
+    /// let d2 = NaiveDate::from_ymd_opt(1993, 2, 1).unwrap();
     /// assert_eq!(d2.last_day_of_month(), 28);
-    /// let d3 = NaiveDate::from_ymd(2000, 1, 1);
+    /// let d3 = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
     /// assert_eq!(d3.last_day_of_month(), 31);
     #[inline]
     fn last_day_of_month(&self) -> u32 {
@@ -121,6 +324,44 @@ impl DateTransitions for NaiveDate {
         }
     }
 
+    /// Returns the number of days in the year of the date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DateTransitions;
+    ///
+    /// assert_eq!(NaiveDate::from_ymd_opt(1996, 8, 14).unwrap().days_in_year(), 366);
+    /// assert_eq!(NaiveDate::from_ymd_opt(1900, 8, 14).unwrap().days_in_year(), 365);
+    #[inline]
+    fn days_in_year(&self) -> u32 {
+        if self.is_leap_year() {
+            366
+        } else {
+            365
+        }
+    }
+
+    /// Returns the number of days in the quarter of the date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DateTransitions;
+    ///
+    /// assert_eq!(NaiveDate::from_ymd_opt(1996, 2, 14).unwrap().days_in_quarter(), 91);
+    /// assert_eq!(NaiveDate::from_ymd_opt(1997, 2, 14).unwrap().days_in_quarter(), 90);
+    /// assert_eq!(NaiveDate::from_ymd_opt(1997, 12, 1).unwrap().days_in_quarter(), 92);
+    #[inline]
+    fn days_in_quarter(&self) -> u32 {
+        let first = self.month0() / 3 * 3 + 1;
+        (first..first + 3)
+            .map(|month| days_in_month(self.year(), month))
+            .sum()
+    }
+
     /// Returns the year start date for the current date
     ///
     /// # Example
@@ -129,8 +370,8 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d = NaiveDate::from_ymd(2019, 3, 31);
-    /// assert_eq!(d.start_of_year().unwrap(), NaiveDate::from_ymd(2019, 1, 1));
+    /// let d = NaiveDate::from_ymd_opt(2019, 3, 31).unwrap();
+    /// assert_eq!(d.start_of_year().unwrap(), NaiveDate::from_ymd_opt(2019, 1, 1).unwrap());
     #[inline]
     fn start_of_year(&self) -> Option<Self> {
         // TODO: Original chrono PR using private APIs
@@ -146,8 +387,8 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d = NaiveDate::from_ymd(2019, 3, 31);
-    /// assert_eq!(d.end_of_year().unwrap(), NaiveDate::from_ymd(2019, 12, 31));
+    /// let d = NaiveDate::from_ymd_opt(2019, 3, 31).unwrap();
+    /// assert_eq!(d.end_of_year().unwrap(), NaiveDate::from_ymd_opt(2019, 12, 31).unwrap());
     #[inline]
     fn end_of_year(&self) -> Option<Self> {
         // TODO: Original chrono PR using private APIs
@@ -163,8 +404,8 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d = NaiveDate::from_ymd(2019, 9, 13);
-    /// assert_eq!(d.start_of_month().unwrap(), NaiveDate::from_ymd(2019, 9, 1));
+    /// let d = NaiveDate::from_ymd_opt(2019, 9, 13).unwrap();
+    /// assert_eq!(d.start_of_month().unwrap(), NaiveDate::from_ymd_opt(2019, 9, 1).unwrap());
     #[inline]
     fn start_of_month(&self) -> Option<Self> {
         // TODO: Original chrono PR using private APIs
@@ -180,12 +421,12 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(1996, 2, 23);
-    /// assert_eq!(d1.end_of_month().unwrap(), NaiveDate::from_ymd(1996, 2, 29));
-    /// let d2 = NaiveDate::from_ymd(1993, 2, 1);
-    /// assert_eq!(d2.end_of_month().unwrap(), NaiveDate::from_ymd(1993, 2, 28));
-    /// let d3 = NaiveDate::from_ymd(2000, 1, 1);
-    /// assert_eq!(d3.end_of_month().unwrap(), NaiveDate::from_ymd(2000, 1, 31));
+    /// let d1 = NaiveDate::from_ymd_opt(1996, 2, 23).unwrap();
+    /// assert_eq!(d1.end_of_month().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 29).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(1993, 2, 1).unwrap();
+    /// assert_eq!(d2.end_of_month().unwrap(), NaiveDate::from_ymd_opt(1993, 2, 28).unwrap());
+    /// let d3 = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+    /// assert_eq!(d3.end_of_month().unwrap(), NaiveDate::from_ymd_opt(2000, 1, 31).unwrap());
     #[inline]
     fn end_of_month(&self) -> Option<Self> {
         self.with_day(self.last_day_of_month())
@@ -200,17 +441,17 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(2020, 1, 2);
-    /// assert_eq!(d1.start_of_iso8601_week().unwrap(), NaiveDate::from_ymd(2019, 12, 30));
-    /// let d2 = NaiveDate::from_ymd(2019, 12, 29);
-    /// assert_eq!(d2.start_of_iso8601_week().unwrap(), NaiveDate::from_ymd(2019, 12, 23));
-    /// let d3 = NaiveDate::from_ymd(1992, 2, 29);
-    /// assert_eq!(d3.start_of_iso8601_week().unwrap(), NaiveDate::from_ymd(1992, 2, 24));
+    /// let d1 = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+    /// assert_eq!(d1.start_of_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2019, 12, 30).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(2019, 12, 29).unwrap();
+    /// assert_eq!(d2.start_of_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2019, 12, 23).unwrap());
+    /// let d3 = NaiveDate::from_ymd_opt(1992, 2, 29).unwrap();
+    /// assert_eq!(d3.start_of_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1992, 2, 24).unwrap());
     fn start_of_iso8601_week(&self) -> Option<Self> {
         // TODO: Original chrono PR using private APIs
         // let days = self.of().weekday().num_days_from_monday() as i64;
         let days = self.weekday().num_days_from_monday() as i64;
-        self.checked_sub_signed(OldDuration::days(days))
+        self.checked_sub_signed(TimeDelta::days(days))
     }
 
     /// Returns the end of the week for the current date. Uses the ISO 8601 standard for calculating
@@ -222,18 +463,18 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(2020, 1, 2);
-    /// assert_eq!(d1.end_of_iso8601_week().unwrap(), NaiveDate::from_ymd(2020, 1, 5));
-    /// let d2 = NaiveDate::from_ymd(2019, 12, 29);
-    /// assert_eq!(d2.end_of_iso8601_week().unwrap(), NaiveDate::from_ymd(2019, 12, 29));
-    /// let d3 = NaiveDate::from_ymd(1992, 2, 29);
-    /// assert_eq!(d3.end_of_iso8601_week().unwrap(), NaiveDate::from_ymd(1992, 3, 1));
+    /// let d1 = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+    /// assert_eq!(d1.end_of_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2020, 1, 5).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(2019, 12, 29).unwrap();
+    /// assert_eq!(d2.end_of_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2019, 12, 29).unwrap());
+    /// let d3 = NaiveDate::from_ymd_opt(1992, 2, 29).unwrap();
+    /// assert_eq!(d3.end_of_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1992, 3, 1).unwrap());
     fn end_of_iso8601_week(&self) -> Option<Self> {
         // TODO: Original chrono PR using private APIs
         // let days = 6 - self.of().weekday().num_days_from_monday() as i64;
         let max_days = 6;
         let days = max_days - self.weekday().num_days_from_monday() as i64;
-        self.checked_add_signed(OldDuration::days(days))
+        self.checked_add_signed(TimeDelta::days(days))
     }
 
     /// Returns the start of preceding year relative to the current date
@@ -244,8 +485,8 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d = NaiveDate::from_ymd(2019, 3, 31);
-    /// assert_eq!(d.start_of_pred_year().unwrap(), NaiveDate::from_ymd(2018, 1, 1));
+    /// let d = NaiveDate::from_ymd_opt(2019, 3, 31).unwrap();
+    /// assert_eq!(d.start_of_pred_year().unwrap(), NaiveDate::from_ymd_opt(2018, 1, 1).unwrap());
     #[inline]
     fn start_of_pred_year(&self) -> Option<Self> {
         let prev_year = self.year() - 1;
@@ -264,8 +505,8 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d = NaiveDate::from_ymd(2019, 3, 31);
-    /// assert_eq!(d.end_of_pred_year().unwrap(), NaiveDate::from_ymd(2018, 12, 31));
+    /// let d = NaiveDate::from_ymd_opt(2019, 3, 31).unwrap();
+    /// assert_eq!(d.end_of_pred_year().unwrap(), NaiveDate::from_ymd_opt(2018, 12, 31).unwrap());
     #[inline]
     fn end_of_pred_year(&self) -> Option<Self> {
         let prev_year = self.year() - 1;
@@ -284,10 +525,10 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(2019, 1, 4);
-    /// assert_eq!(d1.start_of_pred_month().unwrap(), NaiveDate::from_ymd(2018, 12, 1));
-    /// let d2 = NaiveDate::from_ymd(1999, 11, 17);
-    /// assert_eq!(d2.start_of_pred_month().unwrap(), NaiveDate::from_ymd(1999, 10, 1));
+    /// let d1 = NaiveDate::from_ymd_opt(2019, 1, 4).unwrap();
+    /// assert_eq!(d1.start_of_pred_month().unwrap(), NaiveDate::from_ymd_opt(2018, 12, 1).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(1999, 11, 17).unwrap();
+    /// assert_eq!(d2.start_of_pred_month().unwrap(), NaiveDate::from_ymd_opt(1999, 10, 1).unwrap());
     fn start_of_pred_month(&self) -> Option<Self> {
         // TODO: Original chrono PR using private APIs
         // let mut month = self.month() - 1;
@@ -317,12 +558,12 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(2019, 1, 4);
-    /// assert_eq!(d1.end_of_pred_month().unwrap(), NaiveDate::from_ymd(2018, 12, 31));
-    /// let d2 = NaiveDate::from_ymd(1999, 10, 17);
-    /// assert_eq!(d2.end_of_pred_month().unwrap(), NaiveDate::from_ymd(1999, 9, 30));
-    /// let d3 = NaiveDate::from_ymd(1996, 3, 1);
-    /// assert_eq!(d3.end_of_pred_month().unwrap(), NaiveDate::from_ymd(1996, 2, 29));
+    /// let d1 = NaiveDate::from_ymd_opt(2019, 1, 4).unwrap();
+    /// assert_eq!(d1.end_of_pred_month().unwrap(), NaiveDate::from_ymd_opt(2018, 12, 31).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(1999, 10, 17).unwrap();
+    /// assert_eq!(d2.end_of_pred_month().unwrap(), NaiveDate::from_ymd_opt(1999, 9, 30).unwrap());
+    /// let d3 = NaiveDate::from_ymd_opt(1996, 3, 1).unwrap();
+    /// assert_eq!(d3.end_of_pred_month().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 29).unwrap());
     fn end_of_pred_month(&self) -> Option<Self> {
         match self.start_of_pred_month() {
             Some(pred_start) => pred_start.with_day(pred_start.last_day_of_month()),
@@ -339,15 +580,15 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(2019, 1, 4);
-    /// assert_eq!(d1.start_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd(2018, 12, 24));
-    /// let d2 = NaiveDate::from_ymd(1999, 10, 17);
-    /// assert_eq!(d2.start_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd(1999, 10, 4));
-    /// let d3 = NaiveDate::from_ymd(1996, 3, 1);
-    /// assert_eq!(d3.start_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd(1996, 2, 19));
+    /// let d1 = NaiveDate::from_ymd_opt(2019, 1, 4).unwrap();
+    /// assert_eq!(d1.start_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2018, 12, 24).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(1999, 10, 17).unwrap();
+    /// assert_eq!(d2.start_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1999, 10, 4).unwrap());
+    /// let d3 = NaiveDate::from_ymd_opt(1996, 3, 1).unwrap();
+    /// assert_eq!(d3.start_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 19).unwrap());
     fn start_of_pred_iso8601_week(&self) -> Option<Self> {
         match self.start_of_iso8601_week() {
-            Some(week_start) => Some(week_start - OldDuration::days(7)),
+            Some(week_start) => week_start.checked_sub_signed(TimeDelta::days(7)),
             None => None,
         }
     }
@@ -361,15 +602,15 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(2019, 1, 4);
-    /// assert_eq!(d1.end_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd(2018, 12, 30));
-    /// let d2 = NaiveDate::from_ymd(1999, 10, 17);
-    /// assert_eq!(d2.end_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd(1999, 10, 10));
-    /// let d3 = NaiveDate::from_ymd(1996, 3, 1);
-    /// assert_eq!(d3.end_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd(1996, 2, 25));
+    /// let d1 = NaiveDate::from_ymd_opt(2019, 1, 4).unwrap();
+    /// assert_eq!(d1.end_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2018, 12, 30).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(1999, 10, 17).unwrap();
+    /// assert_eq!(d2.end_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1999, 10, 10).unwrap());
+    /// let d3 = NaiveDate::from_ymd_opt(1996, 3, 1).unwrap();
+    /// assert_eq!(d3.end_of_pred_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 25).unwrap());
     fn end_of_pred_iso8601_week(&self) -> Option<Self> {
         match self.start_of_iso8601_week() {
-            Some(week_start) => Some(week_start - OldDuration::days(1)),
+            Some(week_start) => week_start.checked_sub_signed(TimeDelta::days(1)),
             None => None,
         }
     }
@@ -382,8 +623,8 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d = NaiveDate::from_ymd(2019, 3, 31);
-    /// assert_eq!(d.start_of_succ_year().unwrap(), NaiveDate::from_ymd(2020, 1, 1));
+    /// let d = NaiveDate::from_ymd_opt(2019, 3, 31).unwrap();
+    /// assert_eq!(d.start_of_succ_year().unwrap(), NaiveDate::from_ymd_opt(2020, 1, 1).unwrap());
     #[inline]
     fn start_of_succ_year(&self) -> Option<Self> {
         let nxt_year = self.year() + 1;
@@ -401,8 +642,8 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d = NaiveDate::from_ymd(2019, 3, 31);
-    /// assert_eq!(d.end_of_succ_year().unwrap(), NaiveDate::from_ymd(2020, 12, 31));
+    /// let d = NaiveDate::from_ymd_opt(2019, 3, 31).unwrap();
+    /// assert_eq!(d.end_of_succ_year().unwrap(), NaiveDate::from_ymd_opt(2020, 12, 31).unwrap());
     #[inline]
     fn end_of_succ_year(&self) -> Option<Self> {
         let nxt_year = self.year() + 1;
@@ -420,10 +661,10 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(2019, 12, 12);
-    /// assert_eq!(d1.start_of_succ_month().unwrap(), NaiveDate::from_ymd(2020, 1, 1));
-    /// let d2 = NaiveDate::from_ymd(1999, 2, 28);
-    /// assert_eq!(d2.start_of_succ_month().unwrap(), NaiveDate::from_ymd(1999, 3, 1));
+    /// let d1 = NaiveDate::from_ymd_opt(2019, 12, 12).unwrap();
+    /// assert_eq!(d1.start_of_succ_month().unwrap(), NaiveDate::from_ymd_opt(2020, 1, 1).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(1999, 2, 28).unwrap();
+    /// assert_eq!(d2.start_of_succ_month().unwrap(), NaiveDate::from_ymd_opt(1999, 3, 1).unwrap());
     fn start_of_succ_month(&self) -> Option<Self> {
         // TODO: Original chrono PR using private APIs
         // let mut month = self.month() + 1;
@@ -453,12 +694,12 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(2019, 1, 4);
-    /// assert_eq!(d1.end_of_pred_month().unwrap(), NaiveDate::from_ymd(2018, 12, 31));
-    /// let d2 = NaiveDate::from_ymd(1999, 10, 17);
-    /// assert_eq!(d2.end_of_pred_month().unwrap(), NaiveDate::from_ymd(1999, 9, 30));
-    /// let d3 = NaiveDate::from_ymd(1996, 3, 1);
-    /// assert_eq!(d3.end_of_pred_month().unwrap(), NaiveDate::from_ymd(1996, 2, 29));
+    /// let d1 = NaiveDate::from_ymd_opt(2019, 1, 4).unwrap();
+    /// assert_eq!(d1.end_of_pred_month().unwrap(), NaiveDate::from_ymd_opt(2018, 12, 31).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(1999, 10, 17).unwrap();
+    /// assert_eq!(d2.end_of_pred_month().unwrap(), NaiveDate::from_ymd_opt(1999, 9, 30).unwrap());
+    /// let d3 = NaiveDate::from_ymd_opt(1996, 3, 1).unwrap();
+    /// assert_eq!(d3.end_of_pred_month().unwrap(), NaiveDate::from_ymd_opt(1996, 2, 29).unwrap());
     fn end_of_succ_month(&self) -> Option<Self> {
         match self.start_of_succ_month() {
             Some(succ_start) => succ_start.with_day(succ_start.last_day_of_month()),
@@ -475,15 +716,15 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(2020, 1, 4);
-    /// assert_eq!(d1.start_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd(2020, 1, 6));
-    /// let d2 = NaiveDate::from_ymd(2017, 12, 28);
-    /// assert_eq!(d2.start_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd(2018, 1, 1));
-    /// let d3 = NaiveDate::from_ymd(1996, 2, 26);
-    /// assert_eq!(d3.start_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd(1996, 3, 4));
+    /// let d1 = NaiveDate::from_ymd_opt(2020, 1, 4).unwrap();
+    /// assert_eq!(d1.start_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2020, 1, 6).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(2017, 12, 28).unwrap();
+    /// assert_eq!(d2.start_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2018, 1, 1).unwrap());
+    /// let d3 = NaiveDate::from_ymd_opt(1996, 2, 26).unwrap();
+    /// assert_eq!(d3.start_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(1996, 3, 4).unwrap());
     fn start_of_succ_iso8601_week(&self) -> Option<Self> {
         match self.start_of_iso8601_week() {
-            Some(week_start) => Some(week_start + OldDuration::days(7)),
+            Some(week_start) => week_start.checked_add_signed(TimeDelta::days(7)),
             None => None,
         }
     }
@@ -497,16 +738,356 @@ impl DateTransitions for NaiveDate {
     /// use chrono::NaiveDate;
     /// use chrono_utilities::naive::DateTransitions;
     ///
-    /// let d1 = NaiveDate::from_ymd(2019, 1, 4);
-    /// assert_eq!(d1.end_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd(2019, 1, 13));
-    /// let d2 = NaiveDate::from_ymd(2004, 2, 20);
-    /// assert_eq!(d2.end_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd(2004, 2, 29));
-    /// let d3 = NaiveDate::from_ymd(2005, 12, 20);
-    /// assert_eq!(d3.end_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd(2006, 1, 1));
+    /// let d1 = NaiveDate::from_ymd_opt(2019, 1, 4).unwrap();
+    /// assert_eq!(d1.end_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2019, 1, 13).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(2004, 2, 20).unwrap();
+    /// assert_eq!(d2.end_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2004, 2, 29).unwrap());
+    /// let d3 = NaiveDate::from_ymd_opt(2005, 12, 20).unwrap();
+    /// assert_eq!(d3.end_of_succ_iso8601_week().unwrap(), NaiveDate::from_ymd_opt(2006, 1, 1).unwrap());
     fn end_of_succ_iso8601_week(&self) -> Option<Self> {
         match self.start_of_succ_iso8601_week() {
-            Some(week_start) => Some(week_start + OldDuration::days(6)),
+            Some(week_start) => week_start.checked_add_signed(TimeDelta::days(6)),
             None => None,
         }
     }
+
+    /// Returns true if both dates fall within the same ISO 8601 week, which may span two years
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DateTransitions;
+    ///
+    /// let d1 = NaiveDate::from_ymd_opt(2019, 12, 30).unwrap();
+    /// let d2 = NaiveDate::from_ymd_opt(2020, 1, 5).unwrap();
+    /// assert!(d1.is_same_iso8601_week_as(&d2));
+    /// assert!(!d2.is_same_iso8601_week_as(&NaiveDate::from_ymd_opt(2020, 1, 6).unwrap()));
+    #[inline]
+    fn is_same_iso8601_week_as(&self, other: &Self) -> bool {
+        self.iso_week() == other.iso_week()
+    }
+
+    /// Returns true if both dates fall within the same month of the same year
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DateTransitions;
+    ///
+    /// let d1 = NaiveDate::from_ymd_opt(2020, 3, 1).unwrap();
+    /// assert!(d1.is_same_month_as(&NaiveDate::from_ymd_opt(2020, 3, 31).unwrap()));
+    /// assert!(!d1.is_same_month_as(&NaiveDate::from_ymd_opt(2021, 3, 1).unwrap()));
+    #[inline]
+    fn is_same_month_as(&self, other: &Self) -> bool {
+        self.year() == other.year() && self.month() == other.month()
+    }
+
+    /// Returns true if both dates fall within the same quarter of the same year
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DateTransitions;
+    ///
+    /// let d1 = NaiveDate::from_ymd_opt(2020, 4, 1).unwrap();
+    /// assert!(d1.is_same_quarter_as(&NaiveDate::from_ymd_opt(2020, 6, 30).unwrap()));
+    /// assert!(!d1.is_same_quarter_as(&NaiveDate::from_ymd_opt(2020, 3, 31).unwrap()));
+    #[inline]
+    fn is_same_quarter_as(&self, other: &Self) -> bool {
+        self.year() == other.year() && self.quarter_of_year() == other.quarter_of_year()
+    }
+
+    /// Returns true if both dates fall within the same year
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DateTransitions;
+    ///
+    /// let d1 = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+    /// assert!(d1.is_same_year_as(&NaiveDate::from_ymd_opt(2020, 12, 31).unwrap()));
+    /// assert!(!d1.is_same_year_as(&NaiveDate::from_ymd_opt(2019, 12, 31).unwrap()));
+    #[inline]
+    fn is_same_year_as(&self, other: &Self) -> bool {
+        self.year() == other.year()
+    }
+}
+
+impl DatePredicates for NaiveDate {
+    /// Returns true if the date falls on a Saturday or Sunday
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// let d1 = NaiveDate::from_ymd_opt(2020, 5, 2).unwrap();
+    /// assert!(d1.is_weekend());
+    /// let d2 = NaiveDate::from_ymd_opt(2020, 5, 1).unwrap();
+    /// assert!(!d2.is_weekend());
+    #[inline]
+    fn is_weekend(&self) -> bool {
+        self.is_weekend_in(WeekendSet::SATURDAY_SUNDAY)
+    }
+
+    /// Returns true if the date falls from Monday to Friday
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 5, 1).unwrap();
+    /// assert!(d.is_weekday());
+    #[inline]
+    fn is_weekday(&self) -> bool {
+        !self.is_weekend()
+    }
+
+    /// Returns true if the date falls on a day of the given weekend
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::WeekendSet;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 5, 1).unwrap();
+    /// assert!(d.is_weekend_in(WeekendSet::FRIDAY_SATURDAY));
+    /// assert!(!d.is_weekday_in(WeekendSet::FRIDAY_SATURDAY));
+    #[inline]
+    fn is_weekend_in(&self, weekend: WeekendSet) -> bool {
+        weekend.is_weekend(*self)
+    }
+
+    /// Returns true if the date falls on a day outside of the given weekend
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::WeekendSet;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 5, 3).unwrap();
+    /// assert!(d.is_weekday_in(WeekendSet::FRIDAY_SATURDAY));
+    /// assert!(!d.is_weekday_in(WeekendSet::SATURDAY_SUNDAY));
+    #[inline]
+    fn is_weekday_in(&self, weekend: WeekendSet) -> bool {
+        !weekend.is_weekend(*self)
+    }
+
+    /// Returns true if the date is the first day of its month
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// assert!(NaiveDate::from_ymd_opt(2020, 3, 1).unwrap().is_first_day_of_month());
+    /// assert!(!NaiveDate::from_ymd_opt(2020, 3, 2).unwrap().is_first_day_of_month());
+    #[inline]
+    fn is_first_day_of_month(&self) -> bool {
+        self.day() == 1
+    }
+
+    /// Returns true if the date is the last day of its month
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// assert!(NaiveDate::from_ymd_opt(1996, 2, 29).unwrap().is_last_day_of_month());
+    /// assert!(!NaiveDate::from_ymd_opt(1996, 2, 28).unwrap().is_last_day_of_month());
+    /// assert!(NaiveDate::from_ymd_opt(1997, 2, 28).unwrap().is_last_day_of_month());
+    #[inline]
+    fn is_last_day_of_month(&self) -> bool {
+        self.day() == self.last_day_of_month()
+    }
+
+    /// Returns true if the date is the first day of its quarter
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// assert!(NaiveDate::from_ymd_opt(2020, 4, 1).unwrap().is_first_day_of_quarter());
+    /// assert!(!NaiveDate::from_ymd_opt(2020, 5, 1).unwrap().is_first_day_of_quarter());
+    #[inline]
+    fn is_first_day_of_quarter(&self) -> bool {
+        self.day() == 1 && self.month0() % 3 == 0
+    }
+
+    /// Returns true if the date is the last day of its quarter
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// assert!(NaiveDate::from_ymd_opt(2020, 9, 30).unwrap().is_last_day_of_quarter());
+    /// assert!(!NaiveDate::from_ymd_opt(2020, 8, 31).unwrap().is_last_day_of_quarter());
+    #[inline]
+    fn is_last_day_of_quarter(&self) -> bool {
+        self.month() % 3 == 0 && self.is_last_day_of_month()
+    }
+
+    /// Returns true if the date is the first day of its year
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// assert!(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap().is_first_day_of_year());
+    /// assert!(!NaiveDate::from_ymd_opt(2020, 2, 1).unwrap().is_first_day_of_year());
+    #[inline]
+    fn is_first_day_of_year(&self) -> bool {
+        self.ordinal() == 1
+    }
+
+    /// Returns true if the date is the last day of its year
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// assert!(NaiveDate::from_ymd_opt(2020, 12, 31).unwrap().is_last_day_of_year());
+    /// assert!(!NaiveDate::from_ymd_opt(2020, 12, 30).unwrap().is_last_day_of_year());
+    #[inline]
+    fn is_last_day_of_year(&self) -> bool {
+        self.month() == 12 && self.day() == 31
+    }
+
+    /// Returns true if the date is a Monday, the first day of its ISO 8601 week
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// assert!(NaiveDate::from_ymd_opt(2020, 1, 6).unwrap().is_start_of_iso8601_week());
+    /// assert!(NaiveDate::from_ymd_opt(2020, 1, 5).unwrap().is_end_of_iso8601_week());
+    #[inline]
+    fn is_start_of_iso8601_week(&self) -> bool {
+        self.weekday() == Weekday::Mon
+    }
+
+    /// Returns true if the date is a Sunday, the last day of its ISO 8601 week
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::DatePredicates;
+    ///
+    /// assert!(NaiveDate::from_ymd_opt(2020, 1, 12).unwrap().is_end_of_iso8601_week());
+    /// assert!(!NaiveDate::from_ymd_opt(2020, 1, 13).unwrap().is_end_of_iso8601_week());
+    #[inline]
+    fn is_end_of_iso8601_week(&self) -> bool {
+        self.weekday() == Weekday::Sun
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DateTransitions;
+    use chrono::NaiveDate;
+
+    /// A date implementing only the required methods of `DateTransitions`
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Required(NaiveDate);
+
+    macro_rules! delegate {
+        ($($method:ident),*) => {
+            $(
+                fn $method(&self) -> Option<Self> {
+                    self.0.$method().map(Required)
+                }
+            )*
+        };
+    }
+
+    impl DateTransitions for Required {
+        fn is_leap_year(&self) -> bool {
+            self.0.is_leap_year()
+        }
+
+        fn last_day_of_month(&self) -> u32 {
+            self.0.last_day_of_month()
+        }
+
+        delegate!(
+            start_of_year,
+            end_of_year,
+            start_of_month,
+            end_of_month,
+            start_of_iso8601_week,
+            end_of_iso8601_week,
+            start_of_pred_year,
+            end_of_pred_year,
+            start_of_pred_month,
+            end_of_pred_month,
+            start_of_pred_iso8601_week,
+            end_of_pred_iso8601_week,
+            start_of_succ_year,
+            end_of_succ_year,
+            start_of_succ_month,
+            end_of_succ_month,
+            start_of_succ_iso8601_week,
+            end_of_succ_iso8601_week
+        );
+    }
+
+    #[test]
+    fn test_default_methods_match_naive_date() {
+        let mut date = NaiveDate::from_ymd_opt(2019, 12, 1).unwrap();
+        while date < NaiveDate::from_ymd_opt(2021, 2, 1).unwrap() {
+            let day = Required(date);
+            assert_eq!(day.days_in_year(), date.days_in_year());
+            assert_eq!(day.days_in_quarter(), date.days_in_quarter());
+            for offset in [1, 6, 7, 31, 92].iter() {
+                let other = date + chrono::TimeDelta::days(*offset);
+                let other_day = Required(other);
+                assert_eq!(
+                    day.is_same_iso8601_week_as(&other_day),
+                    date.is_same_iso8601_week_as(&other)
+                );
+                assert_eq!(
+                    day.is_same_month_as(&other_day),
+                    date.is_same_month_as(&other)
+                );
+                assert_eq!(
+                    day.is_same_quarter_as(&other_day),
+                    date.is_same_quarter_as(&other)
+                );
+                assert_eq!(
+                    day.is_same_year_as(&other_day),
+                    date.is_same_year_as(&other)
+                );
+            }
+            date = date.succ_opt().unwrap();
+        }
+        let last = Required(NaiveDate::MAX);
+        assert!(last.is_same_month_as(&last));
+        assert!(last.is_same_quarter_as(&last));
+        assert_eq!(last.days_in_quarter(), 92);
+    }
 }
@@ -0,0 +1,341 @@
+//! Parsing of English phrases such as `next monday`, `end of next month` or `in 3 weeks` into
+//! dates relative to a reference date, and of durations such as `3 weeks 2 days` or `1y 6mo`
+use super::duration::CalendarDuration;
+use super::period::Unit;
+use chrono::{Datelike, NaiveDate, TimeDelta, Weekday};
+use std::error::Error;
+use std::fmt;
+
+/// Parses an English phrase into a date relative to the reference date. Letters may be of any
+/// case and words may be separated by any whitespace. The following phrases are understood,
+/// along with ISO 8601 dates such as `2020-01-31`:
+///
+/// - `today`, `tomorrow` and `yesterday`
+/// - `in 3 days`, `in a week`, `2 months ago`, with days, weeks, months, quarters and years
+/// - `monday` or `this monday` for the first Monday on or after the reference date, `next
+///   monday` for the first one after it and `last monday` for the last one before it
+/// - `next week`, `last month`, `this year`, moving the reference date by one period
+/// - `start of next month`, `beginning of the week`, `end of last quarter`, `end of year`
+///
+/// Months, quarters and years are added by clamping the day to the length of the resulting
+/// month, as [CalendarDuration](../duration/struct.CalendarDuration.html) does, and weeks are
+/// ISO 8601 weeks starting on Monday.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::natural::parse_date;
+///
+/// // A Wednesday
+/// let today = NaiveDate::from_ymd_opt(2020, 1, 15).unwrap();
+/// assert_eq!(parse_date("next Monday", today), Ok(NaiveDate::from_ymd_opt(2020, 1, 20).unwrap()));
+/// assert_eq!(parse_date("last wednesday", today), Ok(NaiveDate::from_ymd_opt(2020, 1, 8).unwrap()));
+/// assert_eq!(parse_date("end of next month", today), Ok(NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()));
+/// assert_eq!(parse_date("in 3 weeks", today), Ok(NaiveDate::from_ymd_opt(2020, 2, 5).unwrap()));
+/// assert_eq!(parse_date("a year ago", today), Ok(NaiveDate::from_ymd_opt(2019, 1, 15).unwrap()));
+/// assert!(parse_date("next blue moon", today).is_err());
+pub fn parse_date(phrase: &str, reference: NaiveDate) -> Result<NaiveDate, ParseNaturalError> {
+    let lowercase = phrase.to_ascii_lowercase();
+    let words: Vec<&str> = lowercase
+        .split_whitespace()
+        .filter(|word| *word != "the")
+        .collect();
+    let date = match words[..] {
+        ["today"] => Some(reference),
+        ["tomorrow"] => reference.succ_opt(),
+        ["yesterday"] => reference.pred_opt(),
+        ["in", amount, unit] => shift(reference, parse_amount(amount)?, parse_unit(unit)?),
+        [amount, unit, "ago"] => {
+            let amount = parse_amount(amount)?
+                .checked_neg()
+                .ok_or(ParseNaturalError { part: "amount" })?;
+            shift(reference, amount, parse_unit(unit)?)
+        }
+        ["start", "of", ref rest @ ..] | ["beginning", "of", ref rest @ ..] => {
+            let (date, unit) = parse_period(rest, reference)?;
+            unit.start_of(date)
+        }
+        ["end", "of", ref rest @ ..] => {
+            let (date, unit) = parse_period(rest, reference)?;
+            unit.period_of(date).map(|period| period.end())
+        }
+        [relative, word] if parse_relative(relative).is_some() => {
+            let relative = parse_relative(relative).unwrap_or(0);
+            match parse_weekday(word) {
+                Some(weekday) => weekday_relative_to(reference, weekday, relative),
+                None => shift(reference, relative, parse_unit(word)?),
+            }
+        }
+        [word] if parse_weekday(word).is_some() => {
+            parse_weekday(word).and_then(|weekday| weekday_relative_to(reference, weekday, 0))
+        }
+        _ => {
+            return phrase
+                .trim()
+                .parse()
+                .map_err(|_| ParseNaturalError { part: "phrase" })
+        }
+    };
+    date.ok_or(ParseNaturalError { part: "date" })
+}
+
+/// Parses an English duration made of amounts of years, quarters, months, weeks and days, such
+/// as `3 weeks 2 days`, `1y 6mo` or `2 years, 1 month and a day`, into a calendar duration
+/// which can be added to dates. Units may be written in full, in the singular or the plural, or
+/// abbreviated as `y`, `yr`, `q`, `mo`, `w`, `wk` and `d`. Amounts may be negative, and a
+/// quarter counts as 3 months.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::duration::CalendarDuration;
+/// use chrono_utilities::naive::natural::parse_duration;
+///
+/// assert_eq!(parse_duration("3 weeks 2 days"), Ok(CalendarDuration::new(0, 0, 3, 2)));
+/// let d = parse_duration("1y 6mo").unwrap();
+/// assert_eq!(d, CalendarDuration::new(1, 6, 0, 0));
+/// assert_eq!(d.add_to(NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()), NaiveDate::from_ymd_opt(2021, 7, 31));
+/// assert_eq!(parse_duration("a quarter and 1 day"), Ok(CalendarDuration::new(0, 3, 0, 1)));
+/// assert!(parse_duration("3 fortnights").is_err());
+pub fn parse_duration(s: &str) -> Result<CalendarDuration, ParseNaturalError> {
+    let lowercase = s.to_ascii_lowercase();
+    let mut rest = lowercase.as_str();
+    let mut totals = [0i32; 4];
+    let mut empty = true;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if let Some(after) = rest.strip_prefix("and ") {
+            if !empty {
+                rest = after.trim_start();
+            }
+        }
+        if rest.is_empty() && !empty {
+            break;
+        }
+        let number = rest
+            .char_indices()
+            .find(|(i, c)| !(c.is_ascii_digit() || (*i == 0 && (*c == '-' || *c == '+'))))
+            .map_or(rest.len(), |(i, _)| i);
+        let (amount, after) = if number > 0 {
+            (parse_amount(&rest[..number])?, &rest[number..])
+        } else {
+            let word = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            (parse_amount(&rest[..word])?, &rest[word..])
+        };
+        let after = after.trim_start();
+        let word = after
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(after.len());
+        let (index, factor) = match &after[..word] {
+            "y" | "yr" | "yrs" | "year" | "years" => (0, 1),
+            "q" | "quarter" | "quarters" => (1, 3),
+            "mo" | "mos" | "month" | "months" => (1, 1),
+            "w" | "wk" | "wks" | "week" | "weeks" => (2, 1),
+            "d" | "day" | "days" => (3, 1),
+            _ => return Err(ParseNaturalError { part: "unit" }),
+        };
+        totals[index] = amount
+            .checked_mul(factor)
+            .and_then(|amount| totals[index].checked_add(amount))
+            .ok_or(ParseNaturalError { part: "amount" })?;
+        empty = false;
+        rest = &after[word..];
+    }
+    Ok(CalendarDuration::new(
+        totals[0], totals[1], totals[2], totals[3],
+    ))
+}
+
+/// Parses the period of `start of` and `end of` phrases, such as `next month`, returning a date
+/// within the period along with its granularity
+fn parse_period(
+    words: &[&str],
+    reference: NaiveDate,
+) -> Result<(NaiveDate, Unit), ParseNaturalError> {
+    let (relative, unit) = match *words {
+        [unit] => (0, parse_unit(unit)?),
+        [relative, unit] => (
+            parse_relative(relative).ok_or(ParseNaturalError { part: "period" })?,
+            parse_unit(unit)?,
+        ),
+        _ => return Err(ParseNaturalError { part: "period" }),
+    };
+    let date = unit
+        .start_of(reference)
+        .and_then(|start| shift(start, relative, unit))
+        .ok_or(ParseNaturalError { part: "date" })?;
+    Ok((date, unit))
+}
+
+/// Returns the weekday closest to the reference date, on or after it for `this` (0), after it
+/// for `next` (1) and before it for `last` (-1)
+fn weekday_relative_to(reference: NaiveDate, weekday: Weekday, relative: i32) -> Option<NaiveDate> {
+    let ahead = (weekday.num_days_from_monday() as i64
+        - reference.weekday().num_days_from_monday() as i64)
+        .rem_euclid(7);
+    let days = match relative {
+        0 => ahead,
+        1 if ahead == 0 => 7,
+        1 => ahead,
+        _ if ahead == 0 => -7,
+        _ => ahead - 7,
+    };
+    reference.checked_add_signed(TimeDelta::days(days))
+}
+
+/// Moves the date by the given number of periods of the granularity
+fn shift(date: NaiveDate, amount: i32, unit: Unit) -> Option<NaiveDate> {
+    match unit {
+        Unit::Day => CalendarDuration::days(amount),
+        Unit::Week => CalendarDuration::weeks(amount),
+        Unit::Month => CalendarDuration::months(amount),
+        Unit::Quarter => CalendarDuration::months(amount.checked_mul(3)?),
+        Unit::Year => CalendarDuration::years(amount),
+    }
+    .add_to(date)
+}
+
+/// Parses `this`, `next` and `last` into the number of periods they move by
+fn parse_relative(word: &str) -> Option<i32> {
+    match word {
+        "this" => Some(0),
+        "next" => Some(1),
+        "last" | "previous" => Some(-1),
+        _ => None,
+    }
+}
+
+/// Parses an amount written with digits or as `a`, `an` or `one`
+fn parse_amount(word: &str) -> Result<i32, ParseNaturalError> {
+    match word {
+        "a" | "an" | "one" => Ok(1),
+        _ => word
+            .parse()
+            .map_err(|_| ParseNaturalError { part: "amount" }),
+    }
+}
+
+/// Parses a granularity, in the singular or the plural
+fn parse_unit(word: &str) -> Result<Unit, ParseNaturalError> {
+    match word.strip_suffix('s').unwrap_or(word) {
+        "day" => Ok(Unit::Day),
+        "week" => Ok(Unit::Week),
+        "month" => Ok(Unit::Month),
+        "quarter" => Ok(Unit::Quarter),
+        "year" => Ok(Unit::Year),
+        _ => Err(ParseNaturalError { part: "unit" }),
+    }
+}
+
+/// Parses the English name of a weekday
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word {
+        "monday" => Weekday::Mon,
+        "tuesday" => Weekday::Tue,
+        "wednesday" => Weekday::Wed,
+        "thursday" => Weekday::Thu,
+        "friday" => Weekday::Fri,
+        "saturday" => Weekday::Sat,
+        "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// Error returned when parsing a phrase which is not understood
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseNaturalError {
+    part: &'static str,
+}
+
+impl fmt::Display for ParseNaturalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid natural language {}", self.part)
+    }
+}
+
+impl Error for ParseNaturalError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_date, parse_duration};
+    use crate::naive::duration::CalendarDuration;
+    use chrono::NaiveDate;
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_phrases() {
+        // A Monday
+        let today = ymd(2020, 3, 30);
+        let cases = [
+            ("today", today),
+            ("  Tomorrow ", ymd(2020, 3, 31)),
+            ("yesterday", ymd(2020, 3, 29)),
+            ("monday", today),
+            ("this monday", today),
+            ("next monday", ymd(2020, 4, 6)),
+            ("last monday", ymd(2020, 3, 23)),
+            ("sunday", ymd(2020, 4, 5)),
+            ("last sunday", ymd(2020, 3, 29)),
+            ("next week", ymd(2020, 4, 6)),
+            ("last month", ymd(2020, 2, 29)),
+            ("in 2 days", ymd(2020, 4, 1)),
+            ("in 1 quarter", ymd(2020, 6, 30)),
+            ("3 years ago", ymd(2017, 3, 30)),
+            ("start of the week", today),
+            ("end of week", ymd(2020, 4, 5)),
+            ("beginning of next quarter", ymd(2020, 4, 1)),
+            ("end of last year", ymd(2019, 12, 31)),
+            ("end of this month", ymd(2020, 3, 31)),
+            ("2021-02-03", ymd(2021, 2, 3)),
+        ];
+        for (phrase, expected) in cases.iter() {
+            assert_eq!(parse_date(phrase, today), Ok(*expected), "{}", phrase);
+        }
+        for invalid in &[
+            "",
+            "in weeks",
+            "next",
+            "end of",
+            "end of next",
+            "3 fortnights ago",
+        ] {
+            assert!(parse_date(invalid, today).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_durations() {
+        let cases = [
+            ("1d", CalendarDuration::days(1)),
+            (
+                "2 Years, 3 months and 4 days",
+                CalendarDuration::new(2, 3, 0, 4),
+            ),
+            ("1y6mo2w", CalendarDuration::new(1, 6, 2, 0)),
+            ("-1 week 3 days", CalendarDuration::new(0, 0, -1, 3)),
+            ("1 month 1 month", CalendarDuration::months(2)),
+        ];
+        for (s, expected) in cases.iter() {
+            assert_eq!(parse_duration(s), Ok(*expected), "{}", s);
+        }
+        let invalid = [
+            "",
+            "and",
+            "3",
+            "days",
+            "3 days and",
+            "1 m",
+            "5 weeksx",
+            "an hour",
+        ];
+        for invalid in invalid.iter() {
+            assert!(parse_duration(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
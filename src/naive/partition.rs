@@ -0,0 +1,142 @@
+//! Naming of date partitions in data lakes, such as `y=2024/m=07/d=05` or `dt=2024-07-05`
+use super::period::{Unit, YearDivisions};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Layout of partition names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartitionScheme {
+    /// One nested directory per component, as in `y=2024/m=07/d=05`, `y=2024/q=3` or
+    /// `y=2024/w=05`
+    Nested,
+    /// A single `dt` directory, as in `dt=2024-07-05`, `dt=2024-07`, `dt=2024-Q3`, `dt=2024-W05`
+    /// or `dt=2024`
+    Dt,
+}
+
+/// Returns the name of the partition of the given granularity holding the date. Weeks are ISO
+/// 8601 weeks and are named after their week-numbering year.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::partition::{partition_key, PartitionScheme};
+/// use chrono_utilities::naive::period::Unit;
+///
+/// let d = NaiveDate::from_ymd_opt(2024, 7, 5).unwrap();
+/// assert_eq!(partition_key(d, Unit::Day, PartitionScheme::Nested), "y=2024/m=07/d=05");
+/// assert_eq!(partition_key(d, Unit::Month, PartitionScheme::Nested), "y=2024/m=07");
+/// assert_eq!(partition_key(d, Unit::Day, PartitionScheme::Dt), "dt=2024-07-05");
+/// assert_eq!(partition_key(d, Unit::Quarter, PartitionScheme::Dt), "dt=2024-Q3");
+/// let d = NaiveDate::from_ymd_opt(2021, 1, 2).unwrap();
+/// assert_eq!(partition_key(d, Unit::Week, PartitionScheme::Nested), "y=2020/w=53");
+pub fn partition_key(date: NaiveDate, unit: Unit, scheme: PartitionScheme) -> String {
+    let (year, quarter) = (date.year(), date.quarter_of_year());
+    let week = date.iso_week();
+    match scheme {
+        PartitionScheme::Nested => match unit {
+            Unit::Day => format!("y={:04}/m={:02}/d={:02}", year, date.month(), date.day()),
+            Unit::Week => format!("y={:04}/w={:02}", week.year(), week.week()),
+            Unit::Month => format!("y={:04}/m={:02}", year, date.month()),
+            Unit::Quarter => format!("y={:04}/q={}", year, quarter),
+            Unit::Year => format!("y={:04}", year),
+        },
+        PartitionScheme::Dt => match unit {
+            Unit::Day => format!("dt={:04}-{:02}-{:02}", year, date.month(), date.day()),
+            Unit::Week => format!("dt={:04}-W{:02}", week.year(), week.week()),
+            Unit::Month => format!("dt={:04}-{:02}", year, date.month()),
+            Unit::Quarter => format!("dt={:04}-Q{}", year, quarter),
+            Unit::Year => format!("dt={:04}", year),
+        },
+    }
+}
+
+/// Parses a partition name generated by [partition_key](fn.partition_key.html) and returns
+/// the first date of the partition along with its granularity. Returns `None` if the name is not
+/// in the canonical form of the scheme.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::partition::{parse_partition_key, PartitionScheme};
+/// use chrono_utilities::naive::period::Unit;
+///
+/// let nested = parse_partition_key("y=2024/m=07/d=05", PartitionScheme::Nested);
+/// assert_eq!(nested, Some((NaiveDate::from_ymd_opt(2024, 7, 5).unwrap(), Unit::Day)));
+/// let week = parse_partition_key("dt=2020-W53", PartitionScheme::Dt);
+/// assert_eq!(week, Some((NaiveDate::from_ymd_opt(2020, 12, 28).unwrap(), Unit::Week)));
+/// assert_eq!(parse_partition_key("y=2024/m=7", PartitionScheme::Nested), None);
+/// assert_eq!(parse_partition_key("dt=2024-02-30", PartitionScheme::Dt), None);
+pub fn parse_partition_key(key: &str, scheme: PartitionScheme) -> Option<(NaiveDate, Unit)> {
+    let (year, rest) = match scheme {
+        PartitionScheme::Nested => {
+            let mut segments = key.split('/');
+            let year = segments.next()?.strip_prefix("y=")?;
+            let rest: Vec<&str> = segments.collect();
+            (year, rest)
+        }
+        PartitionScheme::Dt => {
+            let value = key.strip_prefix("dt=")?;
+            // A leading minus sign belongs to the year
+            match value.get(1..)?.find('-') {
+                Some(index) => {
+                    let (year, rest) = value.split_at(index + 1);
+                    (year, rest[1..].split('-').collect())
+                }
+                None => (value, Vec::new()),
+            }
+        }
+    };
+    let year: i32 = year.parse().ok()?;
+    let parsed = match (scheme, rest.as_slice()) {
+        (_, []) => (NaiveDate::from_ymd_opt(year, 1, 1)?, Unit::Year),
+        (PartitionScheme::Nested, [month]) if month.starts_with("m=") => {
+            let month = month[2..].parse().ok()?;
+            (NaiveDate::from_ymd_opt(year, month, 1)?, Unit::Month)
+        }
+        (PartitionScheme::Nested, [month, day]) if month.starts_with("m=") => {
+            let day = day.strip_prefix("d=")?.parse().ok()?;
+            let month = month[2..].parse().ok()?;
+            (NaiveDate::from_ymd_opt(year, month, day)?, Unit::Day)
+        }
+        (PartitionScheme::Nested, [quarter]) if quarter.starts_with("q=") => {
+            let quarter = quarter[2..].parse::<u32>().ok()?;
+            (quarter_start(year, quarter)?, Unit::Quarter)
+        }
+        (PartitionScheme::Nested, [week]) if week.starts_with("w=") => {
+            let week = week[2..].parse().ok()?;
+            let start = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+            (start, Unit::Week)
+        }
+        (PartitionScheme::Dt, [quarter]) if quarter.starts_with('Q') => {
+            let quarter = quarter[1..].parse::<u32>().ok()?;
+            (quarter_start(year, quarter)?, Unit::Quarter)
+        }
+        (PartitionScheme::Dt, [week]) if week.starts_with('W') => {
+            let week = week[1..].parse().ok()?;
+            let start = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+            (start, Unit::Week)
+        }
+        (PartitionScheme::Dt, [month]) => {
+            let month = month.parse().ok()?;
+            (NaiveDate::from_ymd_opt(year, month, 1)?, Unit::Month)
+        }
+        (PartitionScheme::Dt, [month, day]) => {
+            let (month, day) = (month.parse().ok()?, day.parse().ok()?);
+            (NaiveDate::from_ymd_opt(year, month, day)?, Unit::Day)
+        }
+        _ => return None,
+    };
+    // Leniently parsed numbers, such as unpadded ones, are rejected by comparing the name to
+    // the canonical one
+    Some(parsed).filter(|(date, unit)| partition_key(*date, *unit, scheme) == key)
+}
+
+fn quarter_start(year: i32, quarter: u32) -> Option<NaiveDate> {
+    if (1..=4).contains(&quarter) {
+        NaiveDate::from_ymd_opt(year, quarter * 3 - 2, 1)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,199 @@
+//! Pay schedules dividing time into pay periods, and the pay dates of those periods
+use super::business::{HolidayCalendar, RollConvention};
+use super::range::DateRange;
+use super::{start_of_cycle, DateTransitions};
+use chrono::{Datelike, NaiveDate, TimeDelta};
+use std::iter::FusedIterator;
+
+/// How often employees are paid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PayFrequency {
+    /// Periods of seven days, one of which starts on the anchor date
+    Weekly(NaiveDate),
+    /// Periods of fourteen days, one of which starts on the anchor date
+    Biweekly(NaiveDate),
+    /// Periods from the 1st to the 15th and from the 16th to the end of every month
+    SemiMonthly,
+    /// Calendar months
+    Monthly,
+}
+
+/// A pay schedule made of pay periods of the given frequency. Pay falls `lag_days` after the end
+/// of each period, and is moved to a business day as per the roll convention when that day is
+/// not one. By default pay falls on the last day of the period, or on the business day before.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::business::HolidayCalendar;
+/// use chrono_utilities::naive::payroll::PaySchedule;
+///
+/// let cal = HolidayCalendar::new();
+/// let schedule = PaySchedule::semi_monthly();
+/// let period = schedule.period_containing(NaiveDate::from_ymd_opt(2020, 2, 20).unwrap()).unwrap();
+/// assert_eq!(period.start(), NaiveDate::from_ymd_opt(2020, 2, 16).unwrap());
+/// assert_eq!(period.end(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap());
+/// // February 29 is a Saturday
+/// assert_eq!(schedule.pay_date_for(&period, &cal), NaiveDate::from_ymd_opt(2020, 2, 28));
+///
+/// let anchor = NaiveDate::from_ymd_opt(2020, 1, 6).unwrap();
+/// let schedule = PaySchedule::biweekly(anchor).lag(5);
+/// let periods: Vec<_> = schedule.periods(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()).take(2).collect();
+/// assert_eq!(periods[0].start(), NaiveDate::from_ymd_opt(2019, 12, 23).unwrap());
+/// assert_eq!(periods[1].start(), anchor);
+/// assert_eq!(schedule.pay_date_for(&periods[1], &cal), NaiveDate::from_ymd_opt(2020, 1, 24));
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PaySchedule {
+    /// How often employees are paid
+    pub frequency: PayFrequency,
+    /// The number of days from the end of a period to its pay date
+    pub lag_days: u32,
+    /// How pay dates falling on a non-business day are moved
+    pub roll: RollConvention,
+}
+
+impl PaySchedule {
+    /// Creates a schedule of the given frequency paying on the last day of each period
+    pub fn new(frequency: PayFrequency) -> Self {
+        PaySchedule {
+            frequency,
+            lag_days: 0,
+            roll: RollConvention::Preceding,
+        }
+    }
+
+    /// Creates a weekly schedule whose periods start on the weekday of the anchor date
+    #[inline]
+    pub fn weekly(anchor: NaiveDate) -> Self {
+        PaySchedule::new(PayFrequency::Weekly(anchor))
+    }
+
+    /// Creates a biweekly schedule with a period starting on the anchor date
+    #[inline]
+    pub fn biweekly(anchor: NaiveDate) -> Self {
+        PaySchedule::new(PayFrequency::Biweekly(anchor))
+    }
+
+    /// Creates a semi-monthly schedule with periods ending on the 15th and at month end
+    #[inline]
+    pub fn semi_monthly() -> Self {
+        PaySchedule::new(PayFrequency::SemiMonthly)
+    }
+
+    /// Creates a monthly schedule
+    #[inline]
+    pub fn monthly() -> Self {
+        PaySchedule::new(PayFrequency::Monthly)
+    }
+
+    /// Returns the schedule paying the given number of days after the end of each period
+    pub fn lag(mut self, days: u32) -> Self {
+        self.lag_days = days;
+        self
+    }
+
+    /// Returns the schedule moving pay dates to business days as per the convention
+    pub fn roll(mut self, convention: RollConvention) -> Self {
+        self.roll = convention;
+        self
+    }
+
+    /// Returns the pay period containing the date, or `None` if it is out of the range
+    /// supported by chrono
+    pub fn period_containing(&self, date: NaiveDate) -> Option<DateRange> {
+        let (start, end) = match self.frequency {
+            PayFrequency::Weekly(anchor) => fixed_period(date, anchor, 7)?,
+            PayFrequency::Biweekly(anchor) => fixed_period(date, anchor, 14)?,
+            PayFrequency::SemiMonthly if date.day() <= 15 => {
+                (date.with_day(1)?, date.with_day(15)?)
+            }
+            PayFrequency::SemiMonthly => (date.with_day(16)?, date.end_of_month()?),
+            PayFrequency::Monthly => (date.start_of_month()?, date.end_of_month()?),
+        };
+        DateRange::new(start, end)
+    }
+
+    /// Returns the pay date of the period, rolled to a business day of the calendar as per the
+    /// roll convention of the schedule
+    pub fn pay_date_for(
+        &self,
+        period: &DateRange,
+        calendar: &HolidayCalendar,
+    ) -> Option<NaiveDate> {
+        let date = period
+            .end()
+            .checked_add_signed(TimeDelta::days(self.lag_days as i64))?;
+        calendar.roll(date, self.roll)
+    }
+
+    /// Returns an iterator over successive pay periods, beginning with the one containing the
+    /// given date. The iterator only stops once the periods run past the range supported by
+    /// chrono.
+    pub fn periods(&self, from: NaiveDate) -> PayPeriods {
+        PayPeriods {
+            schedule: *self,
+            next: self.period_containing(from),
+        }
+    }
+}
+
+/// Returns the first and last dates of the period of the given length containing the date,
+/// periods being aligned on the anchor date
+fn fixed_period(date: NaiveDate, anchor: NaiveDate, length: i64) -> Option<(NaiveDate, NaiveDate)> {
+    let start = start_of_cycle(date, anchor, length)?;
+    Some((
+        start,
+        start.checked_add_signed(TimeDelta::days(length - 1))?,
+    ))
+}
+
+/// Endless iterator over successive pay periods. See
+/// [PaySchedule::periods](struct.PaySchedule.html#method.periods).
+#[derive(Debug, Clone)]
+pub struct PayPeriods {
+    schedule: PaySchedule,
+    next: Option<DateRange>,
+}
+
+impl Iterator for PayPeriods {
+    type Item = DateRange;
+
+    fn next(&mut self) -> Option<DateRange> {
+        let current = self.next?;
+        self.next = current
+            .end()
+            .succ_opt()
+            .and_then(|next| self.schedule.period_containing(next));
+        Some(current)
+    }
+}
+
+impl FusedIterator for PayPeriods {}
+
+#[cfg(test)]
+mod tests {
+    use super::PaySchedule;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_periods_are_contiguous() {
+        let anchor = NaiveDate::from_ymd_opt(2020, 3, 4).unwrap();
+        let from = NaiveDate::from_ymd_opt(2019, 12, 20).unwrap();
+        for schedule in &[
+            PaySchedule::weekly(anchor),
+            PaySchedule::biweekly(anchor),
+            PaySchedule::semi_monthly(),
+            PaySchedule::monthly(),
+        ] {
+            let periods: Vec<_> = schedule.periods(from).take(40).collect();
+            assert!(periods[0].contains(from));
+            for pair in periods.windows(2) {
+                assert_eq!(pair[0].end().succ_opt(), Some(pair[1].start()));
+            }
+            for period in &periods {
+                assert_eq!(schedule.period_containing(period.end()), Some(*period));
+            }
+        }
+    }
+}
@@ -0,0 +1,1494 @@
+//! Calendar periods such as weeks, months, quarters and years
+use super::epoch::EpochDay;
+use super::key::PeriodKey;
+use super::range::{DateRange, DayStep};
+use super::{days_in_month, shift_months, DateTransitions};
+use chrono::{Datelike, IsoWeek, Months, NaiveDate, TimeDelta, Weekday};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+/// Granularity of a calendar period. Weeks follow the ISO 8601 standard and start on Monday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Unit {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl Unit {
+    /// Returns the first date of the period of this granularity containing the given date. The
+    /// returned date identifies the period and can be used as its key.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::Unit;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 8, 13).unwrap();
+    /// assert_eq!(Unit::Day.start_of(d).unwrap(), d);
+    /// assert_eq!(Unit::Week.start_of(d).unwrap(), NaiveDate::from_ymd_opt(2020, 8, 10).unwrap());
+    /// assert_eq!(Unit::Month.start_of(d).unwrap(), NaiveDate::from_ymd_opt(2020, 8, 1).unwrap());
+    /// assert_eq!(Unit::Quarter.start_of(d).unwrap(), NaiveDate::from_ymd_opt(2020, 7, 1).unwrap());
+    /// assert_eq!(Unit::Year.start_of(d).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 1).unwrap());
+    pub fn start_of(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Unit::Day => Some(date),
+            Unit::Week => date.start_of_iso8601_week(),
+            Unit::Month => date.start_of_month(),
+            Unit::Quarter => NaiveDate::from_ymd_opt(date.year(), date.month0() / 3 * 3 + 1, 1),
+            Unit::Year => date.start_of_year(),
+        }
+    }
+
+    /// Returns the first date of the period following the one containing the given date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::Unit;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 12, 31).unwrap();
+    /// assert_eq!(Unit::Day.start_of_succ(d).unwrap(), NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
+    /// assert_eq!(Unit::Week.start_of_succ(d).unwrap(), NaiveDate::from_ymd_opt(2021, 1, 4).unwrap());
+    /// assert_eq!(Unit::Quarter.start_of_succ(d).unwrap(), NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
+    pub fn start_of_succ(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Unit::Day => date.succ_opt(),
+            Unit::Week => date.start_of_succ_iso8601_week(),
+            Unit::Month => date.start_of_succ_month(),
+            Unit::Quarter => shift_months(self.start_of(date)?, 3),
+            Unit::Year => date.start_of_succ_year(),
+        }
+    }
+
+    /// Returns the period of this granularity containing the given date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::Unit;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 8, 13).unwrap();
+    /// let quarter = Unit::Quarter.period_of(d).unwrap();
+    /// assert_eq!(quarter.start(), NaiveDate::from_ymd_opt(2020, 7, 1).unwrap());
+    /// assert_eq!(quarter.end(), NaiveDate::from_ymd_opt(2020, 9, 30).unwrap());
+    pub fn period_of(&self, date: NaiveDate) -> Option<DateRange> {
+        DateRange::new(self.start_of(date)?, self.start_of_succ(date)?.pred_opt()?)
+    }
+
+    /// Returns an endless iterator over the first dates of successive periods of this
+    /// granularity, beginning with the first one falling on or after the given date. The iterator
+    /// only stops once the dates run past the range supported by chrono.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::Unit;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 5, 20).unwrap();
+    /// let starts: Vec<NaiveDate> = Unit::Quarter.starts_from(d).take(3).collect();
+    /// assert_eq!(starts, vec![
+    ///     NaiveDate::from_ymd_opt(2020, 7, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 10, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+    /// ]);
+    pub fn starts_from(&self, date: NaiveDate) -> PeriodStarts {
+        let next = match self.start_of(date) {
+            Some(start) if start == date => Some(start),
+            _ => self.start_of_succ(date),
+        };
+        PeriodStarts { unit: *self, next }
+    }
+}
+
+/// Endless iterator over the first dates of successive periods. See
+/// [Unit::starts_from](enum.Unit.html#method.starts_from).
+#[derive(Debug, Clone)]
+pub struct PeriodStarts {
+    unit: Unit,
+    next: Option<NaiveDate>,
+}
+
+impl Iterator for PeriodStarts {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        let current = self.next?;
+        self.next = self.unit.start_of_succ(current);
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.next {
+            Some(_) => (1, None),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+impl FusedIterator for PeriodStarts {}
+
+/// Returns an endless iterator over the start of every ISO 8601 week falling on or after the
+/// given date
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::period::week_starts_from;
+///
+/// let d = NaiveDate::from_ymd_opt(2020, 2, 20).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2020, 3, 9).unwrap();
+/// let starts: Vec<NaiveDate> = week_starts_from(d).take_while(|s| *s <= end).collect();
+/// assert_eq!(starts, vec![
+///     NaiveDate::from_ymd_opt(2020, 2, 24).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 3, 2).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 3, 9).unwrap(),
+/// ]);
+#[inline]
+pub fn week_starts_from(date: NaiveDate) -> PeriodStarts {
+    Unit::Week.starts_from(date)
+}
+
+/// Returns an endless iterator over the start of every month falling on or after the given date
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::period::month_starts_from;
+///
+/// let d = NaiveDate::from_ymd_opt(2019, 12, 1).unwrap();
+/// let starts: Vec<NaiveDate> = month_starts_from(d).take(2).collect();
+/// assert_eq!(starts, vec![NaiveDate::from_ymd_opt(2019, 12, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()]);
+#[inline]
+pub fn month_starts_from(date: NaiveDate) -> PeriodStarts {
+    Unit::Month.starts_from(date)
+}
+
+/// Returns an endless iterator over the start of every quarter falling on or after the given
+/// date
+#[inline]
+pub fn quarter_starts_from(date: NaiveDate) -> PeriodStarts {
+    Unit::Quarter.starts_from(date)
+}
+
+/// Returns an endless iterator over the start of every year falling on or after the given date
+#[inline]
+pub fn year_starts_from(date: NaiveDate) -> PeriodStarts {
+    Unit::Year.starts_from(date)
+}
+
+/// Common set of methods returning the quarter and the half of the year of any date or datetime.
+/// The methods are not named `quarter` and `half` so as not to clash with the `quarter` method
+/// recent versions of chrono provide on `Datelike`.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::period::YearDivisions;
+///
+/// let d = NaiveDate::from_ymd_opt(2020, 8, 13).unwrap();
+/// assert_eq!(d.quarter_of_year(), 3);
+/// assert_eq!(d.half_of_year(), 2);
+/// let dt = NaiveDate::from_ymd_opt(2020, 6, 30).unwrap().and_hms_opt(23, 59, 59).unwrap();
+/// assert_eq!(dt.quarter_of_year(), 2);
+/// assert_eq!(dt.half_of_year(), 1);
+pub trait YearDivisions: Datelike {
+    /// Returns the quarter of the year, from 1 to 4
+    #[inline]
+    fn quarter_of_year(&self) -> u32 {
+        self.month0() / 3 + 1
+    }
+
+    /// Returns the half of the year, from 1 to 2
+    #[inline]
+    fn half_of_year(&self) -> u32 {
+        self.month0() / 6 + 1
+    }
+}
+
+impl<T: Datelike> YearDivisions for T {}
+
+/// Rules for numbering the weeks of a year. Weeks start on `first_day` and the first week of a
+/// year is the first one having at least `min_days_in_first_week` days in that year. Days before
+/// it belong to the last week of the preceding year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeekConvention {
+    first_day: Weekday,
+    min_days_in_first_week: u8,
+}
+
+impl Default for WeekConvention {
+    fn default() -> Self {
+        WeekConvention::ISO
+    }
+}
+
+impl WeekConvention {
+    /// ISO 8601 weeks starting on Monday, the first week being the one containing January 4
+    pub const ISO: WeekConvention = WeekConvention {
+        first_day: Weekday::Mon,
+        min_days_in_first_week: 4,
+    };
+
+    /// Weeks starting on Sunday, the first week being the one containing January 1
+    pub const US: WeekConvention = WeekConvention {
+        first_day: Weekday::Sun,
+        min_days_in_first_week: 1,
+    };
+
+    /// Creates a week convention. Returns `None` if `min_days_in_first_week` does not lie within
+    /// `1..=7`.
+    pub fn new(first_day: Weekday, min_days_in_first_week: u8) -> Option<Self> {
+        if (1..=7).contains(&min_days_in_first_week) {
+            Some(WeekConvention {
+                first_day,
+                min_days_in_first_week,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the day on which weeks start
+    #[inline]
+    pub fn first_day(&self) -> Weekday {
+        self.first_day
+    }
+
+    /// Returns the first date of the week containing the given date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::WeekConvention;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 8, 13).unwrap();
+    /// assert_eq!(WeekConvention::ISO.start_of_week(d).unwrap(), NaiveDate::from_ymd_opt(2020, 8, 10).unwrap());
+    /// assert_eq!(WeekConvention::US.start_of_week(d).unwrap(), NaiveDate::from_ymd_opt(2020, 8, 9).unwrap());
+    pub fn start_of_week(&self, date: NaiveDate) -> Option<NaiveDate> {
+        date.checked_sub_signed(TimeDelta::days(self.days_into_week(date) as i64))
+    }
+
+    /// Returns the week-numbering year and the week number, starting from 1, of the date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::WeekConvention;
+    ///
+    /// let d1 = NaiveDate::from_ymd_opt(2021, 1, 2).unwrap();
+    /// assert_eq!(WeekConvention::ISO.week_of_year(d1), Some((2020, 53)));
+    /// assert_eq!(WeekConvention::US.week_of_year(d1), Some((2021, 1)));
+    /// let d2 = NaiveDate::from_ymd_opt(2020, 12, 31).unwrap();
+    /// assert_eq!(WeekConvention::US.week_of_year(d2), Some((2021, 1)));
+    pub fn week_of_year(&self, date: NaiveDate) -> Option<(i32, u32)> {
+        let mut year = date.year();
+        let mut first = self.first_week_start(year)?;
+        if date < first {
+            year -= 1;
+            first = self.first_week_start(year)?;
+        } else if let Some(next) = self.first_week_start(year + 1).filter(|next| date >= *next) {
+            year += 1;
+            first = next;
+        }
+        let week = date.signed_duration_since(first).num_days() / 7 + 1;
+        Some((year, week as u32))
+    }
+
+    /// Returns the number of days between the start of the week and the date
+    #[inline]
+    pub(crate) fn days_into_week(&self, date: NaiveDate) -> u32 {
+        (7 + date.weekday().num_days_from_monday() - self.first_day.num_days_from_monday()) % 7
+    }
+
+    /// Returns the first date of the first week of the given year
+    fn first_week_start(&self, year: i32) -> Option<NaiveDate> {
+        let jan1 = NaiveDate::from_ymd_opt(year, 1, 1)?;
+        let before = self.days_into_week(jan1) as i64;
+        let days = if 7 - before >= self.min_days_in_first_week as i64 {
+            -before
+        } else {
+            7 - before
+        };
+        jan1.checked_add_signed(TimeDelta::days(days))
+    }
+}
+
+/// Rules for numbering the weeks of a month or a quarter
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::period::{WeekConvention, WeekNumbering};
+///
+/// // Saturday, 8 days into the month
+/// let d = NaiveDate::from_ymd_opt(2020, 8, 8).unwrap();
+/// assert_eq!(WeekNumbering::FromFirstDay.week_of_month(d), 2);
+/// // August 1 is a Saturday, so the first ISO week of the month starts on August 3
+/// assert_eq!(WeekNumbering::Aligned(WeekConvention::ISO).week_of_month(d), 1);
+/// assert_eq!(WeekNumbering::Aligned(WeekConvention::ISO).week_of_month(NaiveDate::from_ymd_opt(2020, 8, 2).unwrap()), 0);
+/// // The US week containing August 1 is the first one of the month
+/// assert_eq!(WeekNumbering::Aligned(WeekConvention::US).week_of_month(d), 2);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeekNumbering {
+    /// Weeks are counted from the first day of the period, whatever its weekday, so that week 1
+    /// spans days 1 to 7, week 2 days 8 to 14, and so on
+    FromFirstDay,
+    /// Weeks start on the first day of the week convention and the first week of the period is
+    /// the first one having at least as many days in the period as the convention requires in
+    /// a year. Days before it are numbered 0.
+    Aligned(WeekConvention),
+}
+
+impl WeekNumbering {
+    /// Returns the number of the week of the month the date falls in
+    #[inline]
+    pub fn week_of_month(&self, date: NaiveDate) -> u32 {
+        self.week_of(date, date.day0())
+    }
+
+    /// Returns the number of the week of the quarter the date falls in
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::{WeekConvention, WeekNumbering};
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 9, 30).unwrap();
+    /// assert_eq!(WeekNumbering::FromFirstDay.week_of_quarter(d), 14);
+    /// assert_eq!(WeekNumbering::Aligned(WeekConvention::ISO).week_of_quarter(d), 14);
+    pub fn week_of_quarter(&self, date: NaiveDate) -> u32 {
+        let first_month = date.month0() / 3 * 3 + 1;
+        let days_before = (first_month..date.month())
+            .map(|month| days_in_month(date.year(), month))
+            .sum::<u32>();
+        self.week_of(date, days_before + date.day0())
+    }
+
+    /// Returns the number of the week the date falls in, the given number of days after the
+    /// start of its period
+    fn week_of(&self, date: NaiveDate, days_into_period: u32) -> u32 {
+        match self {
+            WeekNumbering::FromFirstDay => days_into_period / 7 + 1,
+            WeekNumbering::Aligned(convention) => {
+                let offset = days_into_period as i64;
+                // Days between the start of the week and the start of the period
+                let before = (convention.days_into_week(date) as i64 - offset).rem_euclid(7);
+                let first = if 7 - before >= convention.min_days_in_first_week as i64 {
+                    -before
+                } else {
+                    7 - before
+                };
+                if offset < first {
+                    0
+                } else {
+                    ((offset - first) / 7 + 1) as u32
+                }
+            }
+        }
+    }
+}
+
+/// The cohort a date belongs to for a given granularity, such as the week or month of a signup.
+/// Cohorts are ordered chronologically and can be used as map keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cohort {
+    start: NaiveDate,
+    unit: Unit,
+}
+
+impl Cohort {
+    /// Returns the cohort of the given granularity the date belongs to. Weekly cohorts start on
+    /// the first day of the week as per the week convention, other granularities ignore it.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::{Cohort, Unit, WeekConvention};
+    /// use std::collections::BTreeMap;
+    ///
+    /// let signups = vec![
+    ///     NaiveDate::from_ymd_opt(2020, 8, 9).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 8, 10).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 8, 15).unwrap(),
+    /// ];
+    /// let mut sizes = BTreeMap::new();
+    /// for signup in signups {
+    ///     let cohort = Cohort::of(signup, Unit::Week, WeekConvention::US).unwrap();
+    ///     *sizes.entry(cohort).or_insert(0) += 1;
+    /// }
+    /// let first = sizes.keys().next().unwrap();
+    /// assert_eq!(first.start(), NaiveDate::from_ymd_opt(2020, 8, 9).unwrap());
+    /// assert_eq!(first.end(), Some(NaiveDate::from_ymd_opt(2020, 8, 15).unwrap()));
+    /// assert_eq!(sizes.values().collect::<Vec<_>>(), vec![&3]);
+    pub fn of(date: NaiveDate, unit: Unit, week: WeekConvention) -> Option<Self> {
+        let start = match unit {
+            Unit::Week => week.start_of_week(date)?,
+            _ => unit.start_of(date)?,
+        };
+        Some(Cohort { start, unit })
+    }
+
+    /// Returns the first date of the cohort
+    #[inline]
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// Returns the last date of the cohort
+    pub fn end(&self) -> Option<NaiveDate> {
+        match self.unit {
+            Unit::Week => self.start.checked_add_signed(TimeDelta::days(6)),
+            _ => self.unit.start_of_succ(self.start)?.pred_opt(),
+        }
+    }
+
+    /// Returns the granularity of the cohort
+    #[inline]
+    pub fn unit(&self) -> Unit {
+        self.unit
+    }
+
+    /// Returns the span of the cohort
+    #[inline]
+    pub fn range(&self) -> Option<DateRange> {
+        DateRange::new(self.start, self.end()?)
+    }
+}
+
+/// A calendar dividing time into periods of every [Unit](enum.Unit.html) granularity. Besides
+/// the [Gregorian](struct.Gregorian.html) calendar, fiscal calendars are provided in the
+/// [fiscal](../fiscal/index.html) module.
+pub trait PeriodCalendar {
+    /// Returns the period of the given granularity containing the date
+    fn period_of(&self, date: NaiveDate, unit: Unit) -> Option<DateRange>;
+
+    /// Returns the `horizon` periods of the given granularity following the one containing
+    /// `from`, in chronological order. Fewer periods are returned if the horizon runs past the
+    /// range supported by chrono.
+    fn forecast_periods(&self, from: NaiveDate, horizon: u32, unit: Unit) -> Vec<DateRange> {
+        let mut periods = Vec::with_capacity(horizon as usize);
+        let mut current = self.period_of(from, unit);
+        for _ in 0..horizon {
+            current = current
+                .and_then(|period| period.end().succ_opt())
+                .and_then(|next| self.period_of(next, unit));
+            match current {
+                Some(period) => periods.push(period),
+                None => break,
+            }
+        }
+        periods
+    }
+}
+
+/// The Gregorian calendar with calendar months, quarters and years, and ISO 8601 weeks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Gregorian;
+
+impl PeriodCalendar for Gregorian {
+    #[inline]
+    fn period_of(&self, date: NaiveDate, unit: Unit) -> Option<DateRange> {
+        unit.period_of(date)
+    }
+}
+
+/// Returns the `horizon` Gregorian periods of the given granularity following the one containing
+/// `from`. Use [PeriodCalendar::forecast_periods](trait.PeriodCalendar.html#method.forecast_periods)
+/// for fiscal calendars.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::period::{forecast_periods, Unit};
+///
+/// let periods = forecast_periods(NaiveDate::from_ymd_opt(2020, 11, 20).unwrap(), 18, Unit::Month);
+/// assert_eq!(periods.len(), 18);
+/// assert_eq!(periods[0].start(), NaiveDate::from_ymd_opt(2020, 12, 1).unwrap());
+/// assert_eq!(periods[17].end(), NaiveDate::from_ymd_opt(2022, 5, 31).unwrap());
+#[inline]
+pub fn forecast_periods(from: NaiveDate, horizon: u32, unit: Unit) -> Vec<DateRange> {
+    Gregorian.forecast_periods(from, horizon, unit)
+}
+
+/// A calendar period of a fixed kind, such as a [Week](struct.Week.html) or a
+/// [Month](struct.Month.html), spanning the dates from its start to its end. Periods are
+/// formatted and parsed in their canonical forms: `2024-W05`, `2024-03`, `2024-Q1` and `2024`.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::period::{Month, Period};
+///
+/// let feb = Month::new(2020, 2).unwrap();
+/// assert_eq!(feb.start(), NaiveDate::from_ymd_opt(2020, 2, 1).unwrap());
+/// assert_eq!(feb.end(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap());
+/// assert!(feb.contains(NaiveDate::from_ymd_opt(2020, 2, 14).unwrap()));
+/// assert_eq!(feb.succ(), Month::new(2020, 3));
+/// assert_eq!(feb.pred(), Month::new(2020, 1));
+/// assert_eq!(feb.iter_days().count(), 29);
+pub trait Period: Copy + Ord {
+    /// Returns the period containing the date. Returns `None` if the period is not entirely
+    /// within the range supported by chrono.
+    fn containing(date: NaiveDate) -> Option<Self>;
+
+    /// Returns the first date of the period
+    fn start(&self) -> NaiveDate;
+
+    /// Returns the last date of the period
+    fn end(&self) -> NaiveDate;
+
+    /// Returns the period following this one
+    fn succ(&self) -> Option<Self>;
+
+    /// Returns the period preceding this one
+    fn pred(&self) -> Option<Self>;
+
+    /// Returns an integer identifying the period. Keys of successive periods are consecutive
+    /// integers, so that keys sort like their periods and make compact storage or map keys.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono_utilities::naive::period::{Month, Period, Quarter, Week, Year};
+    ///
+    /// let month = Month::new(2024, 3).unwrap();
+    /// assert_eq!(month.to_key(), 2024 * 12 + 2);
+    /// assert_eq!(month.succ().unwrap().to_key(), month.to_key() + 1);
+    /// assert_eq!(Month::from_key(month.to_key()), Some(month));
+    /// assert_eq!(Quarter::new(2024, 1).unwrap().to_key(), 2024 * 4);
+    /// assert_eq!(Year::new(2024).unwrap().to_key(), 2024);
+    /// let week = Week::from_isoyw(2024, 5).unwrap();
+    /// assert_eq!(Week::from_key(week.to_key() + 1), week.succ());
+    fn to_key(&self) -> i32;
+
+    /// Returns the period identified by the key, as returned by [to_key](#tymethod.to_key)
+    fn from_key(key: i32) -> Option<Self>;
+
+    /// Returns true if the date falls within the period
+    #[inline]
+    fn contains(&self, date: NaiveDate) -> bool {
+        self.start() <= date && date <= self.end()
+    }
+
+    /// Returns the dates spanned by the period
+    #[inline]
+    fn range(&self) -> DateRange {
+        DateRange::new(self.start(), self.end()).unwrap()
+    }
+
+    /// Returns an iterator over every date of the period
+    #[inline]
+    fn iter_days(&self) -> DayStep {
+        self.range().iter()
+    }
+}
+
+/// An ISO 8601 week, running from Monday to Sunday
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Week {
+    start: NaiveDate,
+}
+
+impl Week {
+    /// Returns the given week of the given ISO 8601 week-numbering year
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::{Period, Week};
+    ///
+    /// let week = Week::from_isoyw(2020, 53).unwrap();
+    /// assert_eq!(week.start(), NaiveDate::from_ymd_opt(2020, 12, 28).unwrap());
+    /// assert_eq!(week.end(), NaiveDate::from_ymd_opt(2021, 1, 3).unwrap());
+    /// assert_eq!(week.succ(), Week::from_isoyw(2021, 1));
+    /// assert_eq!(Week::from_isoyw(2021, 53), None);
+    pub fn from_isoyw(year: i32, week: u32) -> Option<Self> {
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Sun)?;
+        let start = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+        Some(Week { start })
+    }
+
+    /// Returns the ISO 8601 week-numbering year of the week
+    #[inline]
+    pub fn year(&self) -> i32 {
+        self.start.iso_week().year()
+    }
+
+    /// Returns the number of the week within its year, starting from 1
+    #[inline]
+    pub fn week(&self) -> u32 {
+        self.start.iso_week().week()
+    }
+}
+
+impl Period for Week {
+    fn containing(date: NaiveDate) -> Option<Self> {
+        let start = date.start_of_iso8601_week()?;
+        start.checked_add_signed(TimeDelta::days(6))?;
+        Some(Week { start })
+    }
+
+    #[inline]
+    fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    #[inline]
+    fn end(&self) -> NaiveDate {
+        self.start + TimeDelta::days(6)
+    }
+
+    #[inline]
+    fn succ(&self) -> Option<Self> {
+        Week::containing(self.end().succ_opt()?)
+    }
+
+    #[inline]
+    fn pred(&self) -> Option<Self> {
+        Week::containing(self.start.pred_opt()?)
+    }
+
+    /// Weeks are numbered from the one starting on Monday 1969-12-29
+    #[inline]
+    fn to_key(&self) -> i32 {
+        (EpochDay::from(self.start).0 + 3).div_euclid(7)
+    }
+
+    fn from_key(key: i32) -> Option<Self> {
+        let start = i32::try_from(key as i64 * 7 - 3).ok()?;
+        Week::containing(EpochDay(start).to_date()?)
+    }
+}
+
+impl fmt::Display for Week {
+    /// Formats the week as in `2024-W05`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        PeriodKey::from(*self).write_to(f)
+    }
+}
+
+impl FromStr for Week {
+    type Err = ParsePeriodError;
+
+    /// Parses a week formatted as in `2024-W05`
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::{Period, Week};
+    ///
+    /// let week: Week = "2020-W53".parse().unwrap();
+    /// assert_eq!(week.start(), NaiveDate::from_ymd_opt(2020, 12, 28).unwrap());
+    /// assert_eq!(week.succ().unwrap().to_string(), "2021-W01");
+    /// assert!("2021-W53".parse::<Week>().is_err());
+    /// assert!("2021-W1".parse::<Week>().is_err());
+    fn from_str(s: &str) -> Result<Self, ParsePeriodError> {
+        let err = ParsePeriodError { expected: "week" };
+        let (year, week) = s.split_once("-W").ok_or(err)?;
+        let year = year.parse().map_err(|_| err)?;
+        let week = week.parse().map_err(|_| err)?;
+        Week::from_isoyw(year, week)
+            .filter(|parsed| parsed.to_string() == s)
+            .ok_or(err)
+    }
+}
+
+/// Extends chrono's opaque [IsoWeek](https://docs.rs/chrono/0.4.35/chrono/naive/struct.IsoWeek.html)
+/// with its bounds and navigation to neighbouring weeks. Methods return `None` when the result
+/// is out of the range supported by chrono.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{Datelike, NaiveDate};
+/// use chrono_utilities::naive::period::IsoWeekTransitions;
+///
+/// let week = NaiveDate::from_ymd_opt(2021, 1, 2).unwrap().iso_week();
+/// assert_eq!(week.first_day(), Some(NaiveDate::from_ymd_opt(2020, 12, 28).unwrap()));
+/// assert_eq!(week.last_day(), Some(NaiveDate::from_ymd_opt(2021, 1, 3).unwrap()));
+/// assert_eq!(week.succ().map(|next| (next.year(), next.week())), Some((2021, 1)));
+/// assert_eq!(week.pred().map(|prev| (prev.year(), prev.week())), Some((2020, 52)));
+/// assert_eq!(week.date_range().unwrap().num_days(), 7);
+pub trait IsoWeekTransitions: Sized {
+    /// Returns the Monday of the week
+    fn first_day(&self) -> Option<NaiveDate>;
+
+    /// Returns the Sunday of the week
+    fn last_day(&self) -> Option<NaiveDate>;
+
+    /// Returns the dates from Monday to Sunday of the week
+    fn date_range(&self) -> Option<DateRange>;
+
+    /// Returns the week following this one
+    fn succ(&self) -> Option<Self>;
+
+    /// Returns the week preceding this one
+    fn pred(&self) -> Option<Self>;
+}
+
+impl IsoWeekTransitions for IsoWeek {
+    #[inline]
+    fn first_day(&self) -> Option<NaiveDate> {
+        NaiveDate::from_isoywd_opt(self.year(), self.week(), Weekday::Mon)
+    }
+
+    #[inline]
+    fn last_day(&self) -> Option<NaiveDate> {
+        NaiveDate::from_isoywd_opt(self.year(), self.week(), Weekday::Sun)
+    }
+
+    #[inline]
+    fn date_range(&self) -> Option<DateRange> {
+        DateRange::new(self.first_day()?, self.last_day()?)
+    }
+
+    #[inline]
+    fn succ(&self) -> Option<Self> {
+        self.last_day()?.succ_opt().map(|next| next.iso_week())
+    }
+
+    #[inline]
+    fn pred(&self) -> Option<Self> {
+        self.first_day()?.pred_opt().map(|prev| prev.iso_week())
+    }
+}
+
+/// A calendar month of a given year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Month {
+    year: i32,
+    month: u32,
+}
+
+impl Month {
+    /// Returns the given month, from 1 to 12, of the given year
+    pub fn new(year: i32, month: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(year, month, 1)?;
+        Some(Month { year, month })
+    }
+
+    /// Returns the year of the month
+    #[inline]
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the number of the month within its year, from 1 to 12
+    #[inline]
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Returns the first day of the month
+    #[inline]
+    pub fn first_day(&self) -> NaiveDate {
+        self.start()
+    }
+
+    /// Returns the last day of the month
+    #[inline]
+    pub fn last_day(&self) -> NaiveDate {
+        self.end()
+    }
+
+    /// Returns the month the given number of months after this one, going back for negative
+    /// numbers. Returns `None` if the result is out of the range supported by chrono.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::YearMonth;
+    ///
+    /// let nov = YearMonth::from(NaiveDate::from_ymd_opt(2020, 11, 30).unwrap());
+    /// assert_eq!(nov.checked_add_months(3), YearMonth::new(2021, 2));
+    /// assert_eq!(nov.checked_add_months(-11), YearMonth::new(2019, 12));
+    /// assert_eq!(nov + 3, YearMonth::new(2021, 2).unwrap());
+    /// assert_eq!(YearMonth::new(2021, 2).unwrap() - nov, 3);
+    /// assert_eq!((nov + 3).last_day(), NaiveDate::from_ymd_opt(2021, 2, 28).unwrap());
+    pub fn checked_add_months(&self, months: i32) -> Option<Self> {
+        Month::from_index(self.index() + months as i64)
+    }
+
+    /// Returns the month the given number of months before this one. Returns `None` if the
+    /// result is out of the range supported by chrono.
+    pub fn checked_sub_months(&self, months: i32) -> Option<Self> {
+        Month::from_index(self.index() - months as i64)
+    }
+
+    /// Returns the month the given number of months after the start of year 0
+    fn from_index(index: i64) -> Option<Self> {
+        let year = i32::try_from(index.div_euclid(12)).ok()?;
+        Month::new(year, index.rem_euclid(12) as u32 + 1)
+    }
+
+    /// Returns the number of months between the start of year 0 and the month
+    fn index(&self) -> i64 {
+        self.year as i64 * 12 + self.month as i64 - 1
+    }
+}
+
+impl Period for Month {
+    #[inline]
+    fn containing(date: NaiveDate) -> Option<Self> {
+        Month::new(date.year(), date.month())
+    }
+
+    #[inline]
+    fn start(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1).unwrap()
+    }
+
+    #[inline]
+    fn end(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month, days_in_month(self.year, self.month))
+            .unwrap()
+    }
+
+    #[inline]
+    fn succ(&self) -> Option<Self> {
+        Month::from_index(self.index() + 1)
+    }
+
+    #[inline]
+    fn pred(&self) -> Option<Self> {
+        Month::from_index(self.index() - 1)
+    }
+
+    /// Months are numbered from January of year 0
+    #[inline]
+    fn to_key(&self) -> i32 {
+        self.index() as i32
+    }
+
+    #[inline]
+    fn from_key(key: i32) -> Option<Self> {
+        Month::from_index(key as i64)
+    }
+}
+
+impl From<NaiveDate> for Month {
+    #[inline]
+    fn from(date: NaiveDate) -> Self {
+        Month {
+            year: date.year(),
+            month: date.month(),
+        }
+    }
+}
+
+impl Add<i32> for Month {
+    type Output = Month;
+
+    /// Adds the given number of months
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is out of the range supported by chrono.
+    #[inline]
+    fn add(self, months: i32) -> Month {
+        self.checked_add_months(months)
+            .expect("`Month + i32` overflowed")
+    }
+}
+
+impl Sub<i32> for Month {
+    type Output = Month;
+
+    /// Subtracts the given number of months
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is out of the range supported by chrono.
+    #[inline]
+    fn sub(self, months: i32) -> Month {
+        self.checked_sub_months(months)
+            .expect("`Month - i32` overflowed")
+    }
+}
+
+impl Add<Months> for Month {
+    type Output = Month;
+
+    /// Adds chrono's months
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is out of the range supported by chrono.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::Months;
+    /// use chrono_utilities::naive::period::Month;
+    ///
+    /// let jan = Month::new(2020, 1).unwrap();
+    /// assert_eq!(jan + Months::new(14), Month::new(2021, 3).unwrap());
+    /// assert_eq!(jan - Months::new(1), Month::new(2019, 12).unwrap());
+    #[inline]
+    fn add(self, months: Months) -> Month {
+        Month::from_index(self.index() + months.as_u32() as i64)
+            .expect("`Month + Months` overflowed")
+    }
+}
+
+impl Sub<Months> for Month {
+    type Output = Month;
+
+    /// Subtracts chrono's months
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is out of the range supported by chrono.
+    #[inline]
+    fn sub(self, months: Months) -> Month {
+        Month::from_index(self.index() - months.as_u32() as i64)
+            .expect("`Month - Months` overflowed")
+    }
+}
+
+impl Sub<Month> for Month {
+    type Output = i32;
+
+    /// Returns the number of months between both months, negative if `rhs` is later
+    #[inline]
+    fn sub(self, rhs: Month) -> i32 {
+        (self.index() - rhs.index()) as i32
+    }
+}
+
+impl fmt::Display for Month {
+    /// Formats the month as in `2024-03`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        PeriodKey::from(*self).write_to(f)
+    }
+}
+
+impl FromStr for Month {
+    type Err = ParsePeriodError;
+
+    /// Parses a month formatted as in `2024-03`
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono_utilities::naive::period::Month;
+    ///
+    /// let month: Month = "2024-03".parse().unwrap();
+    /// assert_eq!(month, Month::new(2024, 3).unwrap());
+    /// assert_eq!((month + 10).to_string(), "2025-01");
+    /// assert!("2024-3".parse::<Month>().is_err());
+    /// assert!("2024-13".parse::<Month>().is_err());
+    fn from_str(s: &str) -> Result<Self, ParsePeriodError> {
+        let err = ParsePeriodError { expected: "month" };
+        let (year, month) = s.rsplit_once('-').ok_or(err)?;
+        let year = year.parse().map_err(|_| err)?;
+        let month = month.parse().map_err(|_| err)?;
+        Month::new(year, month)
+            .filter(|parsed| parsed.to_string() == s)
+            .ok_or(err)
+    }
+}
+
+/// A month without a day, as another name for [Month](struct.Month.html)
+pub type YearMonth = Month;
+
+/// A calendar quarter of a given year, the first one starting on January 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Quarter {
+    year: i32,
+    quarter: u32,
+}
+
+impl Quarter {
+    /// Returns the given quarter, from 1 to 4, of the given year
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::{Period, Quarter};
+    ///
+    /// let q4 = Quarter::new(2020, 4).unwrap();
+    /// assert_eq!(q4.end(), NaiveDate::from_ymd_opt(2020, 12, 31).unwrap());
+    /// assert_eq!(q4.succ(), Quarter::new(2021, 1));
+    /// assert_eq!(Quarter::containing(NaiveDate::from_ymd_opt(2020, 5, 20).unwrap()), Quarter::new(2020, 2));
+    /// assert_eq!(Quarter::new(2020, 5), None);
+    pub fn new(year: i32, quarter: u32) -> Option<Self> {
+        if !(1..=4).contains(&quarter) {
+            return None;
+        }
+        NaiveDate::from_ymd_opt(year, quarter * 3 - 2, 1)?;
+        Some(Quarter { year, quarter })
+    }
+
+    /// Returns the year of the quarter
+    #[inline]
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the number of the quarter within its year, from 1 to 4
+    #[inline]
+    pub fn quarter(&self) -> u32 {
+        self.quarter
+    }
+}
+
+impl Period for Quarter {
+    #[inline]
+    fn containing(date: NaiveDate) -> Option<Self> {
+        Quarter::new(date.year(), date.quarter_of_year())
+    }
+
+    #[inline]
+    fn start(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.quarter * 3 - 2, 1).unwrap()
+    }
+
+    #[inline]
+    fn end(&self) -> NaiveDate {
+        let month = self.quarter * 3;
+        NaiveDate::from_ymd_opt(self.year, month, days_in_month(self.year, month)).unwrap()
+    }
+
+    fn succ(&self) -> Option<Self> {
+        match self.quarter {
+            4 => Quarter::new(self.year.checked_add(1)?, 1),
+            quarter => Quarter::new(self.year, quarter + 1),
+        }
+    }
+
+    fn pred(&self) -> Option<Self> {
+        match self.quarter {
+            1 => Quarter::new(self.year.checked_sub(1)?, 4),
+            quarter => Quarter::new(self.year, quarter - 1),
+        }
+    }
+
+    /// Quarters are numbered from the first quarter of year 0
+    #[inline]
+    fn to_key(&self) -> i32 {
+        self.year * 4 + self.quarter as i32 - 1
+    }
+
+    #[inline]
+    fn from_key(key: i32) -> Option<Self> {
+        Quarter::new(key.div_euclid(4), key.rem_euclid(4) as u32 + 1)
+    }
+}
+
+impl fmt::Display for Quarter {
+    /// Formats the quarter as in `2024-Q3`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        PeriodKey::from(*self).write_to(f)
+    }
+}
+
+impl FromStr for Quarter {
+    type Err = ParsePeriodError;
+
+    /// Parses a quarter formatted as in `2024-Q3`
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::{Period, YearQuarter};
+    ///
+    /// let q3: YearQuarter = "2024-Q3".parse().unwrap();
+    /// assert_eq!(q3, YearQuarter::new(2024, 3).unwrap());
+    /// assert_eq!(q3.succ().unwrap().to_string(), "2024-Q4");
+    /// assert_eq!(q3.range().start(), NaiveDate::from_ymd_opt(2024, 7, 1).unwrap());
+    /// assert_eq!(q3.range().end(), NaiveDate::from_ymd_opt(2024, 9, 30).unwrap());
+    /// assert!("2024-Q5".parse::<YearQuarter>().is_err());
+    /// assert!("2024Q3".parse::<YearQuarter>().is_err());
+    fn from_str(s: &str) -> Result<Self, ParsePeriodError> {
+        let err = ParsePeriodError {
+            expected: "quarter",
+        };
+        let (year, quarter) = s.split_once("-Q").ok_or(err)?;
+        let year = year.parse().map_err(|_| err)?;
+        let quarter = quarter.parse().map_err(|_| err)?;
+        Quarter::new(year, quarter)
+            .filter(|parsed| parsed.to_string() == s)
+            .ok_or(err)
+    }
+}
+
+/// A quarter of a year, as another name for [Quarter](struct.Quarter.html)
+pub type YearQuarter = Quarter;
+
+/// A calendar year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year {
+    year: i32,
+}
+
+impl Year {
+    /// Returns the given year
+    pub fn new(year: i32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(year, 1, 1)?;
+        NaiveDate::from_ymd_opt(year, 12, 31)?;
+        Some(Year { year })
+    }
+
+    /// Returns the number of the year
+    #[inline]
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+}
+
+impl Period for Year {
+    #[inline]
+    fn containing(date: NaiveDate) -> Option<Self> {
+        Year::new(date.year())
+    }
+
+    #[inline]
+    fn start(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, 1, 1).unwrap()
+    }
+
+    #[inline]
+    fn end(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, 12, 31).unwrap()
+    }
+
+    #[inline]
+    fn succ(&self) -> Option<Self> {
+        Year::new(self.year.checked_add(1)?)
+    }
+
+    #[inline]
+    fn pred(&self) -> Option<Self> {
+        Year::new(self.year.checked_sub(1)?)
+    }
+
+    #[inline]
+    fn to_key(&self) -> i32 {
+        self.year
+    }
+
+    #[inline]
+    fn from_key(key: i32) -> Option<Self> {
+        Year::new(key)
+    }
+}
+
+impl fmt::Display for Year {
+    /// Formats the year as in `2024`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        PeriodKey::from(*self).write_to(f)
+    }
+}
+
+impl FromStr for Year {
+    type Err = ParsePeriodError;
+
+    /// Parses a year formatted as in `2024`
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono_utilities::naive::period::Year;
+    ///
+    /// let year: Year = "2024".parse().unwrap();
+    /// assert_eq!(year.year(), 2024);
+    /// assert_eq!(Year::new(33).unwrap().to_string(), "0033");
+    /// assert!("+2024".parse::<Year>().is_err());
+    fn from_str(s: &str) -> Result<Self, ParsePeriodError> {
+        let err = ParsePeriodError { expected: "year" };
+        let year = s.parse().map_err(|_| err)?;
+        Year::new(year)
+            .filter(|parsed| parsed.to_string() == s)
+            .ok_or(err)
+    }
+}
+
+impl TryFrom<NaiveDate> for Week {
+    type Error = PeriodOutOfRange;
+
+    /// Returns the ISO 8601 week containing the date, failing if the week is not entirely
+    /// within the range supported by chrono
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{Datelike, NaiveDate};
+    /// use chrono_utilities::naive::period::{Month, Quarter, Week, Year};
+    /// use std::convert::TryFrom;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2021, 1, 2).unwrap();
+    /// assert_eq!(Week::try_from(d), Ok(Week::from_isoyw(2020, 53).unwrap()));
+    /// assert_eq!(Quarter::from(Month::from(d)), Quarter::new(2021, 1).unwrap());
+    /// assert_eq!(Year::from(Quarter::from(d)), Year::new(2021).unwrap());
+    /// assert_eq!(Week::try_from(d.iso_week()), Week::try_from(d));
+    fn try_from(date: NaiveDate) -> Result<Self, PeriodOutOfRange> {
+        Week::containing(date).ok_or(PeriodOutOfRange)
+    }
+}
+
+impl TryFrom<IsoWeek> for Week {
+    type Error = PeriodOutOfRange;
+
+    /// Returns the week matching chrono's ISO week, failing if the week is not entirely within
+    /// the range supported by chrono
+    fn try_from(week: IsoWeek) -> Result<Self, PeriodOutOfRange> {
+        Week::from_isoyw(week.year(), week.week()).ok_or(PeriodOutOfRange)
+    }
+}
+
+impl From<Week> for IsoWeek {
+    #[inline]
+    fn from(week: Week) -> Self {
+        week.start.iso_week()
+    }
+}
+
+impl From<NaiveDate> for Quarter {
+    #[inline]
+    fn from(date: NaiveDate) -> Self {
+        Quarter::from(Month::from(date))
+    }
+}
+
+impl From<Month> for Quarter {
+    /// Returns the quarter containing the month
+    #[inline]
+    fn from(month: Month) -> Self {
+        Quarter {
+            year: month.year,
+            quarter: (month.month - 1) / 3 + 1,
+        }
+    }
+}
+
+impl From<NaiveDate> for Year {
+    #[inline]
+    fn from(date: NaiveDate) -> Self {
+        Year { year: date.year() }
+    }
+}
+
+impl From<Month> for Year {
+    /// Returns the year containing the month
+    #[inline]
+    fn from(month: Month) -> Self {
+        Year { year: month.year }
+    }
+}
+
+impl From<Quarter> for Year {
+    /// Returns the year containing the quarter
+    #[inline]
+    fn from(quarter: Quarter) -> Self {
+        Year { year: quarter.year }
+    }
+}
+
+impl From<Quarter> for [Month; 3] {
+    /// Returns the months of the quarter in chronological order
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono_utilities::naive::period::{Month, Quarter, Year};
+    ///
+    /// let months: [Month; 3] = Quarter::new(2024, 2).unwrap().into();
+    /// assert_eq!(months[0], Month::new(2024, 4).unwrap());
+    /// assert_eq!(months[2], Month::new(2024, 6).unwrap());
+    /// let quarters: [Quarter; 4] = Year::new(2024).unwrap().into();
+    /// assert_eq!(quarters[3], Quarter::new(2024, 4).unwrap());
+    fn from(quarter: Quarter) -> Self {
+        let first = quarter.quarter * 3 - 2;
+        let month = |offset| Month {
+            year: quarter.year,
+            month: first + offset,
+        };
+        [month(0), month(1), month(2)]
+    }
+}
+
+impl From<Year> for [Quarter; 4] {
+    /// Returns the quarters of the year in chronological order
+    fn from(year: Year) -> Self {
+        let quarter = |quarter| Quarter {
+            year: year.year,
+            quarter,
+        };
+        [quarter(1), quarter(2), quarter(3), quarter(4)]
+    }
+}
+
+impl From<Year> for [Month; 12] {
+    /// Returns the months of the year in chronological order
+    fn from(year: Year) -> Self {
+        let mut months = [Month {
+            year: year.year,
+            month: 1,
+        }; 12];
+        for (index, month) in months.iter_mut().enumerate() {
+            month.month = index as u32 + 1;
+        }
+        months
+    }
+}
+
+/// Error returned when converting into a period which is not entirely within the range
+/// supported by chrono
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PeriodOutOfRange;
+
+impl fmt::Display for PeriodOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("period out of range")
+    }
+}
+
+impl Error for PeriodOutOfRange {}
+
+/// Error returned when parsing a period fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParsePeriodError {
+    expected: &'static str,
+}
+
+impl ParsePeriodError {
+    pub(crate) fn new(expected: &'static str) -> Self {
+        ParsePeriodError { expected }
+    }
+}
+
+impl fmt::Display for ParsePeriodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {}", self.expected)
+    }
+}
+
+impl Error for ParsePeriodError {}
+
+/// Serialization of periods as their canonical strings, such as `"2024-W05"` or `"2024-03"`
+#[cfg(feature = "serde")]
+pub(crate) mod serde_impls {
+    use super::{Month, Quarter, Week, Year};
+    use serde::de::{self, Deserialize, Deserializer, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use std::fmt;
+    use std::marker::PhantomData;
+    use std::str::FromStr;
+
+    /// Deserializes a period from its canonical string
+    pub(crate) struct CanonicalVisitor<T> {
+        expecting: &'static str,
+        period: PhantomData<T>,
+    }
+
+    impl<T> CanonicalVisitor<T> {
+        pub(crate) fn new(expecting: &'static str) -> Self {
+            CanonicalVisitor {
+                expecting,
+                period: PhantomData,
+            }
+        }
+    }
+
+    impl<'de, T: FromStr> Visitor<'de> for CanonicalVisitor<T>
+    where
+        T::Err: fmt::Display,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.expecting)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+            value.parse().map_err(E::custom)
+        }
+    }
+
+    impl Serialize for Week {
+        /// Serializes the week as in `"2024-W05"`
+        ///
+        /// # Example
+        ///
+        /// ~~~~
+        /// use chrono_utilities::naive::period::{Month, Week};
+        ///
+        /// let week = Week::from_isoyw(2024, 5).unwrap();
+        /// assert_eq!(serde_json::to_string(&week).unwrap(), r#""2024-W05""#);
+        /// let month: Month = serde_json::from_str(r#""2024-03""#).unwrap();
+        /// assert_eq!(month, Month::new(2024, 3).unwrap());
+        /// assert!(serde_json::from_str::<Month>(r#""2024-3""#).is_err());
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Week {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(CanonicalVisitor::new("a week such as 2024-W05"))
+        }
+    }
+
+    impl Serialize for Month {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Month {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(CanonicalVisitor::new("a month such as 2024-03"))
+        }
+    }
+
+    impl Serialize for Quarter {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Quarter {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(CanonicalVisitor::new("a quarter such as 2024-Q1"))
+        }
+    }
+
+    impl Serialize for Year {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Year {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(CanonicalVisitor::new("a year such as 2024"))
+        }
+    }
+}
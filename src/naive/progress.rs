@@ -0,0 +1,119 @@
+//! Percentage of a calendar period elapsed at a date or datetime, for progress bars and pacing
+//! metrics
+use super::period::Unit;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Common set of methods returning the percentage of the period containing a date or datetime
+/// which has elapsed, from 0 at the very start of the period up to but excluding 100. Dates
+/// count as their midnight, so that the first day of a period is 0% into it. Weeks follow the
+/// ISO 8601 standard and start on Monday.
+pub trait PercentElapsed {
+    /// Returns the percentage of the period of the given granularity elapsed, or `None` if the
+    /// period is out of the range supported by chrono
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::Unit;
+    /// use chrono_utilities::naive::progress::PercentElapsed;
+    ///
+    /// let evening = NaiveDate::from_ymd_opt(2020, 6, 15).unwrap().and_hms_opt(18, 0, 0).unwrap();
+    /// assert_eq!(evening.percent_elapsed(Unit::Day), Some(75.0));
+    fn percent_elapsed(&self, unit: Unit) -> Option<f64>;
+
+    /// Returns the percentage of the ISO 8601 week elapsed
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::progress::PercentElapsed;
+    ///
+    /// // Thursday
+    /// let d = NaiveDate::from_ymd_opt(2020, 8, 13).unwrap();
+    /// assert_eq!(d.percent_of_week_elapsed().map(|p| p.round()), Some(43.0));
+    #[inline]
+    fn percent_of_week_elapsed(&self) -> Option<f64> {
+        self.percent_elapsed(Unit::Week)
+    }
+
+    /// Returns the percentage of the month elapsed
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::progress::PercentElapsed;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2021, 2, 15).unwrap();
+    /// assert_eq!(d.percent_of_month_elapsed(), Some(50.0));
+    /// let noon = d.and_hms_opt(12, 0, 0).unwrap();
+    /// assert_eq!(noon.percent_of_month_elapsed().map(|p| (p * 100.0).round()), Some(5179.0));
+    #[inline]
+    fn percent_of_month_elapsed(&self) -> Option<f64> {
+        self.percent_elapsed(Unit::Month)
+    }
+
+    /// Returns the percentage of the quarter elapsed
+    #[inline]
+    fn percent_of_quarter_elapsed(&self) -> Option<f64> {
+        self.percent_elapsed(Unit::Quarter)
+    }
+
+    /// Returns the percentage of the year elapsed
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::progress::PercentElapsed;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// assert_eq!(d.percent_of_year_elapsed(), Some(0.0));
+    #[inline]
+    fn percent_of_year_elapsed(&self) -> Option<f64> {
+        self.percent_elapsed(Unit::Year)
+    }
+}
+
+impl PercentElapsed for NaiveDate {
+    #[inline]
+    fn percent_elapsed(&self, unit: Unit) -> Option<f64> {
+        self.and_time(NaiveTime::MIN).percent_elapsed(unit)
+    }
+}
+
+impl PercentElapsed for NaiveDateTime {
+    fn percent_elapsed(&self, unit: Unit) -> Option<f64> {
+        let date = self.date();
+        let start = unit.start_of(date)?.and_time(NaiveTime::MIN);
+        let end = unit.start_of_succ(date)?.and_time(NaiveTime::MIN);
+        let seconds = |since: NaiveDateTime, until: NaiveDateTime| {
+            let delta = until.signed_duration_since(since);
+            delta.num_seconds() as f64 + delta.subsec_nanos() as f64 / 1e9
+        };
+        Some(seconds(start, *self) / seconds(start, end) * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PercentElapsed;
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn test_bounds() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(date(2020, 10, 1).percent_of_quarter_elapsed(), Some(0.0));
+        assert_eq!(
+            date(2020, 12, 31).percent_of_year_elapsed(),
+            Some(365.0 / 366.0 * 100.0)
+        );
+        let last =
+            date(2020, 12, 31).and_time(NaiveTime::from_hms_milli_opt(23, 59, 59, 999).unwrap());
+        assert!(last.percent_of_week_elapsed().unwrap() < 100.0);
+        assert!(last.percent_of_year_elapsed().unwrap() > 99.999);
+        assert_eq!(NaiveDate::MAX.percent_of_month_elapsed(), None);
+    }
+}
@@ -0,0 +1,126 @@
+//! Proration of amounts billed over a period to the part of the period actually covered, as in
+//! subscription billing
+use super::days_in_month;
+use super::range::DateRange;
+use chrono::{Datelike, NaiveDate};
+
+/// How days are counted when prorating
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayCount {
+    /// Every calendar day counts as one day
+    Actual,
+    /// Every month counts as 30 days as per the 30/360 convention. Days are counted from the
+    /// start date to the end date, both inclusive, each taken as the 30th when it falls on the
+    /// last day of its month. A full month therefore always counts as 30 days.
+    Thirty360,
+}
+
+impl DayCount {
+    /// Returns the number of days in the range, counting both ends, as per the convention
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::proration::DayCount;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let feb = DateRange::new(NaiveDate::from_ymd_opt(2021, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2021, 2, 28).unwrap()).unwrap();
+    /// assert_eq!(DayCount::Actual.num_days(&feb), 28);
+    /// assert_eq!(DayCount::Thirty360.num_days(&feb), 30);
+    pub fn num_days(&self, range: &DateRange) -> i64 {
+        match self {
+            DayCount::Actual => range.num_days(),
+            DayCount::Thirty360 => {
+                let (start, end) = (range.start(), range.end());
+                let day = |date: NaiveDate| {
+                    if date.day() == days_in_month(date.year(), date.month()) {
+                        30
+                    } else {
+                        date.day() as i64
+                    }
+                };
+                360 * (end.year() as i64 - start.year() as i64)
+                    + 30 * (end.month() as i64 - start.month() as i64)
+                    + (day(end) - day(start))
+                    + 1
+            }
+        }
+    }
+}
+
+/// The share of a billing period covered by a range of dates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Proration {
+    /// The number of days of the period covered
+    pub covered_days: i64,
+    /// The number of days in the whole period
+    pub period_days: i64,
+}
+
+impl Proration {
+    /// Returns the fraction of the period covered, from 0 to 1
+    #[inline]
+    pub fn fraction(&self) -> f64 {
+        self.covered_days as f64 / self.period_days as f64
+    }
+
+    /// Returns the share of the amount billed for the whole period owed for the covered days
+    #[inline]
+    pub fn apply(&self, amount: f64) -> f64 {
+        amount * self.fraction()
+    }
+}
+
+/// Returns the share of the billing period covered by the range, counting days as per the
+/// convention. Only the dates of the range falling within the period are covered, so that a
+/// range not overlapping the period covers none of it.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::proration::{prorate, DayCount};
+/// use chrono_utilities::naive::range::DateRange;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2021, m, d).unwrap();
+/// let feb = DateRange::new(date(2, 1), date(2, 28)).unwrap();
+/// // Subscribed on February 15 and cancelled on March 31
+/// let subscription = DateRange::new(date(2, 15), date(3, 31)).unwrap();
+/// let actual = prorate(&feb, &subscription, DayCount::Actual);
+/// assert_eq!((actual.covered_days, actual.period_days), (14, 28));
+/// assert_eq!(actual.apply(10.0), 5.0);
+/// let thirty = prorate(&feb, &subscription, DayCount::Thirty360);
+/// assert_eq!((thirty.covered_days, thirty.period_days), (16, 30));
+/// let march = DateRange::new(date(3, 1), date(3, 31)).unwrap();
+/// assert_eq!(prorate(&march, &feb, DayCount::Actual).fraction(), 0.0);
+pub fn prorate(period: &DateRange, covered: &DateRange, convention: DayCount) -> Proration {
+    Proration {
+        covered_days: period
+            .intersection(covered)
+            .map_or(0, |range| convention.num_days(&range)),
+        period_days: convention.num_days(period),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DayCount;
+    use crate::naive::range::DateRange;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_thirty_360() {
+        let range = |from: (i32, u32, u32), to: (i32, u32, u32)| {
+            let start = NaiveDate::from_ymd_opt(from.0, from.1, from.2).unwrap();
+            let end = NaiveDate::from_ymd_opt(to.0, to.1, to.2).unwrap();
+            DateRange::new(start, end).unwrap()
+        };
+        let days = |r: DateRange| DayCount::Thirty360.num_days(&r);
+        assert_eq!(days(range((2020, 1, 1), (2020, 12, 31))), 360);
+        assert_eq!(days(range((2020, 12, 1), (2021, 2, 28))), 90);
+        assert_eq!(days(range((2020, 1, 31), (2020, 1, 31))), 1);
+        assert_eq!(days(range((2020, 1, 16), (2020, 1, 31))), 15);
+        assert_eq!(days(range((2020, 2, 29), (2020, 3, 1))), 2);
+    }
+}
@@ -0,0 +1,873 @@
+//! Spans of [NaiveDate](https://docs.rs/chrono/0.4.35/chrono/naive/struct.NaiveDate.html) and
+//! iterators to walk over them
+use super::duration::{CalendarDuration, IsoDuration};
+use super::interval::ParseIntervalError;
+use super::period::{month_starts_from, PeriodCalendar, Unit};
+use super::{days_in_month, shift_months};
+use chrono::{Datelike, NaiveDate, TimeDelta, Weekday};
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::str::FromStr;
+
+/// Where the first full window starts when splitting a [DateRange](struct.DateRange.html) into
+/// windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    /// The first window starts at the start of the range
+    Start,
+    /// The first full window starts on the first occurrence of the weekday
+    Weekday(Weekday),
+    /// The first full window starts on the first day of a month
+    MonthStart,
+}
+
+/// An inclusive span of dates from `start` to `end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateRange {
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+impl DateRange {
+    /// Creates a range spanning from `start` to `end`, both inclusive. Returns `None` if `end`
+    /// falls before `start`.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2020, 1, 30).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 2, 2).unwrap();
+    /// let range = DateRange::new(start, end).unwrap();
+    /// assert_eq!(range.num_days(), 4);
+    /// assert!(DateRange::new(end, start).is_none());
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Option<Self> {
+        if end < start {
+            None
+        } else {
+            Some(DateRange { start, end })
+        }
+    }
+
+    /// Creates a range spanning from `start`, inclusive, to `end`, exclusive. Returns `None` if
+    /// the range would be empty.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2020, 1, 30).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 2, 2).unwrap();
+    /// let range = DateRange::from_half_open(start, end).unwrap();
+    /// assert_eq!(range.end(), NaiveDate::from_ymd_opt(2020, 2, 1).unwrap());
+    /// assert_eq!(range.end_exclusive(), Some(end));
+    /// assert!(DateRange::from_half_open(start, start).is_none());
+    pub fn from_half_open(start: NaiveDate, end: NaiveDate) -> Option<Self> {
+        DateRange::new(start, end.pred_opt()?)
+    }
+
+    /// Returns the first date of the range
+    #[inline]
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// Returns the last date of the range
+    #[inline]
+    pub fn end(&self) -> NaiveDate {
+        self.end
+    }
+
+    /// Returns the day after the last date of the range, which ends the range when seen as a
+    /// half-open interval
+    #[inline]
+    pub fn end_exclusive(&self) -> Option<NaiveDate> {
+        self.end.succ_opt()
+    }
+
+    /// Returns true if the date falls within the range
+    #[inline]
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+
+    /// Returns true if the ranges share at least one date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let jan = DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap();
+    /// let feb = DateRange::new(NaiveDate::from_ymd_opt(2020, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()).unwrap();
+    /// let mid = DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 15).unwrap(), NaiveDate::from_ymd_opt(2020, 2, 15).unwrap()).unwrap();
+    /// assert!(!jan.overlaps(&feb));
+    /// assert!(jan.overlaps(&mid));
+    /// assert_eq!(jan.intersection(&mid).unwrap().num_days(), 17);
+    /// assert_eq!(jan.intersection(&feb), None);
+    /// assert_eq!(jan.union(&feb).unwrap().num_days(), 60);
+    /// let mar = DateRange::new(NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 31).unwrap()).unwrap();
+    /// assert_eq!(jan.union(&mar), None);
+    /// assert_eq!(jan.gap(&mar), Some(feb));
+    /// assert_eq!(mar.gap(&jan), Some(feb));
+    /// assert_eq!(jan.gap(&feb), None);
+    #[inline]
+    pub fn overlaps(&self, other: &DateRange) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Returns the dates shared by both ranges, if any
+    #[inline]
+    pub fn intersection(&self, other: &DateRange) -> Option<DateRange> {
+        DateRange::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Returns the range covering both ranges, or `None` if they neither overlap nor touch as
+    /// the result would then include dates from neither range
+    pub fn union(&self, other: &DateRange) -> Option<DateRange> {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if first
+            .end_exclusive()
+            .is_some_and(|next| next < second.start)
+        {
+            return None;
+        }
+        DateRange::new(first.start, first.end.max(second.end))
+    }
+
+    /// Returns the dates lying strictly between the ranges, or `None` if they overlap or touch
+    pub fn gap(&self, other: &DateRange) -> Option<DateRange> {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        DateRange::new(first.end.succ_opt()?, second.start.pred_opt()?)
+    }
+
+    /// Returns the number of days in the range, counting both ends
+    #[inline]
+    pub fn num_days(&self) -> i64 {
+        self.end.signed_duration_since(self.start).num_days() + 1
+    }
+
+    /// Returns an iterator over every date in the range
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let range = DateRange::new(NaiveDate::from_ymd_opt(1996, 2, 28).unwrap(), NaiveDate::from_ymd_opt(1996, 3, 1).unwrap()).unwrap();
+    /// let dates: Vec<NaiveDate> = range.iter().collect();
+    /// assert_eq!(dates, vec![
+    ///     NaiveDate::from_ymd_opt(1996, 2, 28).unwrap(),
+    ///     NaiveDate::from_ymd_opt(1996, 2, 29).unwrap(),
+    ///     NaiveDate::from_ymd_opt(1996, 3, 1).unwrap(),
+    /// ]);
+    #[inline]
+    pub fn iter(&self) -> DayStep {
+        self.step_by_days(1)
+    }
+
+    /// Returns an iterator over the dates of the range starting at `start` and moving `n` days
+    /// at a time. The last date yielded is the last one not exceeding `end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let range = DateRange::new(NaiveDate::from_ymd_opt(2020, 2, 25).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 5).unwrap()).unwrap();
+    /// let dates: Vec<NaiveDate> = range.step_by_days(3).collect();
+    /// assert_eq!(dates, vec![
+    ///     NaiveDate::from_ymd_opt(2020, 2, 25).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 2, 28).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 3, 2).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 3, 5).unwrap(),
+    /// ]);
+    pub fn step_by_days(&self, n: u32) -> DayStep {
+        assert!(n != 0, "step must be non-zero");
+        let step = n as i64;
+        DayStep {
+            start: self.start,
+            step,
+            front: 0,
+            back: (self.num_days() - 1) / step + 1,
+        }
+    }
+
+    /// Returns an iterator over the dates of the range starting at `start` and moving `n` weeks
+    /// at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let range = DateRange::new(NaiveDate::from_ymd_opt(2019, 12, 20).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 20).unwrap()).unwrap();
+    /// let dates: Vec<NaiveDate> = range.step_by_weeks(2).collect();
+    /// assert_eq!(dates, vec![
+    ///     NaiveDate::from_ymd_opt(2019, 12, 20).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 1, 3).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 1, 17).unwrap(),
+    /// ]);
+    pub fn step_by_weeks(&self, n: u32) -> DayStep {
+        assert!(n != 0, "step must be non-zero");
+        let step = n as i64 * 7;
+        DayStep {
+            start: self.start,
+            step,
+            front: 0,
+            back: (self.num_days() - 1) / step + 1,
+        }
+    }
+
+    /// Returns an iterator over the dates of the range starting at `start` and moving `n` months
+    /// at a time.
+    ///
+    /// Every date is computed from `start` rather than from the previously yielded date, with
+    /// the day clamped to the length of the target month. Hence stepping from Jan 31 yields
+    /// Feb 29 (or 28) followed by Mar 31. If `start` is the last day of its month, every date
+    /// yielded is the last day of its month as well.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let r1 = DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 31).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 30).unwrap()).unwrap();
+    /// let dates: Vec<NaiveDate> = r1.step_by_months(1).collect();
+    /// assert_eq!(dates, vec![
+    ///     NaiveDate::from_ymd_opt(2020, 1, 31).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 2, 29).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 3, 31).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 4, 30).unwrap(),
+    /// ]);
+    ///
+    /// let r2 = DateRange::new(NaiveDate::from_ymd_opt(2019, 2, 28).unwrap(), NaiveDate::from_ymd_opt(2019, 8, 1).unwrap()).unwrap();
+    /// let dates: Vec<NaiveDate> = r2.step_by_months(2).collect();
+    /// assert_eq!(dates, vec![
+    ///     NaiveDate::from_ymd_opt(2019, 2, 28).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2019, 4, 30).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2019, 6, 30).unwrap(),
+    /// ]);
+    pub fn step_by_months(&self, n: u32) -> MonthStep {
+        assert!(n != 0, "step must be non-zero");
+        let start = self.start;
+        let mut iter = MonthStep {
+            start,
+            step: n as i64,
+            end_of_month: start.day() == days_in_month(start.year(), start.month()),
+            front: 0,
+            back: 0,
+        };
+        let months = (self.end.year() as i64 - start.year() as i64) * 12 + self.end.month0() as i64
+            - start.month0() as i64;
+        let mut last = months / iter.step;
+        // The clamped date in the last candidate month may still fall after `end`
+        if iter.nth_date(last).map_or(true, |date| date > self.end) {
+            last -= 1;
+        }
+        iter.back = last + 1;
+        iter
+    }
+
+    /// Splits the range into consecutive sub-ranges aligned to calendar months. The first and
+    /// last sub-ranges are partial months when the range does not start on the first or end on
+    /// the last day of a month.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let range = DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 15).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 10).unwrap()).unwrap();
+    /// let chunks = range.split_by_month();
+    /// assert_eq!(chunks, vec![
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 15).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap(),
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()).unwrap(),
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 10).unwrap()).unwrap(),
+    /// ]);
+    #[inline]
+    pub fn split_by_month(&self) -> Vec<DateRange> {
+        self.split_by(Unit::Month)
+    }
+
+    /// Splits the range into consecutive sub-ranges aligned to the calendar periods of the given
+    /// granularity. The first and last sub-ranges are partial periods when the range does not
+    /// start or end on a period boundary.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::period::Unit;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let range = DateRange::new(NaiveDate::from_ymd_opt(2019, 11, 15).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 30).unwrap()).unwrap();
+    /// assert_eq!(range.split_by(Unit::Quarter), vec![
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2019, 11, 15).unwrap(), NaiveDate::from_ymd_opt(2019, 12, 31).unwrap()).unwrap(),
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 31).unwrap()).unwrap(),
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 30).unwrap()).unwrap(),
+    /// ]);
+    /// assert_eq!(range.split_by(Unit::Year).len(), 2);
+    /// assert_eq!(range.split_by(Unit::Week).len(), 25);
+    pub fn split_by(&self, unit: Unit) -> Vec<DateRange> {
+        let mut chunks = Vec::new();
+        let mut start = self.start;
+        while let Some(next_start) = unit
+            .start_of_succ(start)
+            .filter(|next_start| *next_start <= self.end)
+        {
+            chunks.push(DateRange {
+                start,
+                end: next_start.pred_opt().unwrap(),
+            });
+            start = next_start;
+        }
+        chunks.push(DateRange {
+            start,
+            end: self.end,
+        });
+        chunks
+    }
+
+    /// Splits the range into consecutive, non-overlapping windows of `n_days` days each, the last
+    /// one being truncated to the end of the range. When aligned to a weekday or to the start of
+    /// a month, the dates before the first such boundary make up a shorter leading window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_days` is zero.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::range::{Alignment, DateRange};
+    ///
+    /// let range = DateRange::new(NaiveDate::from_ymd_opt(2020, 6, 3).unwrap(), NaiveDate::from_ymd_opt(2020, 6, 20).unwrap()).unwrap();
+    /// let windows = range.split_into_windows(7, Alignment::Start);
+    /// assert_eq!(windows.len(), 3);
+    /// assert_eq!(windows[2], DateRange::new(NaiveDate::from_ymd_opt(2020, 6, 17).unwrap(), NaiveDate::from_ymd_opt(2020, 6, 20).unwrap()).unwrap());
+    ///
+    /// let windows = range.split_into_windows(7, Alignment::Weekday(Weekday::Mon));
+    /// assert_eq!(windows, vec![
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 6, 3).unwrap(), NaiveDate::from_ymd_opt(2020, 6, 7).unwrap()).unwrap(),
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 6, 8).unwrap(), NaiveDate::from_ymd_opt(2020, 6, 14).unwrap()).unwrap(),
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 6, 15).unwrap(), NaiveDate::from_ymd_opt(2020, 6, 20).unwrap()).unwrap(),
+    /// ]);
+    pub fn split_into_windows(&self, n_days: u32, align: Alignment) -> Vec<DateRange> {
+        assert!(n_days != 0, "window length must be non-zero");
+        let anchor = match align {
+            Alignment::Start => Some(self.start),
+            Alignment::Weekday(weekday) => {
+                let ahead = (7 + weekday.num_days_from_monday()
+                    - self.start.weekday().num_days_from_monday())
+                    % 7;
+                self.start.checked_add_signed(TimeDelta::days(ahead as i64))
+            }
+            Alignment::MonthStart => month_starts_from(self.start).next(),
+        };
+        let anchor = match anchor.filter(|anchor| *anchor <= self.end) {
+            Some(anchor) => anchor,
+            None => return vec![*self],
+        };
+        let mut windows = Vec::new();
+        if anchor > self.start {
+            windows.push(DateRange {
+                start: self.start,
+                end: anchor.pred_opt().unwrap(),
+            });
+        }
+        let rest = DateRange {
+            start: anchor,
+            end: self.end,
+        };
+        let last = rest.end;
+        windows.extend(rest.step_by_days(n_days).map(|start| {
+            DateRange {
+                start,
+                end: start
+                    .checked_add_signed(TimeDelta::days(n_days as i64 - 1))
+                    .map_or(last, |end| end.min(last)),
+            }
+        }));
+        windows
+    }
+
+    /// Returns an iterator over the overlapping windows of the range used for rolling analytics.
+    /// The first window starts at the start of the range and spans `length`, every following one
+    /// starting `step` after the start of the previous. Only windows which end within the range
+    /// are yielded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `length` or `step` does not move dates forward.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::duration::CalendarDuration;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let range = DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 10).unwrap()).unwrap();
+    /// let windows: Vec<DateRange> = range
+    ///     .sliding_windows(CalendarDuration::weeks(1), CalendarDuration::days(2))
+    ///     .collect();
+    /// assert_eq!(windows, vec![
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 7).unwrap()).unwrap(),
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 3).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 9).unwrap()).unwrap(),
+    /// ]);
+    pub fn sliding_windows(
+        &self,
+        length: CalendarDuration,
+        step: CalendarDuration,
+    ) -> SlidingWindows {
+        let moves_forward = |duration: CalendarDuration| {
+            duration
+                .add_to(self.start)
+                .map_or(true, |date| date > self.start)
+        };
+        assert!(
+            moves_forward(length) && moves_forward(step),
+            "window length and step must move dates forward"
+        );
+        SlidingWindows {
+            range: *self,
+            length,
+            step,
+            index: 0,
+        }
+    }
+}
+
+/// An inclusive span of dates, as another name for [DateRange](struct.DateRange.html)
+pub type DateInterval = DateRange;
+
+impl IntoIterator for DateRange {
+    type Item = NaiveDate;
+    type IntoIter = DayStep;
+
+    fn into_iter(self) -> DayStep {
+        self.iter()
+    }
+}
+
+impl fmt::Display for DateRange {
+    /// Formats the range as an ISO 8601 interval made of its first and last dates, such as
+    /// `2020-01-01/2020-01-31`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.start, self.end)
+    }
+}
+
+impl FromStr for DateRange {
+    type Err = ParseIntervalError;
+
+    /// Parses an ISO 8601 interval of dates made of a start and an end, a start and a duration,
+    /// or a duration and an end, separated by a slash. An end date is part of the range, while
+    /// a duration gives the number of dates in the range, so that `2020-01-01/P1M` ends on
+    /// January 31. Durations may not have a time part.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::DateRange;
+    ///
+    /// let january = DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap();
+    /// assert_eq!("2020-01-01/2020-01-31".parse(), Ok(january));
+    /// assert_eq!("2020-01-01/P1M".parse(), Ok(january));
+    /// assert_eq!("P31D/2020-01-31".parse(), Ok(january));
+    /// assert_eq!(january.to_string(), "2020-01-01/2020-01-31");
+    /// assert!("2020-01-01/PT12H".parse::<DateRange>().is_err());
+    fn from_str(s: &str) -> Result<Self, ParseIntervalError> {
+        let err = ParseIntervalError::new;
+        let (first, second) = s.split_once('/').ok_or(err("separator"))?;
+        let duration = |s: &str| {
+            s.parse::<IsoDuration>()
+                .ok()
+                .filter(|duration| duration.time().is_zero())
+                .map(|duration| duration.calendar())
+                .ok_or(err("duration"))
+        };
+        let date = |s: &str, part| s.parse::<NaiveDate>().map_err(|_| err(part));
+        let range = match (first.starts_with('P'), second.starts_with('P')) {
+            (true, true) => None,
+            (true, false) => {
+                let duration = duration(first)?;
+                let end = date(second, "end")?;
+                let start = end.succ_opt().and_then(|next| duration.sub_from(next));
+                start.and_then(|start| DateRange::new(start, end))
+            }
+            (false, true) => {
+                let start = date(first, "start")?;
+                let end = duration(second)?.add_to(start);
+                end.and_then(|end| DateRange::from_half_open(start, end))
+            }
+            (false, false) => DateRange::new(date(first, "start")?, date(second, "end")?),
+        };
+        range.ok_or(err("interval"))
+    }
+}
+
+/// Common set of methods for deriving ranges of dates relative to a date
+pub trait RangeTransitions: Sized {
+    /// Returns the range of `n` days ending on the date
+    fn trailing_days(&self, n: u32) -> Option<DateRange>;
+
+    /// Returns the range of `n` weeks ending on the date
+    fn trailing_weeks(&self, n: u32) -> Option<DateRange>;
+
+    /// Returns the range of `n` months ending on the date
+    fn trailing_months(&self, n: u32) -> Option<DateRange>;
+
+    /// Returns the range from the start of the current month up to the date
+    fn month_to_date(&self) -> Option<DateRange>;
+
+    /// Returns the range from the start of the current quarter up to the date
+    fn quarter_to_date(&self) -> Option<DateRange>;
+
+    /// Returns the range from the start of the current year up to the date
+    fn year_to_date(&self) -> Option<DateRange>;
+
+    /// Returns the range from the start of the current period of the calendar up to the date
+    fn period_to_date<C: PeriodCalendar>(&self, calendar: &C, unit: Unit) -> Option<DateRange>;
+}
+
+impl RangeTransitions for NaiveDate {
+    /// Returns the range of `n` days ending on the date, or `None` if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::RangeTransitions;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 3, 1).unwrap();
+    /// let range = d.trailing_days(7).unwrap();
+    /// assert_eq!(range.start(), NaiveDate::from_ymd_opt(2020, 2, 24).unwrap());
+    /// assert_eq!(range.end(), d);
+    fn trailing_days(&self, n: u32) -> Option<DateRange> {
+        let back = (n as i64).checked_sub(1).filter(|back| *back >= 0)?;
+        DateRange::new(self.checked_sub_signed(TimeDelta::days(back))?, *self)
+    }
+
+    /// Returns the range of `n` weeks ending on the date, or `None` if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::RangeTransitions;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 3, 1).unwrap();
+    /// let range = d.trailing_weeks(2).unwrap();
+    /// assert_eq!(range.start(), NaiveDate::from_ymd_opt(2020, 2, 17).unwrap());
+    /// assert_eq!(range.num_days(), 14);
+    #[inline]
+    fn trailing_weeks(&self, n: u32) -> Option<DateRange> {
+        self.trailing_days(n.checked_mul(7)?)
+    }
+
+    /// Returns the range of `n` months ending on the date, or `None` if `n` is zero. The range
+    /// starts on the day following the same day `n` months earlier. When the date is the last
+    /// day of its month the range is made of whole months instead, so that trailing months
+    /// ending on Apr 30 start on the first day of a month rather than on the 31st.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::RangeTransitions;
+    ///
+    /// let d1 = NaiveDate::from_ymd_opt(2020, 3, 31).unwrap();
+    /// assert_eq!(d1.trailing_months(12).unwrap().start(), NaiveDate::from_ymd_opt(2019, 4, 1).unwrap());
+    /// let d2 = NaiveDate::from_ymd_opt(2020, 4, 30).unwrap();
+    /// assert_eq!(d2.trailing_months(1).unwrap().start(), NaiveDate::from_ymd_opt(2020, 4, 1).unwrap());
+    /// let d3 = NaiveDate::from_ymd_opt(2020, 3, 15).unwrap();
+    /// assert_eq!(d3.trailing_months(1).unwrap().start(), NaiveDate::from_ymd_opt(2020, 2, 16).unwrap());
+    fn trailing_months(&self, n: u32) -> Option<DateRange> {
+        let n = i32::try_from(n).ok().filter(|n| *n > 0)?;
+        let start = if self.day() == days_in_month(self.year(), self.month()) {
+            shift_months(self.with_day(1)?, 1 - n)?
+        } else {
+            shift_months(*self, -n)?.succ_opt()?
+        };
+        DateRange::new(start, *self)
+    }
+
+    /// Returns the range from the start of the current month up to the date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::range::RangeTransitions;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 8, 13).unwrap();
+    /// assert_eq!(d.month_to_date().unwrap().start(), NaiveDate::from_ymd_opt(2020, 8, 1).unwrap());
+    /// assert_eq!(d.quarter_to_date().unwrap().start(), NaiveDate::from_ymd_opt(2020, 7, 1).unwrap());
+    /// assert_eq!(d.year_to_date().unwrap().start(), NaiveDate::from_ymd_opt(2020, 1, 1).unwrap());
+    /// assert_eq!(d.year_to_date().unwrap().end(), d);
+    #[inline]
+    fn month_to_date(&self) -> Option<DateRange> {
+        DateRange::new(Unit::Month.start_of(*self)?, *self)
+    }
+
+    /// Returns the range from the start of the current quarter up to the date
+    #[inline]
+    fn quarter_to_date(&self) -> Option<DateRange> {
+        DateRange::new(Unit::Quarter.start_of(*self)?, *self)
+    }
+
+    /// Returns the range from the start of the current year up to the date
+    #[inline]
+    fn year_to_date(&self) -> Option<DateRange> {
+        DateRange::new(Unit::Year.start_of(*self)?, *self)
+    }
+
+    /// Returns the range from the start of the current period of the calendar up to the date.
+    /// Use it with a fiscal calendar to get fiscal quarter-to-date or year-to-date ranges.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::fiscal::FiscalCalendar;
+    /// use chrono_utilities::naive::period::Unit;
+    /// use chrono_utilities::naive::range::RangeTransitions;
+    ///
+    /// let fiscal = FiscalCalendar::new(4).unwrap();
+    /// let d = NaiveDate::from_ymd_opt(2020, 2, 14).unwrap();
+    /// let ytd = d.period_to_date(&fiscal, Unit::Year).unwrap();
+    /// assert_eq!(ytd.start(), NaiveDate::from_ymd_opt(2019, 4, 1).unwrap());
+    /// let qtd = d.period_to_date(&fiscal, Unit::Quarter).unwrap();
+    /// assert_eq!(qtd.start(), NaiveDate::from_ymd_opt(2020, 1, 1).unwrap());
+    #[inline]
+    fn period_to_date<C: PeriodCalendar>(&self, calendar: &C, unit: Unit) -> Option<DateRange> {
+        DateRange::new(calendar.period_of(*self, unit)?.start(), *self)
+    }
+}
+
+/// Iterator over the dates of a [DateRange](struct.DateRange.html) moving a fixed number of
+/// days at a time
+#[derive(Debug, Clone)]
+pub struct DayStep {
+    start: NaiveDate,
+    step: i64,
+    front: i64,
+    back: i64,
+}
+
+impl Iterator for DayStep {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        if self.front >= self.back {
+            return None;
+        }
+        let date = self.start + TimeDelta::days(self.front * self.step);
+        self.front += 1;
+        Some(date)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front).max(0) as usize;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<NaiveDate> {
+        self.front = self.front.saturating_add(n as i64).min(self.back);
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for DayStep {
+    fn next_back(&mut self) -> Option<NaiveDate> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.start + TimeDelta::days(self.back * self.step))
+    }
+}
+
+impl ExactSizeIterator for DayStep {}
+
+impl FusedIterator for DayStep {}
+
+/// Iterator over the dates of a [DateRange](struct.DateRange.html) moving a fixed number of
+/// months at a time
+#[derive(Debug, Clone)]
+pub struct MonthStep {
+    start: NaiveDate,
+    step: i64,
+    end_of_month: bool,
+    front: i64,
+    back: i64,
+}
+
+impl MonthStep {
+    fn nth_date(&self, index: i64) -> Option<NaiveDate> {
+        let months = i32::try_from(index.checked_mul(self.step)?).ok()?;
+        let date = shift_months(self.start, months)?;
+        if self.end_of_month {
+            date.with_day(days_in_month(date.year(), date.month()))
+        } else {
+            Some(date)
+        }
+    }
+}
+
+impl Iterator for MonthStep {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        if self.front >= self.back {
+            return None;
+        }
+        let date = self.nth_date(self.front);
+        self.front += 1;
+        date
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front).max(0) as usize;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<NaiveDate> {
+        self.front = self.front.saturating_add(n as i64).min(self.back);
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for MonthStep {
+    fn next_back(&mut self) -> Option<NaiveDate> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.nth_date(self.back)
+    }
+}
+
+impl ExactSizeIterator for MonthStep {}
+
+impl FusedIterator for MonthStep {}
+
+/// Iterator over the overlapping windows of a [DateRange](struct.DateRange.html). See
+/// [DateRange::sliding_windows](struct.DateRange.html#method.sliding_windows).
+#[derive(Debug, Clone)]
+pub struct SlidingWindows {
+    range: DateRange,
+    length: CalendarDuration,
+    step: CalendarDuration,
+    index: i32,
+}
+
+impl Iterator for SlidingWindows {
+    type Item = DateRange;
+
+    fn next(&mut self) -> Option<DateRange> {
+        let start = self
+            .step
+            .checked_mul(self.index)?
+            .add_to(self.range.start)?;
+        let end = self.length.add_to(start)?.pred_opt()?;
+        if end > self.range.end {
+            return None;
+        }
+        self.index += 1;
+        Some(DateRange { start, end })
+    }
+}
+
+impl FusedIterator for SlidingWindows {}
+
+#[cfg(test)]
+mod tests {
+    use super::DateRange;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_double_ended_exact_size() {
+        let range = DateRange::new(
+            NaiveDate::from_ymd_opt(2020, 1, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2020, 6, 15).unwrap(),
+        )
+        .unwrap();
+
+        let mut days = range.step_by_days(10);
+        assert_eq!(days.len(), 14);
+        assert_eq!(
+            days.next_back(),
+            Some(NaiveDate::from_ymd_opt(2020, 6, 9).unwrap())
+        );
+        assert_eq!(
+            days.next(),
+            Some(NaiveDate::from_ymd_opt(2020, 1, 31).unwrap())
+        );
+        assert_eq!(days.len(), 12);
+        assert_eq!(
+            days.nth(11),
+            Some(NaiveDate::from_ymd_opt(2020, 5, 30).unwrap())
+        );
+        assert_eq!(days.next_back(), None);
+        assert_eq!(days.len(), 0);
+
+        let months = range.step_by_months(1);
+        assert_eq!(months.len(), 5);
+        let reversed: Vec<NaiveDate> = months.rev().collect();
+        assert_eq!(
+            reversed,
+            vec![
+                NaiveDate::from_ymd_opt(2020, 5, 31).unwrap(),
+                NaiveDate::from_ymd_opt(2020, 4, 30).unwrap(),
+                NaiveDate::from_ymd_opt(2020, 3, 31).unwrap(),
+                NaiveDate::from_ymd_opt(2020, 2, 29).unwrap(),
+                NaiveDate::from_ymd_opt(2020, 1, 31).unwrap(),
+            ]
+        );
+
+        let all = DateRange::new(NaiveDate::MIN, NaiveDate::MAX).unwrap();
+        let months: Vec<NaiveDate> = all.step_by_months(u32::MAX).collect();
+        assert_eq!(months, vec![NaiveDate::MIN]);
+
+        let weeks: Vec<NaiveDate> = range.step_by_weeks(4).rev().collect();
+        assert_eq!(weeks.len(), range.step_by_weeks(4).len());
+        assert_eq!(weeks.last(), Some(&range.start()));
+        assert_eq!(range.iter().len() as i64, range.num_days());
+    }
+}
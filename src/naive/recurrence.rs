@@ -0,0 +1,1245 @@
+//! Recurrence rules in the style of the RFC 5545 RRULE, producing occurrence dates
+use super::business::{HolidayCalendar, RollConvention};
+use super::range::DateRange;
+use super::{days_in_month, shift_months};
+use chrono::{Datelike, NaiveDate, TimeDelta, Weekday};
+use std::collections::{BTreeSet, VecDeque};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+/// How often a [Recurrence](struct.Recurrence.html) repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// What a [Recurrence](struct.Recurrence.html) does with a day of the month that a month lacks,
+/// such as the 31st of April, as per the `SKIP` part of RFC 7529
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Skip {
+    /// Leave out the occurrence
+    Omit,
+    /// Move the occurrence to the last day of the month
+    Backward,
+    /// Move the occurrence to the first day of the following month
+    Forward,
+}
+
+/// A weekday, optionally restricted to its n-th occurrence within the month or year of a
+/// recurrence, such as the last Friday (`-1FR`). Negative ordinals count from the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeekdayNum {
+    /// The occurrence of the weekday within the period, or `None` for every occurrence
+    pub ordinal: Option<i32>,
+    /// The weekday
+    pub weekday: Weekday,
+}
+
+impl WeekdayNum {
+    /// Returns every occurrence of the weekday
+    #[inline]
+    pub fn every(weekday: Weekday) -> Self {
+        WeekdayNum {
+            ordinal: None,
+            weekday,
+        }
+    }
+
+    /// Returns the n-th occurrence of the weekday, counting from the end when negative
+    #[inline]
+    pub fn nth(ordinal: i32, weekday: Weekday) -> Self {
+        WeekdayNum {
+            ordinal: Some(ordinal),
+            weekday,
+        }
+    }
+}
+
+/// A recurrence rule supporting the `FREQ`, `INTERVAL`, `BYDAY`, `BYMONTHDAY`, `BYSETPOS`,
+/// `COUNT` and `UNTIL` parts of an RFC 5545 RRULE. Weeks start on Monday.
+///
+/// Every `interval` periods of the frequency, counting from the one containing the start of the
+/// recurrence, the dates matching the rule make up a set:
+///
+/// - `by_month_day` picks days of the month, negative days counting from the end of the month.
+///   It narrows down the days of daily and weekly rules.
+/// - `by_day` picks weekdays, narrowing down the days picked by `by_month_day` when both are
+///   given. Ordinals refer to the occurrence of the weekday within the month of monthly rules
+///   and within the year of yearly rules, and are ignored by daily and weekly rules.
+/// - Without either of them, weekly, monthly and yearly rules repeat on the weekday, the day
+///   of the month and the day of the year of the start.
+/// - Days of the month that a month lacks, such as the 31st of April, are left out unless the
+///   `skip` policy moves them to the end of the month or to the start of the next one.
+/// - `by_set_pos` then keeps the n-th dates of the set, counting from the end when negative.
+///
+/// Occurrences are the dates of these sets falling on or after the start, up to `until`
+/// included and limited to `count` occurrences. The start itself is only an occurrence if it
+/// matches the rule.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, Weekday};
+/// use chrono_utilities::naive::recurrence::{Frequency, Recurrence, WeekdayNum};
+/// use std::num::NonZeroU32;
+///
+/// // The last weekday of every month, three times
+/// let mut rule = Recurrence::new(Frequency::Monthly);
+/// rule.by_day = vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]
+///     .into_iter()
+///     .map(WeekdayNum::every)
+///     .collect();
+/// rule.by_set_pos = vec![-1];
+/// rule.count = Some(3);
+/// let dates: Vec<NaiveDate> = rule.occurrences(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()).collect();
+/// assert_eq!(dates, vec![
+///     NaiveDate::from_ymd_opt(2020, 1, 31).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 2, 28).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 3, 31).unwrap(),
+/// ]);
+///
+/// // Every other week on Tuesday and Thursday until the end of January
+/// let mut rule = Recurrence::new(Frequency::Weekly);
+/// rule.interval = NonZeroU32::new(2).unwrap();
+/// rule.by_day = vec![WeekdayNum::every(Weekday::Tue), WeekdayNum::every(Weekday::Thu)];
+/// rule.until = NaiveDate::from_ymd_opt(2020, 1, 31);
+/// let dates: Vec<NaiveDate> = rule.occurrences(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()).collect();
+/// assert_eq!(dates, vec![
+///     NaiveDate::from_ymd_opt(2020, 1, 2).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 14).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 16).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 28).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 30).unwrap(),
+/// ]);
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Recurrence {
+    /// The periods the rule repeats over
+    pub frequency: Frequency,
+    /// The number of periods between two periods holding occurrences
+    pub interval: NonZeroU32,
+    /// The weekdays of the occurrences
+    pub by_day: Vec<WeekdayNum>,
+    /// The days of the month of the occurrences
+    pub by_month_day: Vec<i32>,
+    /// The positions of the occurrences within the dates of each period matching the rule
+    pub by_set_pos: Vec<i32>,
+    /// The maximum number of occurrences
+    pub count: Option<u32>,
+    /// The last date on which an occurrence may fall
+    pub until: Option<NaiveDate>,
+    /// What to do with the days of the month that a month lacks
+    pub skip: Skip,
+}
+
+impl Recurrence {
+    /// Creates a rule repeating every period of the given frequency, without limits
+    pub fn new(frequency: Frequency) -> Self {
+        Recurrence {
+            frequency,
+            interval: NonZeroU32::new(1).unwrap(),
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_set_pos: Vec::new(),
+            count: None,
+            until: None,
+            skip: Skip::Omit,
+        }
+    }
+
+    /// Creates a rule repeating every day. Together with the methods refining it, this builds
+    /// rules without going through their RRULE syntax.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::recurrence::Recurrence;
+    ///
+    /// let rule = Recurrence::weekly().on(Weekday::Tue).on(Weekday::Thu).every(2).times(4);
+    /// assert_eq!(rule, "FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,TH".parse().unwrap());
+    /// let rule = Recurrence::monthly().on_nth(-1, Weekday::Fri).until(NaiveDate::from_ymd_opt(2020, 3, 31).unwrap());
+    /// assert_eq!(rule.to_string(), "FREQ=MONTHLY;UNTIL=20200331;BYDAY=-1FR");
+    /// let rule = Recurrence::yearly().on_month_day(-1).at_position(-1);
+    /// assert_eq!(rule.occurrences(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()).next(), NaiveDate::from_ymd_opt(2020, 12, 31));
+    #[inline]
+    pub fn daily() -> Self {
+        Recurrence::new(Frequency::Daily)
+    }
+
+    /// Creates a rule repeating every week
+    #[inline]
+    pub fn weekly() -> Self {
+        Recurrence::new(Frequency::Weekly)
+    }
+
+    /// Creates a rule repeating every month
+    #[inline]
+    pub fn monthly() -> Self {
+        Recurrence::new(Frequency::Monthly)
+    }
+
+    /// Creates a rule repeating every year
+    #[inline]
+    pub fn yearly() -> Self {
+        Recurrence::new(Frequency::Yearly)
+    }
+
+    /// Returns the rule repeating every `interval` periods
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn every(mut self, interval: u32) -> Self {
+        self.interval = NonZeroU32::new(interval).expect("interval must be non-zero");
+        self
+    }
+
+    /// Returns the rule also occurring on every occurrence of the weekday
+    pub fn on(mut self, weekday: Weekday) -> Self {
+        self.by_day.push(WeekdayNum::every(weekday));
+        self
+    }
+
+    /// Returns the rule also occurring on the n-th occurrence of the weekday within the month
+    /// or year, counting from the end when negative
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ordinal` is zero or does not lie within `-53..=53`.
+    pub fn on_nth(mut self, ordinal: i32, weekday: Weekday) -> Self {
+        assert!(
+            ordinal != 0 && (-53..=53).contains(&ordinal),
+            "ordinal must be non-zero and within -53..=53"
+        );
+        self.by_day.push(WeekdayNum::nth(ordinal, weekday));
+        self
+    }
+
+    /// Returns the rule also occurring on the day of the month, counting from the end when
+    /// negative
+    ///
+    /// # Panics
+    ///
+    /// Panics if `day` is zero or does not lie within `-31..=31`.
+    pub fn on_month_day(mut self, day: i32) -> Self {
+        assert!(
+            day != 0 && (-31..=31).contains(&day),
+            "day must be non-zero and within -31..=31"
+        );
+        self.by_month_day.push(day);
+        self
+    }
+
+    /// Returns the rule also keeping the n-th date of every period matching it, counting from
+    /// the end when negative
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is zero or does not lie within `-366..=366`.
+    pub fn at_position(mut self, position: i32) -> Self {
+        assert!(
+            position != 0 && (-366..=366).contains(&position),
+            "position must be non-zero and within -366..=366"
+        );
+        self.by_set_pos.push(position);
+        self
+    }
+
+    /// Returns the rule handling the days of the month that a month lacks as per the policy
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::recurrence::{Recurrence, Skip};
+    ///
+    /// // The 31st of each month, or the last day if the month is shorter
+    /// let rule = Recurrence::monthly().on_month_day(31).skip(Skip::Backward);
+    /// let dates: Vec<NaiveDate> = rule.occurrences(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()).take(3).collect();
+    /// assert_eq!(dates, vec![
+    ///     NaiveDate::from_ymd_opt(2020, 1, 31).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 2, 29).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 3, 31).unwrap(),
+    /// ]);
+    /// assert_eq!(rule.to_string(), "FREQ=MONTHLY;BYMONTHDAY=31;SKIP=BACKWARD");
+    pub fn skip(mut self, skip: Skip) -> Self {
+        self.skip = skip;
+        self
+    }
+
+    /// Returns the rule limited to `count` occurrences, replacing any end date
+    pub fn times(mut self, count: u32) -> Self {
+        self.count = Some(count);
+        self.until = None;
+        self
+    }
+
+    /// Returns the rule ending on the given date, replacing any limit on its number of
+    /// occurrences
+    pub fn until(mut self, until: NaiveDate) -> Self {
+        self.until = Some(until);
+        self.count = None;
+        self
+    }
+
+    /// Returns an iterator over the occurrences of the rule starting from the given date, in
+    /// chronological order
+    pub fn occurrences(&self, start: NaiveDate) -> Occurrences {
+        Occurrences {
+            rule: self.clone(),
+            start,
+            period: 0,
+            pending: VecDeque::new(),
+            last: None,
+            emitted: 0,
+            done: false,
+        }
+    }
+
+    /// Returns the first date of the given period, counting periods of the rule from the one
+    /// containing the start
+    fn period_start(&self, start: NaiveDate, period: u32) -> Option<NaiveDate> {
+        let steps = period.checked_mul(self.interval.get())?;
+        match self.frequency {
+            Frequency::Daily => start.checked_add_signed(TimeDelta::days(steps as i64)),
+            Frequency::Weekly => {
+                let monday = start.checked_sub_signed(TimeDelta::days(
+                    start.weekday().num_days_from_monday() as i64,
+                ))?;
+                monday.checked_add_signed(TimeDelta::weeks(steps as i64))
+            }
+            Frequency::Monthly => {
+                let first = NaiveDate::from_ymd_opt(start.year(), start.month(), 1)?;
+                shift_months(first, i32::try_from(steps).ok()?)
+            }
+            Frequency::Yearly => {
+                NaiveDate::from_ymd_opt(start.year().checked_add(i32::try_from(steps).ok()?)?, 1, 1)
+            }
+        }
+    }
+
+    /// Returns the dates of the period starting on the given date which match the rule, in
+    /// chronological order
+    fn dates_of(&self, period_start: NaiveDate, start: NaiveDate) -> Vec<NaiveDate> {
+        let mut dates: Vec<NaiveDate> = match self.frequency {
+            Frequency::Daily => vec![period_start],
+            Frequency::Weekly => (0..7)
+                .filter_map(|days| period_start.checked_add_signed(TimeDelta::days(days)))
+                .filter(|date| !self.by_day.is_empty() || date.weekday() == start.weekday())
+                .collect(),
+            Frequency::Monthly => {
+                let (year, month) = (period_start.year(), period_start.month());
+                if !self.by_month_day.is_empty() {
+                    self.month_days(year, month)
+                } else if !self.by_day.is_empty() {
+                    days_of_month(year, month)
+                } else {
+                    self.month_day(year, month, start.day() as i32)
+                        .into_iter()
+                        .collect()
+                }
+            }
+            Frequency::Yearly => {
+                let year = period_start.year();
+                if !self.by_month_day.is_empty() {
+                    (1..=12)
+                        .flat_map(|month| self.month_days(year, month))
+                        .collect()
+                } else if !self.by_day.is_empty() {
+                    (1..=12)
+                        .flat_map(|month| days_of_month(year, month))
+                        .collect()
+                } else {
+                    self.month_day(year, start.month(), start.day() as i32)
+                        .into_iter()
+                        .collect()
+                }
+            }
+        };
+        if matches!(self.frequency, Frequency::Daily | Frequency::Weekly)
+            && !self.by_month_day.is_empty()
+        {
+            dates.retain(|date| {
+                self.by_month_day
+                    .iter()
+                    .any(|day| month_day_matches(*day, *date))
+            });
+        }
+        if !self.by_day.is_empty() {
+            dates.retain(|date| {
+                self.by_day
+                    .iter()
+                    .any(|day| self.weekday_matches(*day, *date))
+            });
+        }
+        dates.sort();
+        dates.dedup();
+        if !self.by_set_pos.is_empty() {
+            let len = dates.len() as i64;
+            let mut picked: Vec<NaiveDate> = self
+                .by_set_pos
+                .iter()
+                .filter_map(|pos| {
+                    let index = if *pos > 0 {
+                        *pos as i64 - 1
+                    } else {
+                        len + *pos as i64
+                    };
+                    usize::try_from(index)
+                        .ok()
+                        .and_then(|index| dates.get(index).copied())
+                })
+                .collect();
+            picked.sort();
+            picked.dedup();
+            dates = picked;
+        }
+        dates
+    }
+
+    /// Returns the dates of the month picked by `by_month_day`
+    fn month_days(&self, year: i32, month: u32) -> Vec<NaiveDate> {
+        self.by_month_day
+            .iter()
+            .filter_map(|day| self.month_day(year, month, *day))
+            .collect()
+    }
+
+    /// Returns the given day of the month, counting from the end if negative. Days the month
+    /// lacks are handled as per the skip policy of the rule.
+    fn month_day(&self, year: i32, month: u32, day: i32) -> Option<NaiveDate> {
+        let last = days_in_month(year, month) as i32;
+        let resolved = if day < 0 { last + 1 + day } else { day };
+        if (1..=last).contains(&resolved) {
+            return NaiveDate::from_ymd_opt(year, month, resolved as u32);
+        }
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        match (self.skip, resolved > last) {
+            (Skip::Omit, _) => None,
+            (Skip::Backward, true) => NaiveDate::from_ymd_opt(year, month, last as u32),
+            (Skip::Backward, false) => first.pred_opt(),
+            (Skip::Forward, true) => shift_months(first, 1),
+            (Skip::Forward, false) => Some(first),
+        }
+    }
+
+    /// Returns true if the date falls on the weekday, and on its n-th occurrence within the
+    /// month or year for monthly and yearly rules
+    fn weekday_matches(&self, day: WeekdayNum, date: NaiveDate) -> bool {
+        if date.weekday() != day.weekday {
+            return false;
+        }
+        let ordinal = match day.ordinal {
+            Some(ordinal) => ordinal,
+            None => return true,
+        };
+        let (index, length) = match self.frequency {
+            Frequency::Monthly => (date.day0(), days_in_month(date.year(), date.month())),
+            Frequency::Yearly => (date.ordinal0(), if date.leap_year() { 366 } else { 365 }),
+            _ => return true,
+        };
+        if ordinal > 0 {
+            (index / 7 + 1) as i32 == ordinal
+        } else {
+            -(((length - 1 - index) / 7 + 1) as i32) == ordinal
+        }
+    }
+
+    /// Returns the number of consecutive periods without occurrences after which the rule is
+    /// deemed to have none left, spanning a whole 400 year cycle of the Gregorian calendar
+    fn max_empty_periods(&self) -> u32 {
+        match self.frequency {
+            Frequency::Daily => 146_097,
+            Frequency::Weekly => 20_872,
+            Frequency::Monthly => 4_800,
+            Frequency::Yearly => 400,
+        }
+    }
+}
+
+/// Returns true if the date falls on the day of the month, counting from the end if negative
+fn month_day_matches(day: i32, date: NaiveDate) -> bool {
+    let last = days_in_month(date.year(), date.month()) as i32;
+    date.day() as i32 == if day < 0 { last + 1 + day } else { day }
+}
+
+/// Returns every date of the month
+fn days_of_month(year: i32, month: u32) -> Vec<NaiveDate> {
+    (1..=days_in_month(year, month))
+        .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .collect()
+}
+
+impl fmt::Display for WeekdayNum {
+    /// Formats the weekday as in RFC 5545, such as `-1FR` or `MO`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ordinal) = self.ordinal {
+            write!(f, "{}", ordinal)?;
+        }
+        f.write_str(match self.weekday {
+            Weekday::Mon => "MO",
+            Weekday::Tue => "TU",
+            Weekday::Wed => "WE",
+            Weekday::Thu => "TH",
+            Weekday::Fri => "FR",
+            Weekday::Sat => "SA",
+            Weekday::Sun => "SU",
+        })
+    }
+}
+
+impl FromStr for WeekdayNum {
+    type Err = ParseRecurrenceError;
+
+    /// Parses a weekday formatted as in RFC 5545, such as `-1FR`, `+2MO` or `SU`
+    fn from_str(s: &str) -> Result<Self, ParseRecurrenceError> {
+        let err = ParseRecurrenceError { part: "BYDAY" };
+        let split = s
+            .len()
+            .checked_sub(2)
+            .filter(|split| s.is_char_boundary(*split));
+        let (ordinal, weekday) = s.split_at(split.ok_or(err)?);
+        let weekday = match weekday {
+            "MO" => Weekday::Mon,
+            "TU" => Weekday::Tue,
+            "WE" => Weekday::Wed,
+            "TH" => Weekday::Thu,
+            "FR" => Weekday::Fri,
+            "SA" => Weekday::Sat,
+            "SU" => Weekday::Sun,
+            _ => return Err(err),
+        };
+        if ordinal.is_empty() {
+            return Ok(WeekdayNum::every(weekday));
+        }
+        let ordinal: i32 = ordinal.parse().map_err(|_| err)?;
+        if ordinal == 0 || !(-53..=53).contains(&ordinal) {
+            return Err(err);
+        }
+        Ok(WeekdayNum::nth(ordinal, weekday))
+    }
+}
+
+impl fmt::Display for Recurrence {
+    /// Formats the rule as an RFC 5545 RRULE value, such as `FREQ=MONTHLY;BYDAY=-1FR`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.frequency {
+            Frequency::Daily => "FREQ=DAILY",
+            Frequency::Weekly => "FREQ=WEEKLY",
+            Frequency::Monthly => "FREQ=MONTHLY",
+            Frequency::Yearly => "FREQ=YEARLY",
+        })?;
+        if self.interval.get() != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        write_list(f, "BYDAY", &self.by_day)?;
+        write_list(f, "BYMONTHDAY", &self.by_month_day)?;
+        write_list(f, "BYSETPOS", &self.by_set_pos)?;
+        match self.skip {
+            Skip::Omit => Ok(()),
+            Skip::Backward => f.write_str(";SKIP=BACKWARD"),
+            Skip::Forward => f.write_str(";SKIP=FORWARD"),
+        }
+    }
+}
+
+/// Writes a comma separated rule part, unless it has no values
+fn write_list<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    values: &[T],
+) -> fmt::Result {
+    for (index, value) in values.iter().enumerate() {
+        if index == 0 {
+            write!(f, ";{}=", name)?;
+        } else {
+            f.write_str(",")?;
+        }
+        write!(f, "{}", value)?;
+    }
+    Ok(())
+}
+
+impl FromStr for Recurrence {
+    type Err = ParseRecurrenceError;
+
+    /// Parses an RFC 5545 RRULE value, optionally prefixed with `RRULE:`. Only the parts
+    /// supported by [Recurrence](struct.Recurrence.html) are accepted, including the `SKIP` part
+    /// of RFC 7529, along with a `WKST` part set to Monday. The time of an `UNTIL` datetime is ignored.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::recurrence::{Frequency, Recurrence, WeekdayNum};
+    ///
+    /// let rule: Recurrence = "FREQ=MONTHLY;BYDAY=-1FR".parse().unwrap();
+    /// assert_eq!(rule.frequency, Frequency::Monthly);
+    /// assert_eq!(rule.by_day, vec![WeekdayNum::nth(-1, Weekday::Fri)]);
+    /// let next = rule.occurrences(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()).next();
+    /// assert_eq!(next, NaiveDate::from_ymd_opt(2020, 1, 31));
+    ///
+    /// let rule: Recurrence = "RRULE:FREQ=WEEKLY;UNTIL=20200131T235959Z;INTERVAL=2;BYDAY=TU,TH".parse().unwrap();
+    /// assert_eq!(rule.until, NaiveDate::from_ymd_opt(2020, 1, 31));
+    /// assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;UNTIL=20200131;BYDAY=TU,TH");
+    /// assert_eq!(rule.to_string().parse::<Recurrence>().unwrap(), rule);
+    ///
+    /// assert!("FREQ=HOURLY".parse::<Recurrence>().is_err());
+    /// assert!("FREQ=DAILY;COUNT=3;UNTIL=20200101".parse::<Recurrence>().is_err());
+    /// assert!("BYDAY=MO".parse::<Recurrence>().is_err());
+    fn from_str(s: &str) -> Result<Self, ParseRecurrenceError> {
+        let s = s.strip_prefix("RRULE:").unwrap_or(s);
+        let mut frequency = None;
+        let mut rule = Recurrence::new(Frequency::Daily);
+        let mut seen: Vec<&str> = Vec::new();
+        for part in s.split(';') {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or(ParseRecurrenceError { part: "rule" })?;
+            let part = PARTS.iter().find(|part| **part == name);
+            let err = ParseRecurrenceError {
+                part: part.copied().unwrap_or("rule"),
+            };
+            if seen.contains(&name) {
+                return Err(err);
+            }
+            seen.push(name);
+            match name {
+                "FREQ" => {
+                    frequency = Some(match value {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(err),
+                    })
+                }
+                "INTERVAL" => rule.interval = value.parse().map_err(|_| err)?,
+                "COUNT" => rule.count = Some(value.parse().map_err(|_| err)?),
+                "UNTIL" => rule.until = Some(parse_ical_date(value).ok_or(err)?),
+                "BYDAY" => {
+                    rule.by_day = value.split(',').map(str::parse).collect::<Result<_, _>>()?
+                }
+                "BYMONTHDAY" => rule.by_month_day = parse_numbers(value, 31).ok_or(err)?,
+                "BYSETPOS" => rule.by_set_pos = parse_numbers(value, 366).ok_or(err)?,
+                "SKIP" => {
+                    rule.skip = match value {
+                        "OMIT" => Skip::Omit,
+                        "BACKWARD" => Skip::Backward,
+                        "FORWARD" => Skip::Forward,
+                        _ => return Err(err),
+                    }
+                }
+                "WKST" if value == "MO" => {}
+                _ => return Err(err),
+            }
+        }
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err(ParseRecurrenceError { part: "rule" });
+        }
+        rule.frequency = frequency.ok_or(ParseRecurrenceError { part: "FREQ" })?;
+        Ok(rule)
+    }
+}
+
+/// Parses an iCalendar date such as `20200131`, or the date of a datetime such as
+/// `20200131T235959Z`
+fn parse_ical_date(value: &str) -> Option<NaiveDate> {
+    let date = value.get(..8)?;
+    if value.len() != 8 && value.as_bytes().get(8) != Some(&b'T') {
+        return None;
+    }
+    NaiveDate::parse_from_str(date, "%Y%m%d").ok()
+}
+
+/// Names of the RRULE parts accepted when parsing a rule
+const PARTS: [&str; 9] = [
+    "FREQ",
+    "INTERVAL",
+    "COUNT",
+    "UNTIL",
+    "BYDAY",
+    "BYMONTHDAY",
+    "BYSETPOS",
+    "SKIP",
+    "WKST",
+];
+
+/// Parses a comma separated list of non-zero numbers lying within `-max..=max`
+fn parse_numbers(value: &str, max: i32) -> Option<Vec<i32>> {
+    value
+        .split(',')
+        .map(|n| {
+            n.parse()
+                .ok()
+                .filter(|n: &i32| *n != 0 && (-max..=max).contains(n))
+        })
+        .collect()
+}
+
+/// Error returned when parsing an invalid recurrence rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseRecurrenceError {
+    part: &'static str,
+}
+
+impl fmt::Display for ParseRecurrenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid recurrence {}", self.part)
+    }
+}
+
+impl Error for ParseRecurrenceError {}
+
+/// The recurrence of an iCalendar event, made of its `DTSTART`, `RRULE`, `RDATE` and `EXDATE`
+/// components. Its occurrences are the start, the dates of the rules and the extra dates,
+/// without the excluded dates. As in RFC 5545, the start counts as the first occurrence of every
+/// rule limited by a count, even when it does not match the rule, so that a rule with a `COUNT`
+/// of 3 yields 3 occurrences including the start.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::range::DateRange;
+/// use chrono_utilities::naive::recurrence::RecurrenceSet;
+///
+/// let event: RecurrenceSet = "BEGIN:VEVENT\r\n\
+///     DTSTART;TZID=Europe/Paris:20200106T090000\r\n\
+///     RRULE:FREQ=WEEKLY;BYDAY=MO\r\n\
+///     EXDATE;TZID=Europe/Paris:20200113T090000,20200127T090000\r\n\
+///     RDATE;VALUE=DATE:20200122\r\n\
+///     END:VEVENT".parse().unwrap();
+/// let january = DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap();
+/// assert_eq!(event.occurrences_within(january), vec![
+///     NaiveDate::from_ymd_opt(2020, 1, 6).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 20).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 22).unwrap(),
+/// ]);
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecurrenceSet {
+    /// The date of the first occurrence
+    pub start: NaiveDate,
+    /// The rules the occurrences follow, counting from the start
+    pub rules: Vec<Recurrence>,
+    /// Extra dates of occurrences
+    pub rdates: BTreeSet<NaiveDate>,
+    /// Dates excluded from the occurrences
+    pub exdates: BTreeSet<NaiveDate>,
+}
+
+impl RecurrenceSet {
+    /// Creates a set holding a single occurrence on the start date
+    pub fn new(start: NaiveDate) -> Self {
+        RecurrenceSet {
+            start,
+            rules: Vec::new(),
+            rdates: BTreeSet::new(),
+            exdates: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the occurrences falling within the window, in chronological order
+    pub fn occurrences_within(&self, window: DateRange) -> Vec<NaiveDate> {
+        let mut dates: BTreeSet<NaiveDate> = self
+            .rdates
+            .range(window.start()..=window.end())
+            .copied()
+            .collect();
+        dates.extend(Some(self.start).filter(|start| window.contains(*start)));
+        for rule in self.rules.iter() {
+            let mut occurrences = rule.occurrences(self.start).peekable();
+            let limit = match rule.count {
+                Some(count) if occurrences.peek() != Some(&self.start) => {
+                    count.saturating_sub(1) as usize
+                }
+                _ => usize::MAX,
+            };
+            dates.extend(
+                occurrences
+                    .take(limit)
+                    .take_while(|date| *date <= window.end())
+                    .filter(|date| *date >= window.start()),
+            );
+        }
+        dates
+            .into_iter()
+            .filter(|date| !self.exdates.contains(date))
+            .collect()
+    }
+}
+
+impl FromStr for RecurrenceSet {
+    type Err = ParseRecurrenceError;
+
+    /// Parses the `DTSTART`, `RRULE`, `RDATE` and `EXDATE` content lines of an iCalendar event,
+    /// ignoring its other lines. Datetimes are reduced to their date, regardless of their time
+    /// zone, and a `DTSTART` line is required.
+    fn from_str(s: &str) -> Result<Self, ParseRecurrenceError> {
+        let mut start = None;
+        let mut rules = Vec::new();
+        let mut rdates = BTreeSet::new();
+        let mut exdates = BTreeSet::new();
+        for line in s.lines() {
+            let (name, value) = match line.split_once(':') {
+                Some((name, value)) => (name, value),
+                None => continue,
+            };
+            let (name, params) = name.split_once(';').unwrap_or((name, ""));
+            let part = match name {
+                "DTSTART" => "DTSTART",
+                "RRULE" => "RRULE",
+                "RDATE" => "RDATE",
+                "EXDATE" => "EXDATE",
+                _ => continue,
+            };
+            let err = ParseRecurrenceError { part };
+            if params.split(';').any(|param| param == "VALUE=PERIOD") {
+                return Err(err);
+            }
+            let dates = value
+                .split(',')
+                .map(|date| parse_ical_date(date).ok_or(err));
+            match name {
+                "DTSTART" if start.is_none() => start = Some(parse_ical_date(value).ok_or(err)?),
+                "RRULE" => rules.push(value.parse()?),
+                "RDATE" => rdates.extend(dates.collect::<Result<Vec<_>, _>>()?),
+                "EXDATE" => exdates.extend(dates.collect::<Result<Vec<_>, _>>()?),
+                _ => return Err(err),
+            }
+        }
+        Ok(RecurrenceSet {
+            start: start.ok_or(ParseRecurrenceError { part: "DTSTART" })?,
+            rules,
+            rdates,
+            exdates,
+        })
+    }
+}
+
+/// Iterator over the occurrences of a [Recurrence](struct.Recurrence.html)
+#[derive(Debug, Clone)]
+pub struct Occurrences {
+    rule: Recurrence,
+    start: NaiveDate,
+    period: u32,
+    pending: VecDeque<NaiveDate>,
+    last: Option<NaiveDate>,
+    emitted: u32,
+    done: bool,
+}
+
+impl Iterator for Occurrences {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        if self.rule.count.is_some_and(|count| self.emitted >= count) {
+            self.done = true;
+        }
+        let mut empty_periods = 0;
+        while !self.done && self.pending.is_empty() {
+            let period_start = match self.rule.period_start(self.start, self.period) {
+                Some(period_start) => period_start,
+                None => break,
+            };
+            if self.rule.until.is_some_and(|until| period_start > until) {
+                break;
+            }
+            self.period += 1;
+            // Days moved by the skip policy may fall before dates already returned
+            let first = self
+                .last
+                .and_then(|last| last.succ_opt())
+                .map_or(self.start, |next| next.max(self.start));
+            self.pending.extend(
+                self.rule
+                    .dates_of(period_start, self.start)
+                    .into_iter()
+                    .filter(|date| *date >= first),
+            );
+            empty_periods += 1;
+            if empty_periods > self.rule.max_empty_periods() {
+                break;
+            }
+        }
+        // Periods may hold several dates, some of which are past the count
+        let within_count = self.rule.count.map_or(true, |count| self.emitted < count);
+        match self.pending.pop_front() {
+            Some(date) if within_count && self.rule.until.map_or(true, |until| date <= until) => {
+                self.last = Some(date);
+                self.emitted += 1;
+                Some(date)
+            }
+            _ => {
+                self.done = true;
+                self.pending.clear();
+                None
+            }
+        }
+    }
+}
+
+impl Occurrences {
+    /// Returns an iterator passing every occurrence through `adjust`, which may move it to
+    /// another date or leave it out by returning `None`. The count and end date of the rule
+    /// apply to the occurrences before they are adjusted. Occurrences adjusted onto or before a
+    /// date already returned are left out.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{Datelike, NaiveDate, Weekday};
+    /// use chrono_utilities::naive::recurrence::Recurrence;
+    ///
+    /// // The 1st of each month, moved to the next day when on a Sunday
+    /// let rule = Recurrence::monthly().times(3);
+    /// let dates: Vec<NaiveDate> = rule
+    ///     .occurrences(NaiveDate::from_ymd_opt(2020, 2, 1).unwrap())
+    ///     .adjusted(|date| if date.weekday() == Weekday::Sun { date.succ_opt() } else { Some(date) })
+    ///     .collect();
+    /// assert_eq!(dates, vec![
+    ///     NaiveDate::from_ymd_opt(2020, 2, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 3, 2).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(),
+    /// ]);
+    pub fn adjusted<F>(self, adjust: F) -> Adjusted<F>
+    where
+        F: FnMut(NaiveDate) -> Option<NaiveDate>,
+    {
+        Adjusted {
+            occurrences: self,
+            adjust,
+            last: None,
+        }
+    }
+
+    /// Returns an iterator moving every occurrence falling on a non-business day of the calendar
+    /// to a business day as per the convention, as with
+    /// [adjusted](struct.Occurrences.html#method.adjusted)
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::business::{HolidayCalendar, RollConvention};
+    /// use chrono_utilities::naive::recurrence::Recurrence;
+    ///
+    /// // The 15th of each month, or the previous business day
+    /// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 6, 15).unwrap()].into_iter().collect();
+    /// let rule = Recurrence::monthly().on_month_day(15);
+    /// let dates: Vec<NaiveDate> = rule
+    ///     .occurrences(NaiveDate::from_ymd_opt(2020, 2, 1).unwrap())
+    ///     .rolled(&cal, RollConvention::Preceding)
+    ///     .step_by(4)
+    ///     .take(2)
+    ///     .collect();
+    /// assert_eq!(dates, vec![
+    ///     NaiveDate::from_ymd_opt(2020, 2, 14).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 6, 12).unwrap(),
+    /// ]);
+    /// // The last day of each month, adjusted modified following
+    /// let rule = Recurrence::monthly().on_month_day(-1);
+    /// let dates: Vec<NaiveDate> = rule
+    ///     .occurrences(NaiveDate::from_ymd_opt(2020, 5, 1).unwrap())
+    ///     .rolled(&cal, RollConvention::ModifiedFollowing)
+    ///     .take(2)
+    ///     .collect();
+    /// assert_eq!(dates, vec![
+    ///     NaiveDate::from_ymd_opt(2020, 5, 29).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2020, 6, 30).unwrap(),
+    /// ]);
+    pub fn rolled(
+        self,
+        calendar: &HolidayCalendar,
+        convention: RollConvention,
+    ) -> Adjusted<impl FnMut(NaiveDate) -> Option<NaiveDate> + '_> {
+        self.adjusted(move |date| calendar.roll(date, convention))
+    }
+}
+
+/// Iterator over the adjusted occurrences of a [Recurrence](struct.Recurrence.html), returned
+/// by [Occurrences::adjusted](struct.Occurrences.html#method.adjusted)
+#[derive(Debug, Clone)]
+pub struct Adjusted<F> {
+    occurrences: Occurrences,
+    adjust: F,
+    last: Option<NaiveDate>,
+}
+
+impl<F> Iterator for Adjusted<F>
+where
+    F: FnMut(NaiveDate) -> Option<NaiveDate>,
+{
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        loop {
+            let date = self.occurrences.next()?;
+            match (self.adjust)(date) {
+                Some(adjusted) if self.last.map_or(true, |last| adjusted > last) => {
+                    self.last = Some(adjusted);
+                    return Some(adjusted);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frequency, Recurrence, RecurrenceSet, Skip, WeekdayNum};
+    use crate::naive::range::DateRange;
+    use chrono::{Datelike, NaiveDate, Weekday};
+    use std::num::NonZeroU32;
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn first(rule: &Recurrence, start: NaiveDate, n: usize) -> Vec<NaiveDate> {
+        rule.occurrences(start).take(n).collect()
+    }
+
+    #[test]
+    fn test_monthly_rules() {
+        // Friday the 13th
+        let mut rule = Recurrence::new(Frequency::Monthly);
+        rule.by_day = vec![WeekdayNum::every(Weekday::Fri)];
+        rule.by_month_day = vec![13];
+        assert_eq!(
+            first(&rule, ymd(2020, 1, 1), 3),
+            vec![ymd(2020, 3, 13), ymd(2020, 11, 13), ymd(2021, 8, 13)]
+        );
+        // The 31st skips shorter months, -1 picks the last day of every month
+        let mut rule = Recurrence::new(Frequency::Monthly);
+        rule.by_month_day = vec![31];
+        assert_eq!(
+            first(&rule, ymd(2020, 1, 31), 3),
+            vec![ymd(2020, 1, 31), ymd(2020, 3, 31), ymd(2020, 5, 31)]
+        );
+        rule.by_month_day = vec![-1];
+        assert_eq!(
+            first(&rule, ymd(2020, 1, 31), 2),
+            vec![ymd(2020, 1, 31), ymd(2020, 2, 29)]
+        );
+        // The second to last Monday, every third month
+        let mut rule = Recurrence::new(Frequency::Monthly);
+        rule.interval = NonZeroU32::new(3).unwrap();
+        rule.by_day = vec![WeekdayNum::nth(-2, Weekday::Mon)];
+        assert_eq!(
+            first(&rule, ymd(2020, 1, 1), 2),
+            vec![ymd(2020, 1, 20), ymd(2020, 4, 20)]
+        );
+    }
+
+    #[test]
+    fn test_skip() {
+        let rule = Recurrence::monthly().skip(Skip::Forward);
+        assert_eq!(
+            first(&rule, ymd(2021, 1, 31), 3),
+            vec![ymd(2021, 1, 31), ymd(2021, 3, 1), ymd(2021, 3, 31)]
+        );
+        // Both the 30th and the 31st move onto the same day in February
+        let rule = Recurrence::monthly()
+            .on_month_day(30)
+            .on_month_day(31)
+            .skip(Skip::Backward);
+        assert_eq!(
+            first(&rule, ymd(2021, 1, 30), 4),
+            vec![
+                ymd(2021, 1, 30),
+                ymd(2021, 1, 31),
+                ymd(2021, 2, 28),
+                ymd(2021, 3, 30)
+            ]
+        );
+        let rule = Recurrence::yearly().skip(Skip::Backward);
+        assert_eq!(
+            first(&rule, ymd(2020, 2, 29), 2),
+            vec![ymd(2020, 2, 29), ymd(2021, 2, 28)]
+        );
+        assert_eq!(
+            "FREQ=MONTHLY;SKIP=FORWARD".parse::<Recurrence>(),
+            Ok(Recurrence::monthly().skip(Skip::Forward))
+        );
+    }
+
+    #[test]
+    fn test_yearly_rules() {
+        let rule = Recurrence::new(Frequency::Yearly);
+        assert_eq!(
+            first(&rule, ymd(2020, 2, 29), 2),
+            vec![ymd(2020, 2, 29), ymd(2024, 2, 29)]
+        );
+        // The 20th Monday of the year and the last day of the year
+        let mut rule = Recurrence::new(Frequency::Yearly);
+        rule.by_day = vec![WeekdayNum::nth(20, Weekday::Mon)];
+        assert_eq!(first(&rule, ymd(1997, 1, 1), 1), vec![ymd(1997, 5, 19)]);
+        let mut rule = Recurrence::new(Frequency::Yearly);
+        rule.by_day = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]
+        .iter()
+        .map(|weekday| WeekdayNum::every(*weekday))
+        .collect();
+        rule.by_set_pos = vec![-1];
+        assert_eq!(
+            first(&rule, ymd(2020, 6, 1), 2),
+            vec![ymd(2020, 12, 31), ymd(2021, 12, 31)]
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut rule = Recurrence::new(Frequency::Daily);
+        rule.by_day = vec![WeekdayNum::every(Weekday::Sat)];
+        rule.count = Some(2);
+        assert_eq!(
+            first(&rule, ymd(2020, 1, 1), 5),
+            vec![ymd(2020, 1, 4), ymd(2020, 1, 11)]
+        );
+        rule.count = None;
+        rule.until = Some(ymd(2020, 1, 11));
+        assert_eq!(rule.occurrences(ymd(2020, 1, 1)).count(), 2);
+        // Rules which cannot match end instead of looping forever
+        let mut rule = Recurrence::new(Frequency::Monthly);
+        rule.by_month_day = vec![30];
+        rule.by_day = vec![WeekdayNum::nth(1, Weekday::Mon)];
+        assert_eq!(rule.occurrences(ymd(2020, 1, 1)).next(), None);
+        assert!("FREQ=DAILY;INTERVAL=0".parse::<Recurrence>().is_err());
+        // Iteration stops at the end of the supported range
+        let rule = Recurrence::new(Frequency::Yearly);
+        let last_year = NaiveDate::MAX.year();
+        assert_eq!(rule.occurrences(ymd(last_year - 1, 1, 1)).count(), 2);
+    }
+
+    #[test]
+    fn test_count_with_several_dates_per_period() {
+        let mut rule = Recurrence::new(Frequency::Weekly);
+        rule.by_day = vec![
+            WeekdayNum::every(Weekday::Tue),
+            WeekdayNum::every(Weekday::Thu),
+        ];
+        rule.count = Some(1);
+        assert_eq!(first(&rule, ymd(2020, 1, 6), 5), vec![ymd(2020, 1, 7)]);
+        rule.count = Some(3);
+        assert_eq!(
+            first(&rule, ymd(2020, 1, 6), 5),
+            vec![ymd(2020, 1, 7), ymd(2020, 1, 9), ymd(2020, 1, 14)]
+        );
+        let mut rule = Recurrence::new(Frequency::Monthly);
+        rule.by_month_day = vec![1, 15];
+        rule.count = Some(3);
+        assert_eq!(
+            first(&rule, ymd(2020, 1, 1), 5),
+            vec![ymd(2020, 1, 1), ymd(2020, 1, 15), ymd(2020, 2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_times_with_several_weekdays() {
+        let rule = Recurrence::weekly()
+            .on(Weekday::Mon)
+            .on(Weekday::Wed)
+            .on(Weekday::Fri)
+            .times(4);
+        assert_eq!(
+            first(&rule, ymd(2020, 1, 6), 10),
+            vec![
+                ymd(2020, 1, 6),
+                ymd(2020, 1, 8),
+                ymd(2020, 1, 10),
+                ymd(2020, 1, 13)
+            ]
+        );
+        let rule = Recurrence::weekly()
+            .on(Weekday::Tue)
+            .on(Weekday::Thu)
+            .every(2)
+            .times(3);
+        assert_eq!(
+            first(&rule, ymd(2020, 1, 6), 10),
+            vec![ymd(2020, 1, 7), ymd(2020, 1, 9), ymd(2020, 1, 21)]
+        );
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=WEEKLY;INTERVAL=2;COUNT=3;BYDAY=TU,TH"
+        );
+    }
+
+    #[test]
+    fn test_start_counts_toward_count() {
+        let year = DateRange::new(ymd(2020, 1, 1), ymd(2020, 12, 31)).unwrap();
+        // January 1, 2020 is a Wednesday, which the rule does not match
+        let event: RecurrenceSet = "DTSTART;VALUE=DATE:20200101\r\n\
+            RRULE:FREQ=WEEKLY;BYDAY=MO;COUNT=3"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            event.occurrences_within(year),
+            vec![ymd(2020, 1, 1), ymd(2020, 1, 6), ymd(2020, 1, 13)]
+        );
+        let event: RecurrenceSet = "DTSTART;VALUE=DATE:20200106\r\n\
+            RRULE:FREQ=WEEKLY;BYDAY=MO;COUNT=3"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            event.occurrences_within(year),
+            vec![ymd(2020, 1, 6), ymd(2020, 1, 13), ymd(2020, 1, 20)]
+        );
+        let event: RecurrenceSet = "DTSTART;VALUE=DATE:20200101\r\n\
+            RRULE:FREQ=WEEKLY;BYDAY=MO;COUNT=0"
+            .parse()
+            .unwrap();
+        assert_eq!(event.occurrences_within(year), vec![ymd(2020, 1, 1)]);
+    }
+
+    #[test]
+    fn test_set_count_with_several_dates_per_period() {
+        let year = DateRange::new(ymd(2020, 1, 1), ymd(2020, 12, 31)).unwrap();
+        // Excluded dates still count toward the rule's count
+        let event: RecurrenceSet = "DTSTART;VALUE=DATE:20200106\r\n\
+            RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR;COUNT=4\r\n\
+            RDATE;VALUE=DATE:20200104\r\n\
+            EXDATE;VALUE=DATE:20200108"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            event.occurrences_within(year),
+            vec![
+                ymd(2020, 1, 4),
+                ymd(2020, 1, 6),
+                ymd(2020, 1, 10),
+                ymd(2020, 1, 13)
+            ]
+        );
+        let event: RecurrenceSet = "DTSTART;VALUE=DATE:20200101\r\n\
+            RRULE:FREQ=MONTHLY;BYMONTHDAY=10,20;COUNT=3\r\n\
+            EXDATE;VALUE=DATE:20200120"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            event.occurrences_within(year),
+            vec![ymd(2020, 1, 1), ymd(2020, 1, 10)]
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Relative date expressions such as `EOM-1BD` or `BOY+2W`, meant to be kept in configuration
+//! files and resolved against a reference date
+use super::business::HolidayCalendar;
+use super::duration::CalendarDuration;
+use super::period::Unit;
+use chrono::{NaiveDate, TimeDelta};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The date a [RelativeDate](struct.RelativeDate.html) is anchored to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    /// The reference date itself, written `T`
+    Today,
+    /// The first day of the period containing the reference date, written `BOW`, `BOM`, `BOQ`
+    /// or `BOY` for ISO 8601 weeks, months, quarters and years
+    StartOf(Unit),
+    /// The last day of the period containing the reference date, written `EOW`, `EOM`, `EOQ`
+    /// or `EOY`
+    EndOf(Unit),
+}
+
+/// The unit of a [Shift](struct.Shift.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShiftUnit {
+    /// Days, written `D`
+    Day,
+    /// Business days of the calendar, written `BD`
+    BusinessDay,
+    /// Weeks, written `W`
+    Week,
+    /// Months, written `M`
+    Month,
+    /// Quarters, written `Q`
+    Quarter,
+    /// Years, written `Y`
+    Year,
+}
+
+/// A signed number of units to move a date by, such as `+2W` or `-1BD`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shift {
+    /// The number of units, negative to move backward
+    pub amount: i32,
+    /// The unit
+    pub unit: ShiftUnit,
+}
+
+/// A date relative to a reference date, made of an anchor followed by shifts applied from left
+/// to right. Shifts by months, quarters and years clamp the day to the length of the resulting
+/// month, as [CalendarDuration](../duration/struct.CalendarDuration.html) does.
+///
+/// Expressions are written as the anchor (`T`, `BOW`, `EOW`, `BOM`, `EOM`, `BOQ`, `EOQ`, `BOY`
+/// or `EOY`) followed by any number of shifts (`+3D`, `-1BD`, `+2W`, `+1M`, `-1Q`, `+1Y`).
+///
+/// Shifts by business days move to the `n`th business day after or before the date, without
+/// counting the date itself, so that `BOM+5BD` is the fifth business day of the month only when
+/// the month starts on a non-business day. Starting from the last business day of the previous
+/// month, as in `BOM-1BD+5BD`, gives the fifth business day of the month in every case.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::business::HolidayCalendar;
+/// use chrono_utilities::naive::relative::RelativeDate;
+///
+/// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()].into_iter().collect();
+/// let today = NaiveDate::from_ymd_opt(2020, 1, 15).unwrap();
+/// // The business day before the end of the month
+/// let expr: RelativeDate = "EOM-1BD".parse().unwrap();
+/// assert_eq!(expr.resolve(today, &cal), Some(NaiveDate::from_ymd_opt(2020, 1, 30).unwrap()));
+/// // Five business days after the start of the month, which is a holiday in January
+/// let expr: RelativeDate = "BOM+5BD".parse().unwrap();
+/// assert_eq!(expr.resolve(today, &cal), Some(NaiveDate::from_ymd_opt(2020, 1, 8).unwrap()));
+/// // but the first business day in April, so that April 8 is its sixth business day
+/// let april = NaiveDate::from_ymd_opt(2020, 4, 15).unwrap();
+/// assert_eq!(expr.resolve(april, &cal), Some(NaiveDate::from_ymd_opt(2020, 4, 8).unwrap()));
+/// // The fifth business day of the month
+/// let expr: RelativeDate = "BOM-1BD+5BD".parse().unwrap();
+/// assert_eq!(expr.resolve(april, &cal), Some(NaiveDate::from_ymd_opt(2020, 4, 7).unwrap()));
+/// // The last business day but one of the month
+/// let expr: RelativeDate = "EOM+1BD-2BD".parse().unwrap();
+/// let may = NaiveDate::from_ymd_opt(2020, 5, 15).unwrap();
+/// assert_eq!(expr.resolve(may, &cal), Some(NaiveDate::from_ymd_opt(2020, 5, 28).unwrap()));
+/// // Two weeks into the year
+/// let expr: RelativeDate = "BOY+2W".parse().unwrap();
+/// assert_eq!(expr.resolve(today, &cal), Some(NaiveDate::from_ymd_opt(2020, 1, 15).unwrap()));
+/// assert_eq!(expr.to_string(), "BOY+2W");
+/// assert!("EOM+1X".parse::<RelativeDate>().is_err());
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RelativeDate {
+    /// The date the expression starts from
+    pub anchor: Anchor,
+    /// The shifts applied to the anchor, in order
+    pub shifts: Vec<Shift>,
+}
+
+impl RelativeDate {
+    /// Creates an expression resolving to the anchor
+    #[inline]
+    pub fn new(anchor: Anchor) -> Self {
+        RelativeDate {
+            anchor,
+            shifts: Vec::new(),
+        }
+    }
+
+    /// Returns the expression followed by a shift of `amount` units
+    pub fn shift(mut self, amount: i32, unit: ShiftUnit) -> Self {
+        self.shifts.push(Shift { amount, unit });
+        self
+    }
+
+    /// Resolves the expression against the reference date, counting business days with the
+    /// calendar. Returns `None` if a date out of the range supported by chrono is reached.
+    pub fn resolve(&self, reference: NaiveDate, calendar: &HolidayCalendar) -> Option<NaiveDate> {
+        let mut date = match self.anchor {
+            Anchor::Today => reference,
+            Anchor::StartOf(unit) => unit.start_of(reference)?,
+            Anchor::EndOf(unit) => unit.period_of(reference)?.end(),
+        };
+        for shift in &self.shifts {
+            let amount = shift.amount;
+            date = match shift.unit {
+                ShiftUnit::Day => date.checked_add_signed(TimeDelta::days(amount as i64))?,
+                ShiftUnit::BusinessDay => calendar.add_business_days(date, amount as i64)?,
+                ShiftUnit::Week => CalendarDuration::weeks(amount).add_to(date)?,
+                ShiftUnit::Month => CalendarDuration::months(amount).add_to(date)?,
+                ShiftUnit::Quarter => {
+                    CalendarDuration::months(amount.checked_mul(3)?).add_to(date)?
+                }
+                ShiftUnit::Year => CalendarDuration::years(amount).add_to(date)?,
+            };
+        }
+        Some(date)
+    }
+}
+
+/// Anchors along with the way they are written
+const ANCHORS: [(Anchor, &str); 9] = [
+    (Anchor::Today, "T"),
+    (Anchor::StartOf(Unit::Week), "BOW"),
+    (Anchor::EndOf(Unit::Week), "EOW"),
+    (Anchor::StartOf(Unit::Month), "BOM"),
+    (Anchor::EndOf(Unit::Month), "EOM"),
+    (Anchor::StartOf(Unit::Quarter), "BOQ"),
+    (Anchor::EndOf(Unit::Quarter), "EOQ"),
+    (Anchor::StartOf(Unit::Year), "BOY"),
+    (Anchor::EndOf(Unit::Year), "EOY"),
+];
+
+/// Shift units along with the way they are written
+const SHIFT_UNITS: [(ShiftUnit, &str); 6] = [
+    (ShiftUnit::Day, "D"),
+    (ShiftUnit::BusinessDay, "BD"),
+    (ShiftUnit::Week, "W"),
+    (ShiftUnit::Month, "M"),
+    (ShiftUnit::Quarter, "Q"),
+    (ShiftUnit::Year, "Y"),
+];
+
+impl fmt::Display for Shift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = SHIFT_UNITS
+            .iter()
+            .find(|(unit, _)| *unit == self.unit)
+            .map_or("", |(_, name)| name);
+        write!(f, "{:+}{}", self.amount, unit)
+    }
+}
+
+impl fmt::Display for RelativeDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let anchor = ANCHORS
+            .iter()
+            .find(|(anchor, _)| *anchor == self.anchor)
+            .map_or("T", |(_, name)| name);
+        f.write_str(anchor)?;
+        for shift in &self.shifts {
+            write!(f, "{}", shift)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RelativeDate {
+    type Err = ParseRelativeDateError;
+
+    /// Parses an expression such as `EOM-1BD`, ignoring the case of letters. Anchors to the
+    /// start or end of a day are not supported as they would be the reference date itself.
+    fn from_str(s: &str) -> Result<Self, ParseRelativeDateError> {
+        let s = s.trim();
+        let end = s.find(['+', '-']).unwrap_or(s.len());
+        let anchor = ANCHORS
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(&s[..end]))
+            .map(|(anchor, _)| *anchor)
+            .ok_or(ParseRelativeDateError { part: "anchor" })?;
+        let mut expr = RelativeDate::new(anchor);
+        let mut rest = &s[end..];
+        while !rest.is_empty() {
+            let err = ParseRelativeDateError { part: "shift" };
+            let digits = rest[1..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(rest.len(), |i| i + 1);
+            let end = rest[digits..]
+                .find(['+', '-'])
+                .map_or(rest.len(), |i| i + digits);
+            let unit = SHIFT_UNITS
+                .iter()
+                .find(|(_, name)| name.eq_ignore_ascii_case(&rest[digits..end]))
+                .map(|(unit, _)| *unit)
+                .ok_or(err)?;
+            if digits == 1 {
+                return Err(err);
+            }
+            let amount = rest[..digits].parse().map_err(|_| err)?;
+            expr.shifts.push(Shift { amount, unit });
+            rest = &rest[end..];
+        }
+        Ok(expr)
+    }
+}
+
+/// Error returned when parsing an invalid relative date expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseRelativeDateError {
+    part: &'static str,
+}
+
+impl fmt::Display for ParseRelativeDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid relative date {}", self.part)
+    }
+}
+
+impl Error for ParseRelativeDateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Anchor, RelativeDate, ShiftUnit};
+    use crate::naive::period::Unit;
+
+    #[test]
+    fn test_parse() {
+        let expr = RelativeDate::new(Anchor::EndOf(Unit::Quarter))
+            .shift(1, ShiftUnit::BusinessDay)
+            .shift(-2, ShiftUnit::Day);
+        assert_eq!("eoq+1bd-2d".parse(), Ok(expr.clone()));
+        assert_eq!(expr.to_string().parse(), Ok(expr));
+        assert_eq!("T".parse(), Ok(RelativeDate::new(Anchor::Today)));
+        for invalid in &[
+            "", "EOD", "EOM+", "EOM+BD", "EOM1BD", "EOM+1", "EOM+1BD+", "+1D",
+        ] {
+            assert!(invalid.parse::<RelativeDate>().is_err(), "{}", invalid);
+        }
+    }
+}
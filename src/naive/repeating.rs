@@ -0,0 +1,218 @@
+//! ISO 8601 repeating intervals such as `R5/2020-01-01T09:00:00/P1W`, describing schedules as a
+//! number of back to back intervals
+use super::duration::IsoDuration;
+use super::interval::{parse_datetime, DateTimeInterval, ParseIntervalError};
+use chrono::NaiveDateTime;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// A number of back to back intervals of the same duration, starting from a datetime or ending
+/// on one. Each interval is computed from the anchor by a multiple of the duration rather than
+/// from the previous interval, so that monthly intervals starting on January 31 start on the
+/// last day of every shorter month and return to the 31st afterwards.
+///
+/// Repeating intervals are written `Rn/start/duration`, `Rn/duration/end` or `Rn/start/end`,
+/// `n` being the number of intervals, or left out for an unbounded number. Intervals written
+/// with a start and an end repeat the exact time between them and are formatted with a
+/// duration.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::interval::DateTimeInterval;
+/// use chrono_utilities::naive::repeating::RepeatingInterval;
+///
+/// let at = |m, d, h| NaiveDate::from_ymd_opt(2020, m, d).unwrap().and_hms_opt(h, 0, 0).unwrap();
+/// let monthly: RepeatingInterval = "R3/2020-01-31T09:00:00/P1M".parse().unwrap();
+/// let starts: Vec<_> = monthly.iter().map(|interval| interval.start()).collect();
+/// assert_eq!(starts, vec![at(1, 31, 9), at(2, 29, 9), at(3, 31, 9)]);
+/// let shifts: RepeatingInterval = "R/PT8H/2020-06-01T06:00".parse().unwrap();
+/// let last: Vec<_> = shifts.iter().take(2).collect();
+/// assert_eq!(last, vec![
+///     DateTimeInterval::new(at(5, 31, 22), at(6, 1, 6)).unwrap(),
+///     DateTimeInterval::new(at(5, 31, 14), at(5, 31, 22)).unwrap(),
+/// ]);
+/// assert_eq!(shifts.to_string(), "R/PT8H/2020-06-01T06:00:00");
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RepeatingInterval {
+    repetitions: Option<u32>,
+    anchor: NaiveDateTime,
+    duration: IsoDuration,
+    backward: bool,
+}
+
+impl RepeatingInterval {
+    /// Creates intervals of the given duration following each other from `start`, repeated
+    /// `repetitions` times or indefinitely. Returns `None` unless the first interval ends after
+    /// it starts.
+    pub fn starting(
+        start: NaiveDateTime,
+        duration: IsoDuration,
+        repetitions: Option<u32>,
+    ) -> Option<Self> {
+        duration.add_to(start).filter(|end| *end > start)?;
+        Some(RepeatingInterval {
+            repetitions,
+            anchor: start,
+            duration,
+            backward: false,
+        })
+    }
+
+    /// Creates intervals of the given duration preceding each other from `end`, repeated
+    /// `repetitions` times or indefinitely. Returns `None` unless the first interval starts
+    /// before it ends.
+    pub fn ending(
+        end: NaiveDateTime,
+        duration: IsoDuration,
+        repetitions: Option<u32>,
+    ) -> Option<Self> {
+        duration.sub_from(end).filter(|start| *start < end)?;
+        Some(RepeatingInterval {
+            repetitions,
+            anchor: end,
+            duration,
+            backward: true,
+        })
+    }
+
+    /// Returns the number of intervals, or `None` if they repeat indefinitely
+    #[inline]
+    pub fn repetitions(&self) -> Option<u32> {
+        self.repetitions
+    }
+
+    /// Returns the duration of each interval
+    #[inline]
+    pub fn duration(&self) -> IsoDuration {
+        self.duration
+    }
+
+    /// Returns the n-th interval, counting from 0, or `None` if there are fewer intervals or it
+    /// is out of the range supported by chrono
+    pub fn nth(&self, n: u32) -> Option<DateTimeInterval> {
+        if self.repetitions.is_some_and(|repetitions| n >= repetitions) {
+            return None;
+        }
+        let n = i32::try_from(n).ok()?;
+        let near = self.duration.checked_mul(n)?;
+        let far = self.duration.checked_mul(n.checked_add(1)?)?;
+        if self.backward {
+            DateTimeInterval::new(far.sub_from(self.anchor)?, near.sub_from(self.anchor)?)
+        } else {
+            DateTimeInterval::new(near.add_to(self.anchor)?, far.add_to(self.anchor)?)
+        }
+    }
+
+    /// Returns an iterator over the intervals, moving away from the start or the end they are
+    /// anchored to, so that intervals anchored to their end come in reverse chronological order
+    #[inline]
+    pub fn iter(&self) -> Repetitions {
+        Repetitions {
+            repeating: *self,
+            next: 0,
+        }
+    }
+}
+
+impl fmt::Display for RepeatingInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("R")?;
+        if let Some(repetitions) = self.repetitions {
+            write!(f, "{}", repetitions)?;
+        }
+        let anchor = self.anchor.format("%Y-%m-%dT%H:%M:%S%.f");
+        if self.backward {
+            write!(f, "/{}/{}", self.duration, anchor)
+        } else {
+            write!(f, "/{}/{}", anchor, self.duration)
+        }
+    }
+}
+
+impl FromStr for RepeatingInterval {
+    type Err = ParseIntervalError;
+
+    /// Parses an ISO 8601 repeating interval, such as `R5/2020-01-01T09:00:00/P1W`
+    fn from_str(s: &str) -> Result<Self, ParseIntervalError> {
+        let err = ParseIntervalError::new;
+        let (repetitions, interval) = s
+            .strip_prefix('R')
+            .and_then(|s| s.split_once('/'))
+            .ok_or(err("repetitions"))?;
+        let repetitions = match repetitions {
+            "" => None,
+            n => Some(n.parse().map_err(|_| err("repetitions"))?),
+        };
+        let (first, second) = interval.split_once('/').ok_or(err("separator"))?;
+        let duration = |s: &str| s.parse::<IsoDuration>().map_err(|_| err("duration"));
+        let repeating = if first.starts_with('P') {
+            let end = parse_datetime(second).ok_or(err("end"))?;
+            RepeatingInterval::ending(end, duration(first)?, repetitions)
+        } else if second.starts_with('P') {
+            let start = parse_datetime(first).ok_or(err("start"))?;
+            RepeatingInterval::starting(start, duration(second)?, repetitions)
+        } else {
+            let interval: DateTimeInterval = interval.parse()?;
+            let duration = IsoDuration::new(Default::default(), interval.duration());
+            RepeatingInterval::starting(interval.start(), duration, repetitions)
+        };
+        repeating.ok_or(err("interval"))
+    }
+}
+
+impl IntoIterator for &RepeatingInterval {
+    type Item = DateTimeInterval;
+    type IntoIter = Repetitions;
+
+    #[inline]
+    fn into_iter(self) -> Repetitions {
+        self.iter()
+    }
+}
+
+/// Iterator over the intervals of a [RepeatingInterval](struct.RepeatingInterval.html)
+#[derive(Debug, Clone)]
+pub struct Repetitions {
+    repeating: RepeatingInterval,
+    next: u32,
+}
+
+impl Iterator for Repetitions {
+    type Item = DateTimeInterval;
+
+    fn next(&mut self) -> Option<DateTimeInterval> {
+        let interval = self.repeating.nth(self.next)?;
+        self.next += 1;
+        Some(interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RepeatingInterval;
+
+    #[test]
+    fn test_parse() {
+        let weekly: RepeatingInterval = "R2/2020-01-06T09:00/2020-01-13T09:00".parse().unwrap();
+        assert_eq!(weekly.to_string(), "R2/2020-01-06T09:00:00/PT168H");
+        assert_eq!(weekly.iter().count(), 2);
+        assert_eq!(weekly.to_string().parse(), Ok(weekly));
+        let invalid = [
+            "2020-01-06T09:00/P1D",
+            "Rx/2020-01-06T09:00/P1D",
+            "R-1/2020-01-06T09:00/P1D",
+            "R1/2020-01-06T09:00",
+            "R1/P1D/P1D",
+            "R1/2020-01-06T09:00/PT0S",
+            "R1/2020-01-06T09:00/-P1D",
+        ];
+        for invalid in invalid.iter() {
+            assert!(invalid.parse::<RepeatingInterval>().is_err(), "{}", invalid);
+        }
+        let none: RepeatingInterval = "R0/2020-01-06/P1D".parse().unwrap();
+        assert_eq!(none.iter().next(), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! Rotating shift patterns, such as four days on followed by four days off, for workforce
+//! scheduling
+use chrono::{NaiveDate, TimeDelta};
+use std::iter::FusedIterator;
+
+/// A pattern of on-shift and off-shift days repeating endlessly in both directions from an
+/// anchor date, on which the first day of the pattern falls
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::rota::ShiftPattern;
+///
+/// let anchor = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+/// let pattern = ShiftPattern::on_off(anchor, 4, 4).unwrap();
+/// assert!(pattern.is_on_shift(NaiveDate::from_ymd_opt(2020, 1, 4).unwrap()));
+/// assert!(!pattern.is_on_shift(NaiveDate::from_ymd_opt(2020, 1, 5).unwrap()));
+/// assert!(pattern.is_on_shift(NaiveDate::from_ymd_opt(2020, 1, 9).unwrap()));
+/// // The pattern extends before the anchor
+/// assert!(!pattern.is_on_shift(NaiveDate::from_ymd_opt(2019, 12, 31).unwrap()));
+/// let shifts: Vec<NaiveDate> = pattern.on_shift_dates(NaiveDate::from_ymd_opt(2020, 1, 3).unwrap()).take(3).collect();
+/// assert_eq!(shifts, vec![
+///     NaiveDate::from_ymd_opt(2020, 1, 3).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 4).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 9).unwrap(),
+/// ]);
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShiftPattern {
+    anchor: NaiveDate,
+    days: Vec<bool>,
+}
+
+impl ShiftPattern {
+    /// Creates a pattern from the on-shift (`true`) and off-shift (`false`) days of a cycle,
+    /// the first of which falls on the anchor date. Returns `None` if the cycle is empty.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::rota::ShiftPattern;
+    ///
+    /// // Two days on, one off, three on, two off
+    /// let cycle = vec![true, true, false, true, true, true, false, false];
+    /// let pattern = ShiftPattern::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), cycle).unwrap();
+    /// assert_eq!(pattern.cycle_len(), 8);
+    /// assert!(!pattern.is_on_shift(NaiveDate::from_ymd_opt(2020, 1, 3).unwrap()));
+    pub fn new(anchor: NaiveDate, days: Vec<bool>) -> Option<Self> {
+        if days.is_empty() {
+            None
+        } else {
+            Some(ShiftPattern { anchor, days })
+        }
+    }
+
+    /// Creates a pattern of `on` days on shift followed by `off` days off shift, starting on
+    /// the anchor date. Returns `None` if the cycle is empty.
+    pub fn on_off(anchor: NaiveDate, on: u32, off: u32) -> Option<Self> {
+        let mut days = vec![true; on as usize];
+        days.resize(on.checked_add(off)? as usize, false);
+        ShiftPattern::new(anchor, days)
+    }
+
+    /// Returns the date on which the first day of the cycle falls
+    #[inline]
+    pub fn anchor(&self) -> NaiveDate {
+        self.anchor
+    }
+
+    /// Returns the number of days in a cycle
+    #[inline]
+    pub fn cycle_len(&self) -> usize {
+        self.days.len()
+    }
+
+    /// Returns the position of the date within the cycle, starting from 0 on the anchor
+    #[inline]
+    pub fn day_in_cycle(&self, date: NaiveDate) -> usize {
+        let days = date.signed_duration_since(self.anchor).num_days();
+        days.rem_euclid(self.days.len() as i64) as usize
+    }
+
+    /// Returns true if the date is an on-shift day
+    #[inline]
+    pub fn is_on_shift(&self, date: NaiveDate) -> bool {
+        self.days[self.day_in_cycle(date)]
+    }
+
+    /// Returns an iterator over the on-shift days falling on or after the given date. The
+    /// iterator is empty if the pattern has no on-shift day, and endless otherwise until the
+    /// dates run past the range supported by chrono.
+    pub fn on_shift_dates(&self, from: NaiveDate) -> OnShiftDates<'_> {
+        let next = if self.days.contains(&true) {
+            Some(from)
+        } else {
+            None
+        };
+        OnShiftDates {
+            pattern: self,
+            next,
+        }
+    }
+}
+
+/// Iterator over the on-shift days of a pattern. See
+/// [ShiftPattern::on_shift_dates](struct.ShiftPattern.html#method.on_shift_dates).
+#[derive(Debug, Clone)]
+pub struct OnShiftDates<'a> {
+    pattern: &'a ShiftPattern,
+    next: Option<NaiveDate>,
+}
+
+impl Iterator for OnShiftDates<'_> {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        let start = self.next?;
+        let days = &self.pattern.days;
+        let position = self.pattern.day_in_cycle(start);
+        // An on-shift day is always found within a cycle as the pattern has one
+        let offset = (0..days.len())
+            .find(|offset| days[(position + offset) % days.len()])
+            .unwrap_or(0);
+        let date = start.checked_add_signed(TimeDelta::days(offset as i64));
+        self.next = date.and_then(|date| date.succ_opt());
+        date
+    }
+}
+
+impl FusedIterator for OnShiftDates<'_> {}
@@ -0,0 +1,351 @@
+//! Planning helpers built on top of ranges, periods and durations
+use super::business::HolidayCalendar;
+use super::duration::CalendarDuration;
+use super::interval::IntervalSet;
+use super::period::Unit;
+use super::range::DateRange;
+use chrono::NaiveDate;
+
+/// Size of the chunks a span of dates is split into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChunkSize {
+    /// Chunks aligned to the calendar periods of the given granularity
+    Period(Unit),
+    /// Chunks of the given duration counted from the start of the span
+    Duration(CalendarDuration),
+}
+
+impl From<Unit> for ChunkSize {
+    fn from(unit: Unit) -> Self {
+        ChunkSize::Period(unit)
+    }
+}
+
+impl From<CalendarDuration> for ChunkSize {
+    fn from(duration: CalendarDuration) -> Self {
+        ChunkSize::Duration(duration)
+    }
+}
+
+/// Chronological order in which a plan is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Order {
+    OldestFirst,
+    NewestFirst,
+}
+
+/// Splits the range into chunks of the given size and lays them out in the given order. When a
+/// set of already covered dates is supplied, only the uncovered parts of the range are planned,
+/// each of them being chunked on its own.
+///
+/// # Panics
+///
+/// Panics if the chunk is a duration which does not move dates forward.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::duration::CalendarDuration;
+/// use chrono_utilities::naive::interval::IntervalSet;
+/// use chrono_utilities::naive::period::Unit;
+/// use chrono_utilities::naive::range::DateRange;
+/// use chrono_utilities::naive::schedule::{backfill_plan, Order};
+///
+/// let range = DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 15).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 10).unwrap()).unwrap();
+/// let plan = backfill_plan(range, Unit::Month, Order::NewestFirst, None);
+/// assert_eq!(plan.len(), 4);
+/// assert_eq!(plan[0], DateRange::new(NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 10).unwrap()).unwrap());
+/// assert_eq!(plan[3], DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 15).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap());
+///
+/// let covered: IntervalSet = vec![
+///     DateRange::new(NaiveDate::from_ymd_opt(2020, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 31).unwrap()).unwrap(),
+/// ].into_iter().collect();
+/// let plan = backfill_plan(range, CalendarDuration::weeks(2), Order::OldestFirst, Some(&covered));
+/// assert_eq!(plan, vec![
+///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 15).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 28).unwrap()).unwrap(),
+///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 29).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 31).unwrap()).unwrap(),
+///     DateRange::new(NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 10).unwrap()).unwrap(),
+/// ]);
+pub fn backfill_plan<C: Into<ChunkSize>>(
+    range: DateRange,
+    chunk: C,
+    order: Order,
+    covered: Option<&IntervalSet>,
+) -> Vec<DateRange> {
+    let chunk = chunk.into();
+    let mut plan = Vec::new();
+    match covered {
+        Some(covered) => {
+            for piece in covered.complement_within(range).iter() {
+                plan.extend(chunks(*piece, chunk));
+            }
+        }
+        None => plan.extend(chunks(range, chunk)),
+    }
+    if order == Order::NewestFirst {
+        plan.reverse();
+    }
+    plan
+}
+
+/// Returns the rolling-origin splits of the range used to validate time series models. The
+/// first training window starts at the start of the range and every following split moves it
+/// forward by `step`. Each training window spans `train` and is immediately followed by a test
+/// window spanning `test`. Splits are produced as long as the test window ends within the range.
+///
+/// # Panics
+///
+/// Panics if any of the durations does not move dates forward.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::duration::CalendarDuration;
+/// use chrono_utilities::naive::range::DateRange;
+/// use chrono_utilities::naive::schedule::rolling_splits;
+///
+/// let range = DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 12, 31).unwrap()).unwrap();
+/// let splits = rolling_splits(range, CalendarDuration::months(6), CalendarDuration::months(1), CalendarDuration::months(2));
+/// assert_eq!(splits.len(), 3);
+/// let (train, test) = splits[1];
+/// assert_eq!(train, DateRange::new(NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 8, 31).unwrap()).unwrap());
+/// assert_eq!(test, DateRange::new(NaiveDate::from_ymd_opt(2020, 9, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 9, 30).unwrap()).unwrap());
+pub fn rolling_splits(
+    range: DateRange,
+    train: CalendarDuration,
+    test: CalendarDuration,
+    step: CalendarDuration,
+) -> Vec<(DateRange, DateRange)> {
+    let mut splits = Vec::new();
+    let mut previous = None;
+    for index in 0.. {
+        let split = step
+            .checked_mul(index)
+            .and_then(|offset| offset.add_to(range.start()))
+            .and_then(|train_start| {
+                let test_start = train.add_to(train_start)?;
+                let test_end = test.add_to(test_start)?.pred_opt()?;
+                assert!(
+                    train_start < test_start && test_start <= test_end,
+                    "train and test windows must move dates forward"
+                );
+                Some((
+                    DateRange::new(train_start, test_start.pred_opt()?)?,
+                    DateRange::new(test_start, test_end)?,
+                ))
+            });
+        let (train_window, test_window) = match split {
+            Some(split) if split.1.end() <= range.end() => split,
+            _ => break,
+        };
+        if let Some(previous) = previous {
+            assert!(
+                train_window.start() > previous,
+                "step must move dates forward"
+            );
+        }
+        previous = Some(train_window.start());
+        splits.push((train_window, test_window));
+    }
+    splits
+}
+
+/// Returns the next `count` release dates of a train leaving every `cadence` from `anchor`.
+/// With a period cadence the train leaves at the start of every period, starting with the first
+/// one on or after `anchor`. A release scheduled on a non-business day or during a blackout is
+/// rolled forward to the first business day outside of any blackout, and releases rolled onto
+/// the same date are merged.
+///
+/// # Panics
+///
+/// Panics if the cadence is a duration which does not move dates forward.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::business::HolidayCalendar;
+/// use chrono_utilities::naive::duration::CalendarDuration;
+/// use chrono_utilities::naive::interval::IntervalSet;
+/// use chrono_utilities::naive::range::DateRange;
+/// use chrono_utilities::naive::schedule::release_train;
+///
+/// let cal = HolidayCalendar::new();
+/// let blackout: IntervalSet = vec![
+///     DateRange::new(NaiveDate::from_ymd_opt(2020, 12, 18).unwrap(), NaiveDate::from_ymd_opt(2021, 1, 3).unwrap()).unwrap(),
+/// ].into_iter().collect();
+/// let anchor = NaiveDate::from_ymd_opt(2020, 12, 1).unwrap();
+/// let releases = release_train(anchor, CalendarDuration::weeks(2), &blackout, &cal, 4);
+/// assert_eq!(releases, vec![
+///     NaiveDate::from_ymd_opt(2020, 12, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 12, 15).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 1, 4).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 1, 12).unwrap(),
+/// ]);
+pub fn release_train<C: Into<ChunkSize>>(
+    anchor: NaiveDate,
+    cadence: C,
+    blackout: &IntervalSet,
+    cal: &HolidayCalendar,
+    count: usize,
+) -> Vec<NaiveDate> {
+    let scheduled: Box<dyn Iterator<Item = NaiveDate>> = match cadence.into() {
+        ChunkSize::Period(unit) => Box::new(unit.starts_from(anchor)),
+        ChunkSize::Duration(duration) => {
+            Box::new((0..).map_while(move |index| duration.checked_mul(index)?.add_to(anchor)))
+        }
+    };
+    let mut releases: Vec<NaiveDate> = Vec::with_capacity(count);
+    let mut previous = None;
+    for scheduled in scheduled {
+        if releases.len() == count {
+            break;
+        }
+        if let Some(previous) = previous {
+            assert!(scheduled > previous, "cadence must move dates forward");
+        }
+        previous = Some(scheduled);
+        let release = match roll_to_open_day(scheduled, blackout, cal) {
+            Some(release) => release,
+            None => break,
+        };
+        if releases.last().map_or(true, |last| *last < release) {
+            releases.push(release);
+        }
+    }
+    releases
+}
+
+/// Returns the validity ranges of `count` successive credentials, such as certificates or keys,
+/// rotated so that each one overlaps with the next. The first credential is issued on `start`
+/// and every credential is valid for `validity`, its range ending on its last valid day. Each
+/// following credential is issued `overlap` before its predecessor lapses, so that both are
+/// valid for the whole overlap.
+///
+/// # Panics
+///
+/// Panics if `overlap` does not move dates forward or is not shorter than `validity`.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::duration::CalendarDuration;
+/// use chrono_utilities::naive::schedule::rotation_schedule;
+///
+/// let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+/// let schedule = rotation_schedule(start, CalendarDuration::days(90), CalendarDuration::days(30), 3);
+/// assert_eq!(schedule.len(), 3);
+/// assert_eq!(schedule[0].end(), NaiveDate::from_ymd_opt(2020, 3, 30).unwrap());
+/// assert_eq!(schedule[1].start(), NaiveDate::from_ymd_opt(2020, 3, 1).unwrap());
+/// assert_eq!(schedule[2].start(), NaiveDate::from_ymd_opt(2020, 4, 30).unwrap());
+pub fn rotation_schedule(
+    start: NaiveDate,
+    validity: CalendarDuration,
+    overlap: CalendarDuration,
+    count: usize,
+) -> Vec<DateRange> {
+    let mut schedule = Vec::with_capacity(count);
+    let mut issued = start;
+    while schedule.len() < count {
+        let lapses = match validity.add_to(issued) {
+            Some(lapses) => lapses,
+            None => break,
+        };
+        let next = overlap.sub_from(lapses);
+        assert!(
+            next.map_or(true, |next| issued < next && next < lapses),
+            "overlap must be positive and shorter than the validity"
+        );
+        match lapses
+            .pred_opt()
+            .and_then(|end| DateRange::new(issued, end))
+        {
+            Some(range) => schedule.push(range),
+            None => break,
+        }
+        match next {
+            Some(next) => issued = next,
+            None => break,
+        }
+    }
+    schedule
+}
+
+/// Returns the cutoff dates of storage tiers, such as hot, warm and cold, for lifecycle jobs
+/// running on `today`. Each tier keeps data for the given duration, and its cutoff is the start
+/// of the period of the `align` granularity containing the date that far back. Data dated before
+/// a cutoff has outgrown the tier. Aligning cutoffs to period starts keeps them stable during the
+/// period, so every job running within it computes the same ones. Returns `None` if a cutoff
+/// falls outside of the range supported by chrono.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::duration::CalendarDuration;
+/// use chrono_utilities::naive::period::Unit;
+/// use chrono_utilities::naive::schedule::tier_boundaries;
+///
+/// let tiers = [CalendarDuration::days(7), CalendarDuration::months(3), CalendarDuration::years(1)];
+/// let cutoffs = tier_boundaries(NaiveDate::from_ymd_opt(2020, 8, 13).unwrap(), &tiers, Unit::Month).unwrap();
+/// assert_eq!(cutoffs, vec![
+///     NaiveDate::from_ymd_opt(2020, 8, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 5, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2019, 8, 1).unwrap(),
+/// ]);
+/// let later = tier_boundaries(NaiveDate::from_ymd_opt(2020, 8, 20).unwrap(), &tiers, Unit::Month).unwrap();
+/// assert_eq!(later, cutoffs);
+pub fn tier_boundaries(
+    today: NaiveDate,
+    tiers: &[CalendarDuration],
+    align: Unit,
+) -> Option<Vec<NaiveDate>> {
+    tiers
+        .iter()
+        .map(|retention| align.start_of(retention.sub_from(today)?))
+        .collect()
+}
+
+/// Returns the first business day on or after the date which does not fall within a blackout
+fn roll_to_open_day(
+    date: NaiveDate,
+    blackout: &IntervalSet,
+    cal: &HolidayCalendar,
+) -> Option<NaiveDate> {
+    let mut date = cal.next_business_day(date)?;
+    while let Some(frozen) = blackout.range_containing(date) {
+        date = cal.next_business_day(frozen.end().succ_opt()?)?;
+    }
+    Some(date)
+}
+
+/// Splits the range into consecutive chunks of the given size, the last one being truncated to
+/// the end of the range
+pub(crate) fn chunks(range: DateRange, chunk: ChunkSize) -> Vec<DateRange> {
+    let duration = match chunk {
+        ChunkSize::Period(unit) => return range.split_by(unit),
+        ChunkSize::Duration(duration) => duration,
+    };
+    let mut chunks = Vec::new();
+    let mut start = range.start();
+    for index in 1.. {
+        let next = duration
+            .checked_mul(index)
+            .and_then(|step| step.add_to(range.start()));
+        if let Some(next) = next {
+            assert!(next > start, "chunk must move dates forward");
+        }
+        match next.filter(|next| *next <= range.end()) {
+            Some(next) => {
+                chunks.push(DateRange::new(start, next.pred_opt().unwrap()).unwrap());
+                start = next;
+            }
+            None => break,
+        }
+    }
+    chunks.extend(DateRange::new(start, range.end()));
+    chunks
+}
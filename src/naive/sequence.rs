@@ -0,0 +1,284 @@
+//! Data-quality checks over sequences of
+//! [NaiveDate](https://docs.rs/chrono/0.4.35/chrono/naive/struct.NaiveDate.html)
+use super::business::HolidayCalendar;
+use super::period::Unit;
+use super::range::DateRange;
+use chrono::NaiveDate;
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+
+/// How strictly a sequence of dates is required to increase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strictness {
+    /// Every date must be later than the one before it, duplicates are rejected
+    Strict,
+    /// Every date must be the same as or later than the one before it
+    NonStrict,
+}
+
+/// Which record to retain when several of them fall within the same period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeepPolicy {
+    /// Keep the record with the earliest date, the first one seen on ties
+    First,
+    /// Keep the record with the latest date, the last one seen on ties
+    Last,
+}
+
+/// The reason a date breaks the ordering of a sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InversionKind {
+    /// The date is the same as the one before it
+    Duplicate,
+    /// The date is earlier than the one before it
+    OutOfOrder,
+}
+
+/// A date that breaks the ordering of a sequence along with its position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Inversion {
+    /// Index of the offending date within the sequence
+    pub index: usize,
+    /// The date preceding the offending one, found at `index - 1`
+    pub previous: NaiveDate,
+    /// The offending date
+    pub date: NaiveDate,
+    /// Whether the date is a duplicate or out of order
+    pub kind: InversionKind,
+}
+
+/// Returns every date in the sequence which is not strictly later than the one preceding it.
+/// Each date is compared against its immediate predecessor only.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::sequence::{find_inversions, InversionKind};
+///
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 3).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 2).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 4).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 4).unwrap(),
+/// ];
+/// let inversions = find_inversions(&dates);
+/// assert_eq!(inversions.len(), 2);
+/// assert_eq!(inversions[0].index, 2);
+/// assert_eq!(inversions[0].kind, InversionKind::OutOfOrder);
+/// assert_eq!(inversions[1].index, 4);
+/// assert_eq!(inversions[1].kind, InversionKind::Duplicate);
+pub fn find_inversions<I>(dates: I) -> Vec<Inversion>
+where
+    I: IntoIterator,
+    I::Item: Borrow<NaiveDate>,
+{
+    inversions(dates).collect()
+}
+
+/// Checks that the sequence of dates is increasing. Returns the first date breaking the
+/// ordering for the given strictness, if any.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::sequence::{validate_monotonic, Strictness};
+///
+/// let dates = vec![
+///     NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 2).unwrap(),
+/// ];
+/// assert!(validate_monotonic(&dates, Strictness::NonStrict).is_ok());
+/// assert_eq!(validate_monotonic(&dates, Strictness::Strict).unwrap_err().index, 1);
+pub fn validate_monotonic<I>(dates: I, strictness: Strictness) -> Result<(), Inversion>
+where
+    I: IntoIterator,
+    I::Item: Borrow<NaiveDate>,
+{
+    let found = inversions(dates).find(|inversion| match strictness {
+        Strictness::Strict => true,
+        Strictness::NonStrict => inversion.kind == InversionKind::OutOfOrder,
+    });
+    match found {
+        Some(inversion) => Err(inversion),
+        None => Ok(()),
+    }
+}
+
+/// Returns the ranges of dates missing from a sorted sequence of dates, in chronological order.
+/// Dates which are not later than the one preceding them are ignored. Data-quality checks on
+/// daily feeds use this to report the days for which no data arrived.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::range::DateRange;
+/// use chrono_utilities::naive::sequence::find_gaps;
+///
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 2).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 4).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 1, 8).unwrap(),
+/// ];
+/// assert_eq!(find_gaps(&dates), vec![
+///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 3).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 3).unwrap()).unwrap(),
+///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 5).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 7).unwrap()).unwrap(),
+/// ]);
+pub fn find_gaps<I>(dates: I) -> Vec<DateRange>
+where
+    I: IntoIterator,
+    I::Item: Borrow<NaiveDate>,
+{
+    gaps(dates).collect()
+}
+
+/// Returns the ranges of business days missing from a sorted sequence of dates, in
+/// chronological order. Every range starts and ends on a business day and holds no date of the
+/// sequence, but may span weekends and holidays. Dates which are not later than the one
+/// preceding them are ignored.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::business::HolidayCalendar;
+/// use chrono_utilities::naive::range::DateRange;
+/// use chrono_utilities::naive::sequence::find_business_day_gaps;
+///
+/// let cal: HolidayCalendar = vec![NaiveDate::from_ymd_opt(2020, 12, 28).unwrap()].into_iter().collect();
+/// // Friday the 25th is followed by a weekend, a holiday and two missing business days
+/// let dates = [
+///     NaiveDate::from_ymd_opt(2020, 12, 25).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 12, 31).unwrap(),
+///     NaiveDate::from_ymd_opt(2021, 1, 4).unwrap(),
+/// ];
+/// assert_eq!(find_business_day_gaps(&dates, &cal), vec![
+///     DateRange::new(NaiveDate::from_ymd_opt(2020, 12, 29).unwrap(), NaiveDate::from_ymd_opt(2020, 12, 30).unwrap()).unwrap(),
+///     DateRange::new(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()).unwrap(),
+/// ]);
+pub fn find_business_day_gaps<I>(dates: I, cal: &HolidayCalendar) -> Vec<DateRange>
+where
+    I: IntoIterator,
+    I::Item: Borrow<NaiveDate>,
+{
+    gaps(dates)
+        .filter_map(|gap| {
+            let start = cal.next_business_day(gap.start())?;
+            let mut end = gap.end();
+            while !cal.is_business_day(end) {
+                end = end.pred_opt()?;
+            }
+            DateRange::new(start, end)
+        })
+        .collect()
+}
+
+fn gaps<I>(dates: I) -> impl Iterator<Item = DateRange>
+where
+    I: IntoIterator,
+    I::Item: Borrow<NaiveDate>,
+{
+    let mut previous: Option<NaiveDate> = None;
+    dates.into_iter().filter_map(move |date| {
+        let date = *date.borrow();
+        match previous {
+            Some(previous) if date <= previous => return None,
+            _ => {}
+        }
+        let gap =
+            previous.and_then(|previous| DateRange::new(previous.succ_opt()?, date.pred_opt()?));
+        previous = Some(date);
+        gap
+    })
+}
+
+fn inversions<I>(dates: I) -> impl Iterator<Item = Inversion>
+where
+    I: IntoIterator,
+    I::Item: Borrow<NaiveDate>,
+{
+    let mut previous: Option<NaiveDate> = None;
+    dates
+        .into_iter()
+        .enumerate()
+        .filter_map(move |(index, date)| {
+            let date = *date.borrow();
+            let inversion = previous.and_then(|previous| {
+                let kind = if date == previous {
+                    InversionKind::Duplicate
+                } else if date < previous {
+                    InversionKind::OutOfOrder
+                } else {
+                    return None;
+                };
+                Some(Inversion {
+                    index,
+                    previous,
+                    date,
+                    kind,
+                })
+            });
+            previous = Some(date);
+            inversion
+        })
+}
+
+/// Collapses the records falling within the same period of the given granularity into a single
+/// one, chosen as per the keep policy. The records returned are ordered by their period and
+/// records whose period lies beyond the supported date range are dropped.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::period::Unit;
+/// use chrono_utilities::naive::sequence::{dedup_by_period, KeepPolicy};
+///
+/// let records = vec![
+///     (NaiveDate::from_ymd_opt(2020, 3, 2).unwrap(), "a"),
+///     (NaiveDate::from_ymd_opt(2020, 2, 11).unwrap(), "b"),
+///     (NaiveDate::from_ymd_opt(2020, 2, 26).unwrap(), "c"),
+///     (NaiveDate::from_ymd_opt(2020, 2, 3).unwrap(), "d"),
+/// ];
+/// let first = dedup_by_period(records.clone(), Unit::Month, KeepPolicy::First);
+/// assert_eq!(first, vec![
+///     (NaiveDate::from_ymd_opt(2020, 2, 3).unwrap(), "d"),
+///     (NaiveDate::from_ymd_opt(2020, 3, 2).unwrap(), "a"),
+/// ]);
+/// let last = dedup_by_period(records, Unit::Month, KeepPolicy::Last);
+/// assert_eq!(last, vec![
+///     (NaiveDate::from_ymd_opt(2020, 2, 26).unwrap(), "c"),
+///     (NaiveDate::from_ymd_opt(2020, 3, 2).unwrap(), "a"),
+/// ]);
+pub fn dedup_by_period<I, T>(items: I, unit: Unit, keep: KeepPolicy) -> Vec<(NaiveDate, T)>
+where
+    I: IntoIterator<Item = (NaiveDate, T)>,
+{
+    let mut kept: BTreeMap<NaiveDate, (NaiveDate, T)> = BTreeMap::new();
+    for (date, item) in items {
+        let key = match unit.start_of(date) {
+            Some(key) => key,
+            None => continue,
+        };
+        match kept.get(&key) {
+            Some((current, _)) => {
+                let replace = match keep {
+                    KeepPolicy::First => date < *current,
+                    KeepPolicy::Last => date >= *current,
+                };
+                if replace {
+                    kept.insert(key, (date, item));
+                }
+            }
+            None => {
+                kept.insert(key, (date, item));
+            }
+        }
+    }
+    kept.into_values().collect()
+}
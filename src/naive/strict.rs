@@ -0,0 +1,191 @@
+//! Restriction of dates to a range of years, such as the 1583 to 9999 range of ISO 8601, for
+//! interop with systems rejecting the proleptic dates chrono supports
+use super::period::{ParsePeriodError, Period};
+use super::range::DateRange;
+use chrono::{Datelike, NaiveDate};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An inclusive range of accepted years
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct YearRange {
+    min: i32,
+    max: i32,
+}
+
+impl Default for YearRange {
+    fn default() -> Self {
+        YearRange::ISO_8601
+    }
+}
+
+impl YearRange {
+    /// Years 1583 to 9999, which ISO 8601 allows without agreement between the parties
+    pub const ISO_8601: YearRange = YearRange {
+        min: 1583,
+        max: 9999,
+    };
+
+    /// Creates a range of years from `min` to `max`. Returns `None` if `min` is after `max`.
+    pub fn new(min: i32, max: i32) -> Option<Self> {
+        if min <= max {
+            Some(YearRange { min, max })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first accepted year
+    #[inline]
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    /// Returns the last accepted year
+    #[inline]
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    /// Returns true if the year is accepted
+    #[inline]
+    pub fn contains(&self, year: i32) -> bool {
+        self.min <= year && year <= self.max
+    }
+
+    /// Returns the date if its year is accepted
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::strict::YearRange;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(1582, 10, 15).unwrap();
+    /// let err = YearRange::ISO_8601.check_date(d).unwrap_err();
+    /// assert_eq!(err.year(), 1582);
+    /// assert_eq!(err.to_string(), "year 1582 is outside of 1583..=9999");
+    /// let d = NaiveDate::from_ymd_opt(1583, 1, 1).unwrap();
+    /// assert_eq!(YearRange::ISO_8601.check_date(d), Ok(d));
+    pub fn check_date(&self, date: NaiveDate) -> Result<NaiveDate, YearOutOfRange> {
+        self.check_year(date.year())?;
+        Ok(date)
+    }
+
+    /// Returns the range if both its ends have accepted years
+    pub fn check_range(&self, range: DateRange) -> Result<DateRange, YearOutOfRange> {
+        self.check_date(range.start())?;
+        self.check_date(range.end())?;
+        Ok(range)
+    }
+
+    /// Returns the period if all of its dates have accepted years. Weeks straddling the first
+    /// or last accepted year are rejected.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono_utilities::naive::period::Week;
+    /// use chrono_utilities::naive::strict::YearRange;
+    ///
+    /// // The first ISO week of 2020 starts on 2019-12-30
+    /// let range = YearRange::new(2020, 2029).unwrap();
+    /// assert!(range.check_period(Week::from_isoyw(2020, 1).unwrap()).is_err());
+    /// assert!(range.check_period(Week::from_isoyw(2020, 2).unwrap()).is_ok());
+    pub fn check_period<P: Period>(&self, period: P) -> Result<P, YearOutOfRange> {
+        self.check_date(period.start())?;
+        self.check_date(period.end())?;
+        Ok(period)
+    }
+
+    /// Parses a period in its canonical form, such as `2024-W05` or `2024-03`, and checks it
+    /// as per [check_period](#method.check_period)
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono_utilities::naive::period::Month;
+    /// use chrono_utilities::naive::strict::{StrictParseError, YearRange};
+    ///
+    /// let strict = YearRange::ISO_8601;
+    /// assert_eq!(strict.parse_period::<Month>("2024-03"), Ok(Month::new(2024, 3).unwrap()));
+    /// assert!(matches!(strict.parse_period::<Month>("12024-03"), Err(StrictParseError::OutOfRange(_))));
+    /// assert!(matches!(strict.parse_period::<Month>("2024-3"), Err(StrictParseError::Invalid(_))));
+    pub fn parse_period<P>(&self, s: &str) -> Result<P, StrictParseError>
+    where
+        P: Period + FromStr<Err = ParsePeriodError>,
+    {
+        let period = s.parse().map_err(StrictParseError::Invalid)?;
+        self.check_period(period)
+            .map_err(StrictParseError::OutOfRange)
+    }
+
+    fn check_year(&self, year: i32) -> Result<(), YearOutOfRange> {
+        if self.contains(year) {
+            Ok(())
+        } else {
+            Err(YearOutOfRange { year, range: *self })
+        }
+    }
+}
+
+/// Error returned when a date falls in a year outside of the accepted range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct YearOutOfRange {
+    year: i32,
+    range: YearRange,
+}
+
+impl YearOutOfRange {
+    /// Returns the rejected year
+    #[inline]
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the range of accepted years
+    #[inline]
+    pub fn range(&self) -> YearRange {
+        self.range
+    }
+}
+
+impl fmt::Display for YearOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "year {} is outside of {}..={}",
+            self.year, self.range.min, self.range.max
+        )
+    }
+}
+
+impl Error for YearOutOfRange {}
+
+/// Error returned when parsing a period within a range of years fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StrictParseError {
+    /// The string is not a period in its canonical form
+    Invalid(ParsePeriodError),
+    /// The period falls outside of the accepted years
+    OutOfRange(YearOutOfRange),
+}
+
+impl fmt::Display for StrictParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrictParseError::Invalid(err) => err.fmt(f),
+            StrictParseError::OutOfRange(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for StrictParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StrictParseError::Invalid(err) => Some(err),
+            StrictParseError::OutOfRange(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Compact, human readable summaries of sets of dates such as `Mar 3–7, Mar 10, Apr 1–30`
+use super::interval::IntervalSet;
+use super::range::DateRange;
+use chrono::{Datelike, NaiveDate};
+use std::borrow::Borrow;
+use std::fmt::Write;
+
+/// When the years of the dates are written in a summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YearDisplay {
+    /// Never write years
+    Never,
+    /// Always write years
+    Always,
+    /// Write years only if the dates span more than one calendar year
+    WhenSpanningYears,
+}
+
+/// Options of the summaries of sets of dates. The default options write English month
+/// abbreviations before the day, as in `Mar 3–7`, join ranges with an en dash and write years
+/// only when the dates span more than one year.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::summary::{SummaryFormat, YearDisplay};
+///
+/// let dates = vec![
+///     NaiveDate::from_ymd_opt(2020, 3, 3).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 3, 4).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 3, 10).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 3, 31).unwrap(),
+///     NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(),
+/// ];
+/// let format = SummaryFormat::default();
+/// assert_eq!(format.summarize(&dates), "Mar 3–4, Mar 10, Mar 31–Apr 1");
+///
+/// let format = SummaryFormat {
+///     month_names: [
+///         "janv.", "févr.", "mars", "avr.", "mai", "juin",
+///         "juil.", "août", "sept.", "oct.", "nov.", "déc.",
+///     ],
+///     day_first: true,
+///     range_separator: "-",
+///     list_separator: " ; ",
+///     years: YearDisplay::Always,
+/// };
+/// assert_eq!(format.summarize(&dates), "3-4 mars 2020 ; 10 mars 2020 ; 31 mars-1 avr. 2020");
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SummaryFormat {
+    /// The names of the months from January to December
+    pub month_names: [&'static str; 12],
+    /// Whether the day is written before the month, as in `3 Mar`, or after it, as in `Mar 3`
+    pub day_first: bool,
+    /// The separator between the first and last dates of a range
+    pub range_separator: &'static str,
+    /// The separator between the ranges of the summary
+    pub list_separator: &'static str,
+    /// When the years of the dates are written
+    pub years: YearDisplay,
+}
+
+impl Default for SummaryFormat {
+    fn default() -> Self {
+        SummaryFormat {
+            month_names: [
+                "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+            ],
+            day_first: false,
+            range_separator: "–",
+            list_separator: ", ",
+            years: YearDisplay::WhenSpanningYears,
+        }
+    }
+}
+
+impl SummaryFormat {
+    /// Returns the summary of the dates, in which consecutive dates are collapsed into ranges
+    #[inline]
+    pub fn summarize<I>(&self, dates: I) -> String
+    where
+        I: IntoIterator,
+        I::Item: Borrow<NaiveDate>,
+    {
+        let set: IntervalSet = dates
+            .into_iter()
+            .filter_map(|date| DateRange::new(*date.borrow(), *date.borrow()))
+            .collect();
+        self.summarize_set(&set)
+    }
+
+    /// Returns the summary of the ranges of the set
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::interval::IntervalSet;
+    /// use chrono_utilities::naive::range::DateRange;
+    /// use chrono_utilities::naive::summary::SummaryFormat;
+    ///
+    /// let set: IntervalSet = vec![
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2019, 12, 30).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 2).unwrap()).unwrap(),
+    ///     DateRange::new(NaiveDate::from_ymd_opt(2020, 1, 6).unwrap(), NaiveDate::from_ymd_opt(2020, 1, 10).unwrap()).unwrap(),
+    /// ].into_iter().collect();
+    /// assert_eq!(SummaryFormat::default().summarize_set(&set), "Dec 30, 2019–Jan 2, 2020, Jan 6–10, 2020");
+    pub fn summarize_set(&self, set: &IntervalSet) -> String {
+        let with_years = match self.years {
+            YearDisplay::Never => false,
+            YearDisplay::Always => true,
+            YearDisplay::WhenSpanningYears => match (set.iter().next(), set.iter().last()) {
+                (Some(first), Some(last)) => first.start().year() != last.end().year(),
+                _ => false,
+            },
+        };
+        let mut summary = String::new();
+        for (i, range) in set.iter().enumerate() {
+            if i > 0 {
+                summary.push_str(self.list_separator);
+            }
+            self.write_range(&mut summary, range, with_years);
+        }
+        summary
+    }
+
+    /// Writes the range, leaving out the parts of its last date shared with its first one
+    fn write_range(&self, out: &mut String, range: &DateRange, with_years: bool) {
+        let (start, end) = (range.start(), range.end());
+        let same_year = start.year() == end.year();
+        let same_month = same_year && start.month() == end.month();
+        if start == end {
+            self.write_date(out, start, with_years);
+        } else if same_month && self.day_first {
+            let _ = write!(out, "{}{}", start.day(), self.range_separator);
+            self.write_date(out, end, with_years);
+        } else if same_month {
+            self.write_date(out, start, false);
+            let _ = write!(out, "{}{}", self.range_separator, end.day());
+            self.write_year(out, end, with_years);
+        } else {
+            self.write_date(out, start, with_years && !same_year);
+            out.push_str(self.range_separator);
+            self.write_date(out, end, with_years);
+        }
+    }
+
+    /// Writes the day and month of the date and, if asked, its year
+    fn write_date(&self, out: &mut String, date: NaiveDate, with_year: bool) {
+        let month = self.month_names[date.month0() as usize];
+        let _ = if self.day_first {
+            write!(out, "{} {}", date.day(), month)
+        } else {
+            write!(out, "{} {}", month, date.day())
+        };
+        self.write_year(out, date, with_year);
+    }
+
+    /// Writes the year of the date if asked, as in `Mar 3, 2020` or `3 Mar 2020`
+    fn write_year(&self, out: &mut String, date: NaiveDate, with_year: bool) {
+        if !with_year {
+            return;
+        }
+        let _ = if self.day_first {
+            write!(out, " {}", date.year())
+        } else {
+            write!(out, ", {}", date.year())
+        };
+    }
+}
+
+/// Returns the summary of the dates with the default options of
+/// [SummaryFormat](struct.SummaryFormat.html)
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::NaiveDate;
+/// use chrono_utilities::naive::range::DateRange;
+/// use chrono_utilities::naive::summary::summarize;
+///
+/// let march = DateRange::new(NaiveDate::from_ymd_opt(2020, 3, 3).unwrap(), NaiveDate::from_ymd_opt(2020, 3, 7).unwrap()).unwrap();
+/// let mut dates: Vec<NaiveDate> = march.iter().collect();
+/// dates.push(NaiveDate::from_ymd_opt(2020, 3, 10).unwrap());
+/// dates.extend(DateRange::new(NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 4, 30).unwrap()).unwrap().iter());
+/// assert_eq!(summarize(&dates), "Mar 3–7, Mar 10, Apr 1–30");
+#[inline]
+pub fn summarize<I>(dates: I) -> String
+where
+    I: IntoIterator,
+    I::Item: Borrow<NaiveDate>,
+{
+    SummaryFormat::default().summarize(dates)
+}
+
+/// Returns the summary of the ranges of the set with the default options of
+/// [SummaryFormat](struct.SummaryFormat.html)
+#[inline]
+pub fn summarize_set(set: &IntervalSet) -> String {
+    SummaryFormat::default().summarize_set(set)
+}
@@ -0,0 +1,175 @@
+//! Timelines of events occupying intervals of datetimes
+use super::interval::{free_slots, DateTimeInterval};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use std::iter::FromIterator;
+use std::slice;
+
+/// An event occupying an interval of datetimes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Event<T> {
+    /// The interval occupied by the event
+    pub interval: DateTimeInterval,
+    /// The value attached to the event
+    pub value: T,
+}
+
+/// A set of possibly overlapping events, kept in chronological order. Recurring events are
+/// inserted as their individual occurrences.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, TimeDelta};
+/// use chrono_utilities::naive::interval::DateTimeInterval;
+/// use chrono_utilities::naive::timeline::Timeline;
+///
+/// let at = |d, h| NaiveDate::from_ymd_opt(2020, 6, d).unwrap().and_hms_opt(h, 0, 0).unwrap();
+/// let mut timeline = Timeline::new();
+/// timeline.insert(DateTimeInterval::new(at(1, 9), at(1, 11)).unwrap(), "review");
+/// timeline.insert(DateTimeInterval::new(at(1, 10), at(1, 12)).unwrap(), "interview");
+/// // A daily stand-up, inserted as its occurrences
+/// timeline.extend((1..=5).map(|d| (DateTimeInterval::new(at(d, 12), at(d, 13)).unwrap(), "stand-up")));
+///
+/// let day = NaiveDate::from_ymd_opt(2020, 6, 1).unwrap();
+/// assert_eq!(timeline.occupancy_on(day), TimeDelta::hours(4));
+/// let conflicts = timeline.conflicts();
+/// assert_eq!(conflicts.len(), 1);
+/// assert_eq!((conflicts[0].0.value, conflicts[0].1.value), ("review", "interview"));
+/// assert_eq!(timeline.next_free(at(1, 9), TimeDelta::hours(2)), DateTimeInterval::new(at(1, 13), at(1, 15)));
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Timeline<T> {
+    events: Vec<Event<T>>,
+}
+
+impl<T> Default for Timeline<T> {
+    fn default() -> Self {
+        Timeline::new()
+    }
+}
+
+impl<T> Timeline<T> {
+    /// Creates an empty timeline
+    #[inline]
+    pub fn new() -> Self {
+        Timeline { events: Vec::new() }
+    }
+
+    /// Returns true if the timeline holds no events
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns the number of events in the timeline
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns an iterator over the events ordered by their start
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, Event<T>> {
+        self.events.iter()
+    }
+
+    /// Adds an event to the timeline, after the events starting no later than it
+    pub fn insert(&mut self, interval: DateTimeInterval, value: T) {
+        let index = self
+            .events
+            .partition_point(|event| event.interval.start() <= interval.start());
+        self.events.insert(index, Event { interval, value });
+    }
+
+    /// Returns the events overlapping the interval, ordered by their start
+    pub fn overlapping(&self, interval: DateTimeInterval) -> impl Iterator<Item = &Event<T>> {
+        let candidates = self
+            .events
+            .partition_point(|event| event.interval.start() < interval.end());
+        self.events[..candidates]
+            .iter()
+            .filter(move |event| event.interval.overlaps(&interval))
+    }
+
+    /// Returns the time of the date occupied by at least one event. Time occupied by several
+    /// events at once is only counted once.
+    pub fn occupancy_on(&self, date: NaiveDate) -> TimeDelta {
+        let day = match day_of(date) {
+            Some(day) => day,
+            None => return TimeDelta::zero(),
+        };
+        let busy: Vec<DateTimeInterval> =
+            self.overlapping(day).map(|event| event.interval).collect();
+        let free = free_slots(&busy, day, TimeDelta::zero())
+            .iter()
+            .fold(TimeDelta::zero(), |total, slot| total + slot.duration());
+        day.duration() - free
+    }
+
+    /// Returns every pair of overlapping events, ordered by the start of the earlier event of
+    /// each pair, then by the start of the later one
+    pub fn conflicts(&self) -> Vec<(&Event<T>, &Event<T>)> {
+        let mut conflicts = Vec::new();
+        for (index, event) in self.events.iter().enumerate() {
+            for other in self.events[index + 1..].iter() {
+                if other.interval.start() >= event.interval.end() {
+                    break;
+                }
+                conflicts.push((event, other));
+            }
+        }
+        conflicts
+    }
+
+    /// Returns the earliest interval lasting `duration`, starting no earlier than `after`, which
+    /// overlaps none of the events. Returns `None` if the duration is not positive or the
+    /// interval would end past the range supported by chrono.
+    pub fn next_free(&self, after: NaiveDateTime, duration: TimeDelta) -> Option<DateTimeInterval> {
+        if duration <= TimeDelta::zero() {
+            return None;
+        }
+        let mut free_from = after;
+        let mut free_until = after.checked_add_signed(duration)?;
+        for event in self.events.iter() {
+            if event.interval.start() >= free_until {
+                break;
+            }
+            if event.interval.end() > free_from {
+                free_from = event.interval.end();
+                free_until = free_from.checked_add_signed(duration)?;
+            }
+        }
+        DateTimeInterval::new(free_from, free_until)
+    }
+}
+
+impl<T> FromIterator<(DateTimeInterval, T)> for Timeline<T> {
+    fn from_iter<I: IntoIterator<Item = (DateTimeInterval, T)>>(iter: I) -> Self {
+        let mut timeline = Timeline::new();
+        timeline.extend(iter);
+        timeline
+    }
+}
+
+impl<T> Extend<(DateTimeInterval, T)> for Timeline<T> {
+    fn extend<I: IntoIterator<Item = (DateTimeInterval, T)>>(&mut self, iter: I) {
+        for (interval, value) in iter {
+            self.insert(interval, value);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Timeline<T> {
+    type Item = &'a Event<T>;
+    type IntoIter = slice::Iter<'a, Event<T>>;
+
+    fn into_iter(self) -> slice::Iter<'a, Event<T>> {
+        self.iter()
+    }
+}
+
+/// Returns the interval spanning the whole date
+fn day_of(date: NaiveDate) -> Option<DateTimeInterval> {
+    let start = date.and_time(NaiveTime::MIN);
+    let end = date.succ_opt()?.and_time(NaiveTime::MIN);
+    DateTimeInterval::new(start, end)
+}
@@ -0,0 +1,391 @@
+//! Occurrences of weekdays within months, quarters and years, such as the third Tuesday of
+//! March or the last Friday of the quarter
+use super::business::TieBreak;
+use super::days_in_month;
+use super::period::YearDivisions;
+use super::range::DateRange;
+use super::DateTransitions;
+use chrono::{Datelike, NaiveDate, TimeDelta, Weekday};
+
+/// Returns the `n`th occurrence, starting from 1, of the weekday in the given month. Returns
+/// `None` if `n` is zero, the month has fewer occurrences of the weekday or the month is
+/// invalid.
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, Weekday};
+/// use chrono_utilities::naive::weekday::nth_weekday_of_month;
+///
+/// assert_eq!(nth_weekday_of_month(2025, 3, Weekday::Tue, 3), NaiveDate::from_ymd_opt(2025, 3, 18));
+/// assert_eq!(nth_weekday_of_month(2025, 3, Weekday::Mon, 5), NaiveDate::from_ymd_opt(2025, 3, 31));
+/// assert_eq!(nth_weekday_of_month(2025, 3, Weekday::Tue, 5), None);
+pub fn nth_weekday_of_month(year: i32, month: u32, weekday: Weekday, n: u32) -> Option<NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let offset = days_until_weekday(first.weekday(), weekday);
+    let day = n.checked_sub(1)?.checked_mul(7)?.checked_add(1 + offset)?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// Returns the last occurrence of the weekday in the given month, or `None` if the month is
+/// invalid
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, Weekday};
+/// use chrono_utilities::naive::weekday::last_weekday_of_month;
+///
+/// assert_eq!(last_weekday_of_month(2020, 5, Weekday::Mon), NaiveDate::from_ymd_opt(2020, 5, 25));
+/// assert_eq!(last_weekday_of_month(2020, 2, Weekday::Sat), NaiveDate::from_ymd_opt(2020, 2, 29));
+pub fn last_weekday_of_month(year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+    if !(1..=12).contains(&month) {
+        return None;
+    }
+    last_weekday_on_or_before(
+        NaiveDate::from_ymd_opt(year, month, days_in_month(year, month))?,
+        weekday,
+    )
+}
+
+/// Returns the last occurrence of the weekday falling on or before the date
+#[inline]
+fn last_weekday_on_or_before(date: NaiveDate, weekday: Weekday) -> Option<NaiveDate> {
+    let days = days_until_weekday(weekday, date.weekday());
+    date.checked_sub_signed(TimeDelta::days(days as i64))
+}
+
+/// Returns the number of occurrences of the weekday within the range, counting both ends
+///
+/// # Example
+///
+/// ~~~~
+/// use chrono::{NaiveDate, Weekday};
+/// use chrono_utilities::naive::range::DateRange;
+/// use chrono_utilities::naive::weekday::count_weekday;
+///
+/// let q3 = DateRange::new(NaiveDate::from_ymd_opt(2020, 7, 1).unwrap(), NaiveDate::from_ymd_opt(2020, 9, 30).unwrap()).unwrap();
+/// assert_eq!(count_weekday(&q3, Weekday::Mon), 13);
+/// assert_eq!(count_weekday(&q3, Weekday::Wed), 14);
+pub fn count_weekday(range: &DateRange, weekday: Weekday) -> i64 {
+    count_from(range.start(), range.num_days(), weekday)
+}
+
+/// Returns the number of occurrences of the weekday within the given number of days from the
+/// start date
+#[inline]
+fn count_from(start: NaiveDate, days: i64, weekday: Weekday) -> i64 {
+    let offset = days_until_weekday(start.weekday(), weekday) as i64;
+    if days > offset {
+        (days - offset - 1) / 7 + 1
+    } else {
+        0
+    }
+}
+
+/// Returns the number of days from a weekday to the next occurrence of the other, zero if both
+/// are the same
+#[inline]
+fn days_until_weekday(from: Weekday, to: Weekday) -> u32 {
+    (7 + to.num_days_from_monday() - from.num_days_from_monday()) % 7
+}
+
+/// Common set of methods locating occurrences of weekdays around a date
+pub trait WeekdayOccurrences: Sized {
+    /// Returns the `n`th occurrence, starting from 1, of the weekday in the month of the date
+    fn nth_weekday_in_same_month(&self, weekday: Weekday, n: u32) -> Option<Self>;
+
+    /// Returns the last occurrence of the weekday in the month of the date
+    fn last_weekday_of_month(&self, weekday: Weekday) -> Option<Self>;
+
+    /// Returns the last occurrence of the weekday in the quarter of the date
+    fn last_weekday_of_quarter(&self, weekday: Weekday) -> Option<Self>;
+
+    /// Returns the last occurrence of the weekday in the year of the date
+    fn last_weekday_of_year(&self, weekday: Weekday) -> Option<Self>;
+
+    /// Returns which occurrence of its weekday in its month the date is, starting from 1
+    fn weekday_ordinal_in_month(&self) -> u32;
+
+    /// Returns true if the date is the last occurrence of its weekday in its month
+    fn is_last_weekday_occurrence_in_month(&self) -> bool;
+
+    /// Returns the number of occurrences of the weekday in the month of the date
+    fn count_weekday_in_month(&self, weekday: Weekday) -> u32;
+
+    /// Returns the number of occurrences of the weekday in the quarter of the date
+    fn count_weekday_in_quarter(&self, weekday: Weekday) -> u32;
+
+    /// Returns the number of occurrences of the weekday in the year of the date
+    fn count_weekday_in_year(&self, weekday: Weekday) -> u32;
+
+    /// Returns the first occurrence of the weekday falling strictly after the date
+    fn next_weekday(&self, weekday: Weekday) -> Option<Self>;
+
+    /// Returns the first occurrence of the weekday falling on or after the date
+    fn next_weekday_or_same(&self, weekday: Weekday) -> Option<Self>;
+
+    /// Returns the last occurrence of the weekday falling strictly before the date
+    fn previous_weekday(&self, weekday: Weekday) -> Option<Self>;
+
+    /// Returns the last occurrence of the weekday falling on or before the date
+    fn previous_weekday_or_same(&self, weekday: Weekday) -> Option<Self>;
+
+    /// Returns the occurrence of the weekday closest to the date
+    fn nearest_weekday(&self, weekday: Weekday) -> Option<Self>;
+}
+
+impl WeekdayOccurrences for NaiveDate {
+    /// Returns the `n`th occurrence of the weekday in the month of the date, or `None` if the
+    /// month has fewer occurrences
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 11, 30).unwrap();
+    /// // Thanksgiving
+    /// assert_eq!(d.nth_weekday_in_same_month(Weekday::Thu, 4), NaiveDate::from_ymd_opt(2020, 11, 26));
+    #[inline]
+    fn nth_weekday_in_same_month(&self, weekday: Weekday, n: u32) -> Option<Self> {
+        nth_weekday_of_month(self.year(), self.month(), weekday, n)
+    }
+
+    /// Returns the last occurrence of the weekday in the month of the date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2021, 7, 4).unwrap();
+    /// assert_eq!(d.last_weekday_of_month(Weekday::Fri), NaiveDate::from_ymd_opt(2021, 7, 30));
+    #[inline]
+    fn last_weekday_of_month(&self, weekday: Weekday) -> Option<Self> {
+        last_weekday_of_month(self.year(), self.month(), weekday)
+    }
+
+    /// Returns the last occurrence of the weekday in the quarter of the date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2021, 7, 4).unwrap();
+    /// assert_eq!(d.last_weekday_of_quarter(Weekday::Fri), NaiveDate::from_ymd_opt(2021, 9, 24));
+    #[inline]
+    fn last_weekday_of_quarter(&self, weekday: Weekday) -> Option<Self> {
+        last_weekday_of_month(self.year(), self.quarter_of_year() * 3, weekday)
+    }
+
+    /// Returns the last occurrence of the weekday in the year of the date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2021, 7, 4).unwrap();
+    /// assert_eq!(d.last_weekday_of_year(Weekday::Fri), NaiveDate::from_ymd_opt(2021, 12, 31));
+    #[inline]
+    fn last_weekday_of_year(&self, weekday: Weekday) -> Option<Self> {
+        last_weekday_of_month(self.year(), 12, weekday)
+    }
+
+    /// Returns which occurrence of its weekday in its month the date is
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// // The second Friday of October 2020
+    /// let d = NaiveDate::from_ymd_opt(2020, 10, 9).unwrap();
+    /// assert_eq!(d.weekday_ordinal_in_month(), 2);
+    /// assert_eq!(NaiveDate::from_ymd_opt(2020, 10, 29).unwrap().weekday_ordinal_in_month(), 5);
+    #[inline]
+    fn weekday_ordinal_in_month(&self) -> u32 {
+        self.day0() / 7 + 1
+    }
+
+    /// Returns true if the date is the last occurrence of its weekday in its month
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::NaiveDate;
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// assert!(NaiveDate::from_ymd_opt(2020, 10, 25).unwrap().is_last_weekday_occurrence_in_month());
+    /// assert!(!NaiveDate::from_ymd_opt(2020, 10, 24).unwrap().is_last_weekday_occurrence_in_month());
+    #[inline]
+    fn is_last_weekday_occurrence_in_month(&self) -> bool {
+        self.day() + 7 > days_in_month(self.year(), self.month())
+    }
+
+    /// Returns the number of occurrences of the weekday in the month of the date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 2, 10).unwrap();
+    /// assert_eq!(d.count_weekday_in_month(Weekday::Sat), 5);
+    /// assert_eq!(d.count_weekday_in_month(Weekday::Mon), 4);
+    #[inline]
+    fn count_weekday_in_month(&self, weekday: Weekday) -> u32 {
+        let start = *self - TimeDelta::days(self.day0() as i64);
+        count_from(start, self.last_day_of_month() as i64, weekday) as u32
+    }
+
+    /// Returns the number of occurrences of the weekday in the quarter of the date
+    #[inline]
+    fn count_weekday_in_quarter(&self, weekday: Weekday) -> u32 {
+        let first_month = self.quarter_of_year() * 3 - 2;
+        let days_before: u32 = (first_month..self.month())
+            .map(|month| days_in_month(self.year(), month))
+            .sum();
+        let start = *self - TimeDelta::days((days_before + self.day0()) as i64);
+        count_from(start, self.days_in_quarter() as i64, weekday) as u32
+    }
+
+    /// Returns the number of occurrences of the weekday in the year of the date
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// let d = NaiveDate::from_ymd_opt(2020, 6, 1).unwrap();
+    /// assert_eq!(d.count_weekday_in_year(Weekday::Wed), 53);
+    /// assert_eq!(d.count_weekday_in_year(Weekday::Fri), 52);
+    #[inline]
+    fn count_weekday_in_year(&self, weekday: Weekday) -> u32 {
+        let start = *self - TimeDelta::days(self.ordinal0() as i64);
+        count_from(start, self.days_in_year() as i64, weekday) as u32
+    }
+
+    /// Returns the first occurrence of the weekday falling strictly after the date, a week
+    /// later if the date is already on that weekday
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// // Friday
+    /// let d = NaiveDate::from_ymd_opt(2020, 5, 1).unwrap();
+    /// assert_eq!(d.next_weekday(Weekday::Mon), NaiveDate::from_ymd_opt(2020, 5, 4));
+    /// assert_eq!(d.next_weekday(Weekday::Fri), NaiveDate::from_ymd_opt(2020, 5, 8));
+    /// assert_eq!(d.next_weekday_or_same(Weekday::Fri), Some(d));
+    #[inline]
+    fn next_weekday(&self, weekday: Weekday) -> Option<Self> {
+        self.succ_opt()?.next_weekday_or_same(weekday)
+    }
+
+    /// Returns the first occurrence of the weekday falling on or after the date
+    #[inline]
+    fn next_weekday_or_same(&self, weekday: Weekday) -> Option<Self> {
+        let days = days_until_weekday(self.weekday(), weekday);
+        self.checked_add_signed(TimeDelta::days(days as i64))
+    }
+
+    /// Returns the last occurrence of the weekday falling strictly before the date, a week
+    /// earlier if the date is already on that weekday
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// // Friday
+    /// let d = NaiveDate::from_ymd_opt(2020, 5, 1).unwrap();
+    /// assert_eq!(d.previous_weekday(Weekday::Mon), NaiveDate::from_ymd_opt(2020, 4, 27));
+    /// assert_eq!(d.previous_weekday(Weekday::Fri), NaiveDate::from_ymd_opt(2020, 4, 24));
+    /// assert_eq!(d.previous_weekday_or_same(Weekday::Fri), Some(d));
+    #[inline]
+    fn previous_weekday(&self, weekday: Weekday) -> Option<Self> {
+        self.pred_opt()?.previous_weekday_or_same(weekday)
+    }
+
+    /// Returns the last occurrence of the weekday falling on or before the date
+    #[inline]
+    fn previous_weekday_or_same(&self, weekday: Weekday) -> Option<Self> {
+        last_weekday_on_or_before(*self, weekday)
+    }
+
+    /// Returns the occurrence of the weekday closest to the date, which is the date itself if
+    /// it falls on that weekday. There are never ties as the previous and next occurrences are
+    /// a week apart.
+    ///
+    /// # Example
+    ///
+    /// ~~~~
+    /// use chrono::{NaiveDate, Weekday};
+    /// use chrono_utilities::naive::weekday::WeekdayOccurrences;
+    ///
+    /// // Friday
+    /// let d = NaiveDate::from_ymd_opt(2020, 5, 1).unwrap();
+    /// assert_eq!(d.nearest_weekday(Weekday::Mon), NaiveDate::from_ymd_opt(2020, 5, 4));
+    /// assert_eq!(d.nearest_weekday(Weekday::Tue), NaiveDate::from_ymd_opt(2020, 4, 28));
+    fn nearest_weekday(&self, weekday: Weekday) -> Option<Self> {
+        match (
+            self.previous_weekday_or_same(weekday),
+            self.next_weekday_or_same(weekday),
+        ) {
+            (Some(prev), Some(next)) => Some(TieBreak::Earlier.nearest(*self, prev, next)),
+            (prev, next) => prev.or(next),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count_weekday, WeekdayOccurrences};
+    use crate::naive::range::DateRange;
+    use chrono::{Datelike, NaiveDate, TimeDelta, Weekday};
+
+    #[test]
+    fn test_counts_match_iteration() {
+        let weekdays = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ];
+        let start = NaiveDate::from_ymd_opt(2019, 11, 20).unwrap();
+        for days in 0..40 {
+            let end = start + TimeDelta::days(days);
+            let range = DateRange::new(start, end).unwrap();
+            for weekday in &weekdays {
+                let expected = range.iter().filter(|d| d.weekday() == *weekday).count();
+                assert_eq!(count_weekday(&range, *weekday), expected as i64);
+            }
+        }
+        let d = NaiveDate::from_ymd_opt(2020, 5, 17).unwrap();
+        let quarter = DateRange::new(
+            NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2020, 6, 30).unwrap(),
+        )
+        .unwrap();
+        for weekday in &weekdays {
+            let in_quarter = count_weekday(&quarter, *weekday) as u32;
+            assert_eq!(d.count_weekday_in_quarter(*weekday), in_quarter);
+        }
+    }
+}